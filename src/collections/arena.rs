@@ -0,0 +1,237 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+/// A stable reference to a value stored in an `Arena<T>`. A handle is an index into the arena
+/// plus the generation of the slot when the value was inserted, so a handle to a removed value
+/// never resolves to whatever is stored in its slot afterwards.
+pub struct Handle<T> {
+    index: u32,
+    generation: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+
+    /// Returns the slot index of the handle, which is unique among the live values of the
+    /// arena and can be used to index side tables.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns the generation of the handle, incremented every time its slot is reused.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Handle<T> {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Handle<T>) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Slot<T> {
+    Occupied { generation: u32, value: T },
+    Free { generation: u32, next_free: Option<u32> },
+}
+
+/// Slab storage handing out generational `Handle`s. Inserting and removing are O(1), removed
+/// slots are reused, and the values are stored contiguously, so iterating over them is cache
+/// friendly.
+///
+/// # Examples
+/// ```
+/// use candle::collections::Arena;
+/// let mut bodies = Arena::new();
+/// let ball = bodies.insert("ball");
+/// let crate_ = bodies.insert("crate");
+/// assert_eq!(bodies[ball], "ball");
+///
+/// assert_eq!(bodies.remove(ball), Some("ball"));
+/// assert_eq!(bodies.get(ball), None);
+///
+/// // The slot is reused, but the old handle stays invalid.
+/// let rock = bodies.insert("rock");
+/// assert_eq!(rock.index(), ball.index());
+/// assert_eq!(bodies.get(ball), None);
+/// assert_eq!(bodies.len(), 2);
+/// assert_eq!(bodies[crate_], "crate");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    first_free: Option<u32>,
+    len: usize,
+}
+
+impl<T> Arena<T> {
+
+    /// Allocates a new, empty Arena.
+    pub fn new() -> Arena<T> {
+        Arena::with_capacity(0)
+    }
+
+    /// Allocates a new, empty Arena with room for `capacity` values before reallocating.
+    pub fn with_capacity(capacity: usize) -> Arena<T> {
+        Arena { slots: Vec::with_capacity(capacity), first_free: None, len: 0 }
+    }
+
+    /// Returns the number of values in the arena.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the arena holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Stores `value` and returns its handle, reusing the most recently freed slot if any.
+    pub fn insert(&mut self, value: T) -> Handle<T> {
+        self.len += 1;
+        match self.first_free {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                let (generation, next_free) = match *slot {
+                    Slot::Free { generation, next_free } => (generation, next_free),
+                    Slot::Occupied { .. } => unreachable!("the free list points to an occupied slot"),
+                };
+                *slot = Slot::Occupied { generation, value };
+                self.first_free = next_free;
+                Handle { index, generation, marker: PhantomData }
+            }
+            None => {
+                let index = self.slots.len() as u32;
+                self.slots.push(Slot::Occupied { generation: 0, value });
+                Handle { index, generation: 0, marker: PhantomData }
+            }
+        }
+    }
+
+    /// Removes and returns the value of `handle`, or `None` if it was already removed.
+    pub fn remove(&mut self, handle: Handle<T>) -> Option<T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        let free = Slot::Free { generation: handle.generation.wrapping_add(1), next_free: self.first_free };
+        let slot = ::std::mem::replace(&mut self.slots[handle.index as usize], free);
+        self.first_free = Some(handle.index);
+        self.len -= 1;
+        match slot {
+            Slot::Occupied { value, .. } => Some(value),
+            Slot::Free { .. } => None,
+        }
+    }
+
+    /// Returns true if `handle` refers to a value in the arena.
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Returns the value of `handle`, or `None` if it was removed.
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        match self.slots.get(handle.index as usize) {
+            Some(&Slot::Occupied { generation, ref value }) if generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of `handle` mutably, or `None` if it was removed.
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(&mut Slot::Occupied { generation, ref mut value }) if generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Removes every value, invalidating all handles. Slots are kept for reuse.
+    pub fn clear(&mut self) {
+        for index in 0..self.slots.len() {
+            if let Slot::Occupied { generation, .. } = self.slots[index] {
+                self.slots[index] = Slot::Free { generation: generation.wrapping_add(1), next_free: self.first_free };
+                self.first_free = Some(index as u32);
+            }
+        }
+        self.len = 0;
+    }
+
+    /// Iterates over the handles and values in slot order.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::collections::Arena;
+    /// let mut arena = Arena::new();
+    /// let a = arena.insert(1);
+    /// let b = arena.insert(2);
+    /// let c = arena.insert(3);
+    /// arena.remove(b);
+    /// let items: Vec<_> = arena.iter().collect();
+    /// assert_eq!(items, vec![(a, &1), (c, &3)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| match *slot {
+            Slot::Occupied { generation, ref value } => {
+                Some((Handle { index: index as u32, generation, marker: PhantomData }, value))
+            }
+            Slot::Free { .. } => None,
+        })
+    }
+
+    /// Iterates mutably over the handles and values in slot order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(index, slot)| match *slot {
+            Slot::Occupied { generation, ref mut value } => {
+                Some((Handle { index: index as u32, generation, marker: PhantomData }, value))
+            }
+            Slot::Free { .. } => None,
+        })
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Arena<T> {
+        Arena::new()
+    }
+}
+
+impl<T> Index<Handle<T>> for Arena<T> {
+    type Output = T;
+
+    /// Returns the value of `handle`, panicking if it was removed.
+    fn index(&self, handle: Handle<T>) -> &T {
+        self.get(handle).expect("handle refers to a removed value")
+    }
+}
+
+impl<T> IndexMut<Handle<T>> for Arena<T> {
+    /// Returns the value of `handle` mutably, panicking if it was removed.
+    fn index_mut(&mut self, handle: Handle<T>) -> &mut T {
+        self.get_mut(handle).expect("handle refers to a removed value")
+    }
+}
@@ -0,0 +1,3 @@
+mod arena;
+
+pub use self::arena::{Arena, Handle};
@@ -0,0 +1,147 @@
+use math::Vec3;
+use geometry::sphere::Sphere;
+
+/// An axis-aligned box in 3D spanning from its `min` corner to its `max` corner. Points on the
+/// faces belong to the box.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Aabb3, Sphere};
+/// use candle::math::Vec3;
+/// let room = Aabb3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 3.0, 4.0));
+/// let crate_ = Aabb3::new(Vec3::new(3.0, 0.0, 3.0), Vec3::new(5.0, 1.0, 5.0));
+/// assert!(room.intersects(&crate_));
+/// assert!(!room.contains_aabb(&crate_));
+/// assert_eq!(room.union(&crate_).max, Vec3::new(5.0, 3.0, 5.0));
+/// assert!(room.intersects_sphere(&Sphere::new(Vec3::new(5.0, 1.0, 1.0), 1.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb3 {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb3 {
+
+    /// Allocates a new Aabb3 between two opposite corners, given in any order.
+    pub fn new(a: Vec3, b: Vec3) -> Aabb3 {
+        Aabb3 { min: a.min(&b), max: a.max(&b) }
+    }
+
+    /// Allocates a new Aabb3 centered on `center` with the given half extents along each axis,
+    /// whose absolute values are used.
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Aabb3 {
+        let half = Vec3::new(half_extents.x.abs(), half_extents.y.abs(), half_extents.z.abs());
+        Aabb3 { min: center - half, max: center + half }
+    }
+
+    /// Allocates the smallest Aabb3 containing all the points, or returns `None` if there are
+    /// none.
+    pub fn from_points(points: &[Vec3]) -> Option<Aabb3> {
+        let first = *points.first()?;
+        let mut aabb = Aabb3 { min: first, max: first };
+        for point in &points[1..] {
+            aabb.include(point);
+        }
+        Some(aabb)
+    }
+
+    /// Returns the center of the box.
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns the extent of the box along each axis.
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Returns the volume of the box.
+    pub fn volume(&self) -> f64 {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    /// Returns true if `point` lies inside or on the box.
+    pub fn contains(&self, point: &Vec3) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x &&
+            point.y >= self.min.y && point.y <= self.max.y &&
+            point.z >= self.min.z && point.z <= self.max.z
+    }
+
+    /// Returns true if `other` lies entirely inside the box.
+    pub fn contains_aabb(&self, other: &Aabb3) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Returns true if the boxes overlap or touch.
+    pub fn intersects(&self, other: &Aabb3) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x &&
+            self.min.y <= other.max.y && other.min.y <= self.max.y &&
+            self.min.z <= other.max.z && other.min.z <= self.max.z
+    }
+
+    /// Returns true if the box overlaps or touches the sphere.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains(&self.closest_point(&sphere.center))
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Aabb3) -> Aabb3 {
+        Aabb3 { min: self.min.min(&other.min), max: self.max.max(&other.max) }
+    }
+
+    /// Grows the box just enough to contain `point`.
+    pub fn include(&mut self, point: &Vec3) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Returns the point of the box closest to `point`, which is `point` itself if it is inside.
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        Vec3::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        )
+    }
+
+    /// Returns the smallest parameter `t >= 0` at which the ray `origin + direction * t` enters
+    /// the box, or `None` if it misses. Rays starting inside hit at zero. Uses the slab method.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Aabb3;
+    /// use candle::math::Vec3;
+    /// let aabb = Aabb3::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    /// let origin = Vec3::new(-5.0, 0.0, 0.0);
+    /// assert_eq!(aabb.intersect_ray(&origin, &Vec3::new(2.0, 0.0, 0.0)), Some(2.0));
+    /// assert_eq!(aabb.intersect_ray(&origin, &Vec3::new(-1.0, 0.0, 0.0)), None);
+    /// assert_eq!(aabb.intersect_ray(&origin, &Vec3::new(1.0, 1.0, 0.0)), None);
+    /// assert_eq!(aabb.intersect_ray(&Vec3::ZERO, &Vec3::new(0.0, 0.0, 1.0)), Some(0.0));
+    /// ```
+    pub fn intersect_ray(&self, origin: &Vec3, direction: &Vec3) -> Option<f64> {
+        let (mut enter, mut exit) = (0.0, f64::INFINITY);
+        let slabs = [
+            (origin.x, direction.x, self.min.x, self.max.x),
+            (origin.y, direction.y, self.min.y, self.max.y),
+            (origin.z, direction.z, self.min.z, self.max.z),
+        ];
+        for &(start, speed, low, high) in &slabs {
+            if speed == 0.0 {
+                // Parallel to the slab: the ray is either always or never between its faces.
+                if start < low || start > high {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = ((low - start) / speed, (high - start) / speed);
+            enter = t0.min(t1).max(enter);
+            exit = t0.max(t1).min(exit);
+            if enter > exit {
+                return None;
+            }
+        }
+        Some(enter)
+    }
+}
@@ -0,0 +1,138 @@
+use std::f64::consts::PI;
+use math::Vec2;
+use geometry::rect::Rect;
+use geometry::polyline::Polyline;
+
+/// The smallest tolerance `Arc::flatten` works to, relative to the radius, which keeps the
+/// number of segments bounded.
+const MIN_TOLERANCE: f64 = 1e-6;
+
+/// The direction in which an arc travels from its start angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcDirection {
+    Counterclockwise,
+    Clockwise,
+}
+
+/// A circular arc around `center`, starting at the angle `start` (in radians, from the positive
+/// x axis) and turning by `sweep` radians: counterclockwise if positive and clockwise if
+/// negative.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::PI;
+/// use candle::geometry::{Arc, ArcDirection};
+/// use candle::math::{Vec2, ApproxEq};
+/// let arc = Arc::from_angles(Vec2::new(0.0, 0.0), 2.0, 0.0, PI / 2.0, ArcDirection::Clockwise);
+/// assert!(arc.sweep.approx_eq(-1.5 * PI));
+/// assert!(arc.length().approx_eq(3.0 * PI));
+/// let eps = Vec2::new(1e-12, 1e-12);
+/// assert!(arc.point_at(1.0 / 3.0).approx_eq_eps(Vec2::new(0.0, -2.0), eps));
+/// assert!(arc.bounds().min.approx_eq_eps(Vec2::new(-2.0, -2.0), eps));
+/// assert!(arc.bounds().max.approx_eq_eps(Vec2::new(2.0, 2.0), eps));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    pub center: Vec2,
+    pub radius: f64,
+    pub start: f64,
+    pub sweep: f64,
+}
+
+impl Arc {
+
+    /// Allocates a new Arc from its start angle and signed sweep, using the absolute value of
+    /// `radius`.
+    pub fn new(center: Vec2, radius: f64, start: f64, sweep: f64) -> Arc {
+        Arc { center, radius: radius.abs(), start, sweep }
+    }
+
+    /// Allocates a new Arc going from the angle `start` to the angle `end` in the given
+    /// direction, turning by less than a full circle. Equal angles give an empty arc.
+    pub fn from_angles(center: Vec2, radius: f64, start: f64, end: f64, direction: ArcDirection) -> Arc {
+        let sweep = match direction {
+            ArcDirection::Counterclockwise => (end - start).rem_euclid(2.0 * PI),
+            ArcDirection::Clockwise => -(start - end).rem_euclid(2.0 * PI),
+        };
+        Arc::new(center, radius, start, sweep)
+    }
+
+    /// Returns the angle at which the arc ends.
+    pub fn end(&self) -> f64 {
+        self.start + self.sweep
+    }
+
+    /// Returns the direction in which the arc turns. Empty arcs are counterclockwise.
+    pub fn direction(&self) -> ArcDirection {
+        if self.sweep < 0.0 { ArcDirection::Clockwise } else { ArcDirection::Counterclockwise }
+    }
+
+    /// Returns the length of the arc.
+    pub fn length(&self) -> f64 {
+        self.radius * self.sweep.abs()
+    }
+
+    /// Returns the point at the angle `angle` on the arc's circle.
+    pub fn point_at_angle(&self, angle: f64) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        self.center + Vec2::new(cos, sin) * self.radius
+    }
+
+    /// Returns the point a fraction `t` of the way along the arc, from its start at zero to its
+    /// end at one.
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        self.point_at_angle(self.start + self.sweep * t)
+    }
+
+    /// Returns the first point of the arc.
+    pub fn start_point(&self) -> Vec2 {
+        self.point_at_angle(self.start)
+    }
+
+    /// Returns the last point of the arc.
+    pub fn end_point(&self) -> Vec2 {
+        self.point_at_angle(self.end())
+    }
+
+    /// Returns the smallest axis-aligned rectangle containing the arc: the box of its ends,
+    /// grown to the circle's extreme points along each axis that the arc passes through.
+    pub fn bounds(&self) -> Rect {
+        let mut bounds = Rect::new(self.start_point(), self.end_point());
+        let (low, high) = if self.sweep < 0.0 { (self.end(), self.start) } else { (self.start, self.end()) };
+        let mut quadrant = (low / (PI / 2.0)).ceil();
+        while quadrant * PI / 2.0 <= high {
+            bounds.include(&self.point_at_angle(quadrant * PI / 2.0));
+            quadrant += 1.0;
+        }
+        bounds
+    }
+
+    /// Approximates the arc with a polyline whose chords stray at most `tolerance` from it,
+    /// using as few segments as that allows, spread evenly. The polyline starts and ends at
+    /// the ends of the arc. Tolerances below a millionth of the radius, including zero or
+    /// negative ones, are raised to that.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use candle::geometry::Arc;
+    /// use candle::math::Vec2;
+    /// let arc = Arc::new(Vec2::new(0.0, 0.0), 10.0, 0.0, PI);
+    /// let coarse = arc.flatten(1.0);
+    /// let fine = arc.flatten(0.01);
+    /// assert!(coarse.len() < fine.len());
+    /// assert!(fine.length() < arc.length());
+    /// assert!(arc.flatten(0.0).len() > fine.len());
+    /// ```
+    pub fn flatten(&self, tolerance: f64) -> Polyline {
+        let tolerance = tolerance.max(MIN_TOLERANCE * self.radius);
+        // A chord spanning the angle θ strays r (1 - cos(θ / 2)) from the arc at its middle.
+        let max_angle = if tolerance >= self.radius {
+            PI
+        } else {
+            (2.0 * (1.0 - tolerance / self.radius).acos()).min(PI)
+        };
+        let segments = if max_angle > 0.0 { (self.sweep.abs() / max_angle).ceil().max(1.0) as usize } else { 1 };
+        Polyline::new((0..=segments).map(|i| self.point_at(i as f64 / segments as f64)).collect())
+    }
+}
@@ -0,0 +1,133 @@
+use math::Vec2;
+use geometry::polygon::Polygon;
+use geometry::obb::Obb;
+use geometry::hull::convex_hull;
+
+/// Returns the oriented box of least area around the polygon, or around any point set wrapped
+/// in one, as its vertices are all that matter. Returns `None` for an empty polygon; collinear
+/// points give a box of zero height along their segment.
+///
+/// The smallest box has a side flush with an edge of the convex hull, so rotating calipers try
+/// each hull edge in turn, advancing the extreme vertices along and across it as they go: O(n
+/// log n) for the hull, then O(n). Unlike `Obb::from_points`, the box is the tightest possible.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{min_area_obb, Polygon};
+/// use candle::math::{Vec2, ApproxEq};
+/// let diamond = Polygon::new(vec![
+///     Vec2::new(0.0, -1.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), Vec2::new(-1.0, 0.0), Vec2::new(0.2, 0.1),
+/// ]);
+/// let obb = min_area_obb(&diamond).unwrap();
+/// assert!(obb.center.approx_eq_eps(Vec2::new(0.0, 0.0), Vec2::new(1e-12, 1e-12)));
+/// assert!((obb.half_extents.x * obb.half_extents.y * 4.0).approx_eq_eps(2.0, 1e-12));
+/// assert!((obb.rotation % std::f64::consts::FRAC_PI_2).abs().approx_eq_eps(std::f64::consts::FRAC_PI_4, 1e-12));
+/// ```
+pub fn min_area_obb(polygon: &Polygon) -> Option<Obb> {
+    let hull = convex_hull(polygon.points()).into_points();
+    match hull.len() {
+        0 => None,
+        1 => Some(Obb::new(hull[0], Vec2::new(0.0, 0.0), 0.0)),
+        2 => {
+            let direction = hull[1] - hull[0];
+            Some(Obb::new((hull[0] + hull[1]) / 2.0, Vec2::new(direction.length() / 2.0, 0.0), direction.y.atan2(direction.x)))
+        }
+        _ => {
+            let best = calipers(&hull).into_iter()
+                .min_by(|p, q| (p.width() * p.height()).total_cmp(&(q.width() * q.height())))
+                .unwrap();
+            let (u, v) = (best.along, best.across);
+            let middle = ((best.min.x + best.max.x) / 2.0, (best.min.y + best.max.y) / 2.0);
+            Some(Obb::new(u * middle.0 + v * middle.1, Vec2::new(best.width(), best.height()) / 2.0, u.y.atan2(u.x)))
+        }
+    }
+}
+
+/// Returns the width of the polygon, or of any point set wrapped in one: the least distance
+/// between two parallel lines enclosing it, together with the unit direction across them. This
+/// is the narrowest gap the shape can be turned to pass through. Returns `None` for an empty
+/// polygon; a single point has zero width in any direction.
+///
+/// Like `min_area_obb`, it uses rotating calipers over the convex hull, as one of the two lines
+/// always runs along a hull edge.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{min_width, Polygon};
+/// use candle::math::{Vec2, ApproxEq};
+/// let triangle = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(1.0, 1.0)]);
+/// let (width, direction) = min_width(&triangle).unwrap();
+/// assert_eq!(width, 1.0);
+/// assert!(direction.approx_eq_eps(Vec2::new(0.0, 1.0), Vec2::new(1e-12, 1e-12)));
+/// ```
+pub fn min_width(polygon: &Polygon) -> Option<(f64, Vec2)> {
+    let hull = convex_hull(polygon.points()).into_points();
+    match hull.len() {
+        0 => None,
+        1 => Some((0.0, Vec2::new(0.0, 1.0))),
+        2 => {
+            let direction = (hull[1] - hull[0]).normalized();
+            Some((0.0, Vec2::new(-direction.y, direction.x)))
+        }
+        _ => calipers(&hull).into_iter()
+            .min_by(|p, q| p.height().total_cmp(&q.height()))
+            .map(|best| (best.height(), best.across)),
+    }
+}
+
+/// The bounds of a convex polygon in the frame of one of its edges.
+struct Caliper {
+    /// The unit direction of the edge.
+    along: Vec2,
+    /// The unit normal of the edge, pointing into the polygon.
+    across: Vec2,
+    /// The least coordinates of the polygon along and across the edge.
+    min: Vec2,
+    /// The greatest coordinates of the polygon along and across the edge.
+    max: Vec2,
+}
+
+impl Caliper {
+
+    fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+}
+
+/// Returns the bounds of a strictly convex, counterclockwise polygon of at least three vertices
+/// in the frame of each of its edges, moving the extreme vertices forward from edge to edge.
+fn calipers(hull: &[Vec2]) -> Vec<Caliper> {
+    let count = hull.len();
+    let next = |i: usize| (i + 1) % count;
+    // Each extreme vertex starts from the previous one on the first edge, then only moves on.
+    let (mut right, mut top, mut left) = (1, 0, 0);
+    (0..count).map(|i| {
+        let along = (hull[next(i)] - hull[i]).normalized();
+        let across = Vec2::new(-along.y, along.x);
+        while along.dot(&(hull[next(right)] - hull[right])) > 0.0 {
+            right = next(right);
+        }
+        if i == 0 {
+            top = right;
+        }
+        while across.dot(&(hull[next(top)] - hull[top])) > 0.0 {
+            top = next(top);
+        }
+        if i == 0 {
+            left = top;
+        }
+        while along.dot(&(hull[next(left)] - hull[left])) < 0.0 {
+            left = next(left);
+        }
+        Caliper {
+            along,
+            across,
+            min: Vec2::new(along.dot(&hull[left]), across.dot(&hull[i])),
+            max: Vec2::new(along.dot(&hull[right]), across.dot(&hull[top])),
+        }
+    }).collect()
+}
@@ -0,0 +1,71 @@
+use std::f64::consts::PI;
+use math::Vec2;
+use geometry::line::Segment;
+use geometry::rect::Rect;
+
+/// A capsule: every point within `radius` of the `segment`, giving a rectangle capped by two
+/// half discs. Points on the boundary belong to it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Capsule2, Segment, Rect};
+/// use candle::math::Vec2;
+/// let body = Capsule2::new(Segment::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 2.0)), 0.5);
+/// assert!(body.contains(&Vec2::new(0.5, 1.0)));
+/// assert!(body.contains(&Vec2::new(0.0, 2.5)));
+/// assert!(!body.contains(&Vec2::new(0.5, 2.5)));
+/// assert_eq!(body.closest_point(&Vec2::new(3.0, 1.0)), Vec2::new(0.5, 1.0));
+/// assert_eq!(body.bounds(), Rect::new(Vec2::new(-0.5, -0.5), Vec2::new(0.5, 2.5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule2 {
+    pub segment: Segment,
+    pub radius: f64,
+}
+
+impl Capsule2 {
+
+    /// Allocates a new Capsule2 around `segment`, using the absolute value of `radius`.
+    pub fn new(segment: Segment, radius: f64) -> Capsule2 {
+        Capsule2 { segment, radius: radius.abs() }
+    }
+
+    /// Returns the area of the capsule.
+    pub fn area(&self) -> f64 {
+        self.radius * (2.0 * self.segment.length() + PI * self.radius)
+    }
+
+    /// Returns true if `point` lies inside or on the capsule.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        let offset = point - self.segment.closest_point(point);
+        offset.dot(&offset) <= self.radius * self.radius
+    }
+
+    /// Returns the signed distance from `point` to the boundary: negative inside the capsule and
+    /// positive outside.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        self.segment.distance(point) - self.radius
+    }
+
+    /// Returns the point on the boundary closest to `point`. Points on the segment itself are
+    /// equally close to both sides and map to the left of it, going from `a` to `b`.
+    pub fn closest_point(&self, point: &Vec2) -> Vec2 {
+        let center = self.segment.closest_point(point);
+        let offset = point - center;
+        let length = offset.length();
+        if length > 0.0 {
+            return center + offset * (self.radius / length);
+        }
+        let direction = self.segment.direction();
+        let along = direction.length();
+        if along == 0.0 {
+            return center + Vec2::new(self.radius, 0.0);
+        }
+        center + Vec2::new(-direction.y, direction.x) * (self.radius / along)
+    }
+
+    /// Returns the smallest axis-aligned rectangle containing the capsule.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.segment.a, self.segment.b).expand(self.radius)
+    }
+}
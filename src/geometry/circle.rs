@@ -0,0 +1,88 @@
+use std::f64::consts::PI;
+use math::Vec2;
+use geometry::rect::Rect;
+
+/// A circle, or a disc when testing containment: points on the boundary belong to it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Circle, Rect};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(0.0, 0.0), 2.0);
+/// assert!(circle.contains(&Vec2::new(0.0, 2.0)));
+/// assert!(circle.intersects(&Circle::new(Vec2::new(3.0, 0.0), 1.5)));
+/// assert!(!circle.intersects_rect(&Rect::new(Vec2::new(1.5, 1.5), Vec2::new(3.0, 3.0))));
+/// assert_eq!(circle.bounds(), Rect::new(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f64,
+}
+
+impl Circle {
+
+    /// Allocates a new Circle, using the absolute value of `radius`.
+    pub fn new(center: Vec2, radius: f64) -> Circle {
+        Circle { center, radius: radius.abs() }
+    }
+
+    /// Returns the area of the disc.
+    pub fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    /// Returns the length of the boundary.
+    pub fn circumference(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+
+    /// Returns the smallest axis-aligned rectangle containing the circle.
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_size(self.center, Vec2::new(2.0 * self.radius, 2.0 * self.radius))
+    }
+
+    /// Returns true if `point` lies inside or on the circle.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        let offset = point - self.center;
+        offset.dot(&offset) <= self.radius * self.radius
+    }
+
+    /// Returns true if the discs overlap or touch.
+    pub fn intersects(&self, other: &Circle) -> bool {
+        let offset = other.center - self.center;
+        let reach = self.radius + other.radius;
+        offset.dot(&offset) <= reach * reach
+    }
+
+    /// Returns true if the disc overlaps or touches the rectangle.
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        self.contains(&rect.closest_point(&self.center))
+    }
+
+    /// Returns the point on the boundary closest to `point`. The center is equally close to the
+    /// whole boundary, so it maps to the rightmost point.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Circle;
+    /// use candle::math::Vec2;
+    /// let circle = Circle::new(Vec2::new(1.0, 1.0), 2.0);
+    /// assert_eq!(circle.closest_boundary_point(&Vec2::new(1.0, 5.0)), Vec2::new(1.0, 3.0));
+    /// assert_eq!(circle.closest_boundary_point(&Vec2::new(0.0, 1.0)), Vec2::new(-1.0, 1.0));
+    /// ```
+    pub fn closest_boundary_point(&self, point: &Vec2) -> Vec2 {
+        let offset = point - self.center;
+        let length = offset.length();
+        if length == 0.0 {
+            return self.center + Vec2::new(self.radius, 0.0);
+        }
+        self.center + offset * (self.radius / length)
+    }
+
+    /// Returns the signed distance from `point` to the boundary: negative inside the circle and
+    /// positive outside.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        (point - self.center).length() - self.radius
+    }
+}
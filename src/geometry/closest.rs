@@ -0,0 +1,169 @@
+//! Closest-point queries between 2D shapes, for proximity tests and snapping. Rectangles,
+//! circles and polygons are treated as solid: a point inside one is its own closest point, at
+//! distance zero. For the distance to a shape's boundary from inside, use the shapes' own
+//! methods, such as `Circle::distance`.
+
+use math::Vec2;
+use geometry::rect::Rect;
+use geometry::circle::Circle;
+use geometry::line::Segment;
+use geometry::polygon::Polygon;
+use geometry::intersect::{self, SegmentIntersection};
+
+/// The point of a shape closest to a query point, and how far the query point is from it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Closest {
+    pub point: Vec2,
+    pub distance: f64,
+}
+
+/// The closest points of two shapes, `first` on the first shape and `second` on the second, and
+/// the distance between them. Overlapping shapes share a point at distance zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPair {
+    pub first: Vec2,
+    pub second: Vec2,
+    pub distance: f64,
+}
+
+/// Returns the point of the segment closest to `point`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Segment;
+/// use candle::geometry::closest::{self, Closest};
+/// use candle::math::Vec2;
+/// let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+/// assert_eq!(closest::point_segment(&Vec2::new(1.0, 2.0), &segment), Closest { point: Vec2::new(1.0, 0.0), distance: 2.0 });
+/// assert_eq!(closest::point_segment(&Vec2::new(7.0, 4.0), &segment), Closest { point: Vec2::new(4.0, 0.0), distance: 5.0 });
+/// ```
+pub fn point_segment(point: &Vec2, segment: &Segment) -> Closest {
+    closest(point, segment.closest_point(point))
+}
+
+/// Returns the point of the rectangle closest to `point`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Rect;
+/// use candle::geometry::closest::{self, Closest};
+/// use candle::math::Vec2;
+/// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// assert_eq!(closest::point_rect(&Vec2::new(7.0, 6.0), &rect), Closest { point: Vec2::new(4.0, 2.0), distance: 5.0 });
+/// assert_eq!(closest::point_rect(&Vec2::new(1.0, 1.0), &rect).distance, 0.0);
+/// ```
+pub fn point_rect(point: &Vec2, rect: &Rect) -> Closest {
+    closest(point, rect.closest_point(point))
+}
+
+/// Returns the point of the disc closest to `point`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Circle;
+/// use candle::geometry::closest::{self, Closest};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(0.0, 0.0), 5.0);
+/// assert_eq!(closest::point_circle(&Vec2::new(0.0, 8.0), &circle), Closest { point: Vec2::new(0.0, 5.0), distance: 3.0 });
+/// assert_eq!(closest::point_circle(&Vec2::new(1.0, 1.0), &circle).point, Vec2::new(1.0, 1.0));
+/// ```
+pub fn point_circle(point: &Vec2, circle: &Circle) -> Closest {
+    if circle.contains(point) {
+        return Closest { point: *point, distance: 0.0 };
+    }
+    closest(point, circle.closest_boundary_point(point))
+}
+
+/// Returns the point of the polygon closest to `point`, or `None` if the polygon has no
+/// vertices. Whether the point is inside is decided as by `Polygon::contains`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Polygon;
+/// use candle::geometry::closest::{self, Closest};
+/// use candle::math::Vec2;
+/// let triangle = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 4.0)]);
+/// assert_eq!(closest::point_polygon(&Vec2::new(4.0, 4.0), &triangle), Some(Closest { point: Vec2::new(2.0, 2.0), distance: 8f64.sqrt() }));
+/// assert_eq!(closest::point_polygon(&Vec2::new(1.0, 1.0), &triangle).unwrap().distance, 0.0);
+/// ```
+pub fn point_polygon(point: &Vec2, polygon: &Polygon) -> Option<Closest> {
+    if polygon.is_empty() {
+        return None;
+    }
+    if polygon.contains(point) {
+        return Some(Closest { point: *point, distance: 0.0 });
+    }
+    if polygon.len() == 1 {
+        return Some(closest(point, polygon.points()[0]));
+    }
+    polygon.edges()
+        .map(|edge| point_segment(point, &edge))
+        .min_by(|p, q| p.distance.total_cmp(&q.distance))
+}
+
+/// Returns the closest points of two segments. Crossing segments share their intersection point,
+/// and collinear overlapping ones the start of the shared part.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Segment;
+/// use candle::geometry::closest::{self, ClosestPair};
+/// use candle::math::Vec2;
+/// let first = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+/// let second = Segment::new(Vec2::new(5.0, -3.0), Vec2::new(5.0, 3.0));
+/// assert_eq!(closest::segment_segment(&first, &second),
+///            ClosestPair { first: Vec2::new(4.0, 0.0), second: Vec2::new(5.0, 0.0), distance: 1.0 });
+/// let crossing = Segment::new(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0));
+/// assert_eq!(closest::segment_segment(&first, &crossing).distance, 0.0);
+/// ```
+pub fn segment_segment(first: &Segment, second: &Segment) -> ClosestPair {
+    match intersect::segment_segment(first, second) {
+        Some(SegmentIntersection::Point { point, .. }) => return ClosestPair { first: point, second: point, distance: 0.0 },
+        Some(SegmentIntersection::Overlap { segment, .. }) => return ClosestPair { first: segment.a, second: segment.a, distance: 0.0 },
+        None => {}
+    }
+    // Segments that don't meet are closest at an endpoint of one of them.
+    let candidates = [
+        pair(first.a, second.closest_point(&first.a)),
+        pair(first.b, second.closest_point(&first.b)),
+        pair(first.closest_point(&second.a), second.a),
+        pair(first.closest_point(&second.b), second.b),
+    ];
+    candidates.iter().cloned().min_by(|p, q| p.distance.total_cmp(&q.distance)).unwrap()
+}
+
+/// Returns the closest points of a disc and a polygon, `first` on the disc and `second` on the
+/// polygon, or `None` if the polygon has no vertices. When they overlap, both points are the
+/// point of the polygon closest to the circle's center.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Circle, Polygon};
+/// use candle::geometry::closest::{self, ClosestPair};
+/// use candle::math::Vec2;
+/// let square = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0)]);
+/// let circle = Circle::new(Vec2::new(7.0, 2.0), 1.0);
+/// assert_eq!(closest::circle_polygon(&circle, &square),
+///            Some(ClosestPair { first: Vec2::new(6.0, 2.0), second: Vec2::new(4.0, 2.0), distance: 2.0 }));
+/// let touching = Circle::new(Vec2::new(5.0, 2.0), 1.5);
+/// assert_eq!(closest::circle_polygon(&touching, &square).unwrap().distance, 0.0);
+/// ```
+pub fn circle_polygon(circle: &Circle, polygon: &Polygon) -> Option<ClosestPair> {
+    let nearest = point_polygon(&circle.center, polygon)?;
+    if nearest.distance <= circle.radius {
+        return Some(ClosestPair { first: nearest.point, second: nearest.point, distance: 0.0 });
+    }
+    Some(ClosestPair {
+        first: circle.closest_boundary_point(&nearest.point),
+        second: nearest.point,
+        distance: nearest.distance - circle.radius,
+    })
+}
+
+fn closest(from: &Vec2, point: Vec2) -> Closest {
+    Closest { point, distance: (point - from).length() }
+}
+
+fn pair(first: Vec2, second: Vec2) -> ClosestPair {
+    ClosestPair { first, second, distance: (second - first).length() }
+}
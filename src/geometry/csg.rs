@@ -0,0 +1,315 @@
+//! Constructive solid geometry: boolean operations on closed meshes, following the BSP tree
+//! approach of Evan Wallace's csg.js.
+//!
+//! Each operand is turned into a BSP tree of its polygons. Clipping a tree against the other
+//! operand's tree removes the parts of its surface inside (or outside) the other solid, and the
+//! surviving polygons of both are merged. The operands must be closed and consistently wound
+//! outwards, as `Mesh` expects; the results are too. Normals and UVs are not carried over, and
+//! the output is split into triangle fans with vertices shared by exact position.
+//!
+//! The trees are built recursively, so very large or badly conditioned inputs can use a lot of
+//! stack. Faces coplanar with the other operand are kept once, on the side their normals agree.
+
+use std::collections::HashMap;
+use math::Vec3;
+use geometry::plane::Plane;
+use geometry::mesh::Mesh;
+
+/// How far from a plane, in world units, a point still counts as lying on it.
+const EPSILON: f64 = 1e-5;
+
+/// Returns the solid made of the space inside either mesh.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{csg, Aabb3};
+/// # use candle::geometry::Mesh;
+/// # use candle::math::Vec3;
+/// # fn cube(min: Vec3, max: Vec3) -> Mesh {
+/// #     let positions = (0..8).map(|i| Vec3::new(
+/// #         if i & 1 == 0 { min.x } else { max.x },
+/// #         if i & 2 == 0 { min.y } else { max.y },
+/// #         if i & 4 == 0 { min.z } else { max.z })).collect();
+/// #     Mesh::new(positions, vec![0, 2, 3, 0, 3, 1, 4, 5, 7, 4, 7, 6, 0, 1, 5, 0, 5, 4,
+/// #                               2, 6, 7, 2, 7, 3, 0, 4, 6, 0, 6, 2, 1, 3, 7, 1, 7, 5])
+/// # }
+/// let a = cube(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+/// let b = cube(Vec3::new(1.0, 1.0, 1.0), Vec3::new(3.0, 3.0, 3.0));
+/// let union = csg::union(&a, &b);
+/// assert_eq!(union.bounds(), Some(Aabb3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(3.0, 3.0, 3.0))));
+/// ```
+pub fn union(a: &Mesh, b: &Mesh) -> Mesh {
+    let (mut a, mut b) = (Node::from_mesh(a), Node::from_mesh(b));
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+    to_mesh(a.all_polygons())
+}
+
+/// Returns the solid made of the space inside `a` but not inside `b`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{csg, Aabb3};
+/// # use candle::geometry::Mesh;
+/// # use candle::math::Vec3;
+/// # fn cube(min: Vec3, max: Vec3) -> Mesh {
+/// #     let positions = (0..8).map(|i| Vec3::new(
+/// #         if i & 1 == 0 { min.x } else { max.x },
+/// #         if i & 2 == 0 { min.y } else { max.y },
+/// #         if i & 4 == 0 { min.z } else { max.z })).collect();
+/// #     Mesh::new(positions, vec![0, 2, 3, 0, 3, 1, 4, 5, 7, 4, 7, 6, 0, 1, 5, 0, 5, 4,
+/// #                               2, 6, 7, 2, 7, 3, 0, 4, 6, 0, 6, 2, 1, 3, 7, 1, 7, 5])
+/// # }
+/// let slab = cube(Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 4.0, 1.0));
+/// let cutter = cube(Vec3::new(2.0, -1.0, -1.0), Vec3::new(5.0, 5.0, 2.0));
+/// let cut = csg::difference(&slab, &cutter);
+/// assert_eq!(cut.bounds(), Some(Aabb3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 4.0, 1.0))));
+/// ```
+pub fn difference(a: &Mesh, b: &Mesh) -> Mesh {
+    let (mut a, mut b) = (Node::from_mesh(a), Node::from_mesh(b));
+    a.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+    a.invert();
+    to_mesh(a.all_polygons())
+}
+
+/// Returns the solid made of the space inside both meshes.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{csg, Aabb3};
+/// # use candle::geometry::Mesh;
+/// # use candle::math::Vec3;
+/// # fn cube(min: Vec3, max: Vec3) -> Mesh {
+/// #     let positions = (0..8).map(|i| Vec3::new(
+/// #         if i & 1 == 0 { min.x } else { max.x },
+/// #         if i & 2 == 0 { min.y } else { max.y },
+/// #         if i & 4 == 0 { min.z } else { max.z })).collect();
+/// #     Mesh::new(positions, vec![0, 2, 3, 0, 3, 1, 4, 5, 7, 4, 7, 6, 0, 1, 5, 0, 5, 4,
+/// #                               2, 6, 7, 2, 7, 3, 0, 4, 6, 0, 6, 2, 1, 3, 7, 1, 7, 5])
+/// # }
+/// let a = cube(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+/// let b = cube(Vec3::new(1.0, 1.0, 1.0), Vec3::new(3.0, 3.0, 3.0));
+/// let overlap = csg::intersection(&a, &b);
+/// assert_eq!(overlap.bounds(), Some(Aabb3::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(2.0, 2.0, 2.0))));
+/// assert!(csg::intersection(&a, &csg::difference(&b, &a)).positions.is_empty());
+/// ```
+pub fn intersection(a: &Mesh, b: &Mesh) -> Mesh {
+    let (mut a, mut b) = (Node::from_mesh(a), Node::from_mesh(b));
+    a.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    a.build(b.all_polygons());
+    a.invert();
+    to_mesh(a.all_polygons())
+}
+
+/// A convex planar polygon, wound counterclockwise around its plane's normal.
+#[derive(Debug, Clone)]
+struct Face {
+    vertices: Vec<Vec3>,
+    plane: Plane,
+}
+
+impl Face {
+
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        self.plane = self.plane.flipped();
+    }
+}
+
+/// Where a face lies relative to a splitting plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Coplanar = 0,
+    Front = 1,
+    Back = 2,
+    Spanning = 3,
+}
+
+/// Sorts `face` by `plane`: coplanar faces go to `coplanar_front` or `coplanar_back` depending on
+/// the way they face, and spanning faces are cut in two.
+fn split(plane: &Plane, face: Face, coplanar_front: &mut Vec<Face>, coplanar_back: &mut Vec<Face>,
+         front: &mut Vec<Face>, back: &mut Vec<Face>) {
+    let sides: Vec<Side> = face.vertices.iter().map(|vertex| {
+        let distance = plane.distance(vertex);
+        if distance < -EPSILON { Side::Back } else if distance > EPSILON { Side::Front } else { Side::Coplanar }
+    }).collect();
+    let side = sides.iter().fold(0, |all, &side| all | side as u8);
+
+    match side {
+        0 => {
+            if plane.normal.dot(&face.plane.normal) > 0.0 {
+                coplanar_front.push(face);
+            } else {
+                coplanar_back.push(face);
+            }
+        }
+        1 => front.push(face),
+        2 => back.push(face),
+        _ => {
+            let (mut f, mut b) = (Vec::new(), Vec::new());
+            let count = face.vertices.len();
+            for i in 0..count {
+                let j = (i + 1) % count;
+                let (si, sj) = (sides[i], sides[j]);
+                let (vi, vj) = (face.vertices[i], face.vertices[j]);
+                if si != Side::Back {
+                    f.push(vi);
+                }
+                if si != Side::Front {
+                    b.push(vi);
+                }
+                if (si as u8 | sj as u8) == Side::Spanning as u8 {
+                    let t = -plane.distance(&vi) / plane.normal.dot(&(vj - vi));
+                    let cut = vi + (vj - vi) * t;
+                    f.push(cut);
+                    b.push(cut);
+                }
+            }
+            if f.len() >= 3 {
+                front.push(Face { vertices: f, plane: face.plane });
+            }
+            if b.len() >= 3 {
+                back.push(Face { vertices: b, plane: face.plane });
+            }
+        }
+    }
+}
+
+/// A node of a BSP tree: the faces lying on its plane, and the subtrees in front of and behind
+/// it. Space behind the leaves' planes is inside the solid.
+#[derive(Debug, Default)]
+struct Node {
+    plane: Option<Plane>,
+    front: Option<Box<Node>>,
+    back: Option<Box<Node>>,
+    faces: Vec<Face>,
+}
+
+impl Node {
+
+    fn from_mesh(mesh: &Mesh) -> Node {
+        let faces = mesh.triangles().filter_map(|t| {
+            let vertices = vec![mesh.positions[t[0] as usize], mesh.positions[t[1] as usize], mesh.positions[t[2] as usize]];
+            let plane = Plane::from_points(&vertices[0], &vertices[1], &vertices[2])?;
+            Some(Face { vertices, plane })
+        }).collect();
+        let mut node = Node::default();
+        node.build(faces);
+        node
+    }
+
+    /// Turns the solid inside out.
+    fn invert(&mut self) {
+        for face in self.faces.iter_mut() {
+            face.flip();
+        }
+        self.plane = self.plane.map(|plane| plane.flipped());
+        if let Some(front) = self.front.as_mut() {
+            front.invert();
+        }
+        if let Some(back) = self.back.as_mut() {
+            back.invert();
+        }
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Removes the parts of `faces` inside the solid of this tree.
+    fn clip_faces(&self, faces: Vec<Face>) -> Vec<Face> {
+        let plane = match self.plane {
+            Some(plane) => plane,
+            None => return faces,
+        };
+        let (mut front, mut back) = (Vec::new(), Vec::new());
+        for face in faces {
+            let (mut coplanar_front, mut coplanar_back) = (Vec::new(), Vec::new());
+            split(&plane, face, &mut coplanar_front, &mut coplanar_back, &mut front, &mut back);
+            front.append(&mut coplanar_front);
+            back.append(&mut coplanar_back);
+        }
+        let mut front = match self.front {
+            Some(ref node) => node.clip_faces(front),
+            None => front,
+        };
+        let back = match self.back {
+            Some(ref node) => node.clip_faces(back),
+            None => Vec::new(),
+        };
+        front.extend(back);
+        front
+    }
+
+    /// Removes the parts of this tree's faces inside the solid of `other`.
+    fn clip_to(&mut self, other: &Node) {
+        self.faces = other.clip_faces(std::mem::take(&mut self.faces));
+        if let Some(front) = self.front.as_mut() {
+            front.clip_to(other);
+        }
+        if let Some(back) = self.back.as_mut() {
+            back.clip_to(other);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Face> {
+        let mut faces = self.faces.clone();
+        if let Some(ref front) = self.front {
+            faces.extend(front.all_polygons());
+        }
+        if let Some(ref back) = self.back {
+            faces.extend(back.all_polygons());
+        }
+        faces
+    }
+
+    /// Adds `faces` to the tree, splitting them by the planes they cross.
+    fn build(&mut self, faces: Vec<Face>) {
+        if faces.is_empty() {
+            return;
+        }
+        let plane = *self.plane.get_or_insert(faces[0].plane);
+        let (mut front, mut back) = (Vec::new(), Vec::new());
+        for face in faces {
+            let (mut coplanar_front, mut coplanar_back) = (Vec::new(), Vec::new());
+            split(&plane, face, &mut coplanar_front, &mut coplanar_back, &mut front, &mut back);
+            self.faces.append(&mut coplanar_front);
+            self.faces.append(&mut coplanar_back);
+        }
+        if !front.is_empty() {
+            self.front.get_or_insert_with(Box::default).build(front);
+        }
+        if !back.is_empty() {
+            self.back.get_or_insert_with(Box::default).build(back);
+        }
+    }
+}
+
+fn to_mesh(faces: Vec<Face>) -> Mesh {
+    let mut mesh = Mesh::default();
+    let mut shared = HashMap::new();
+    for face in faces {
+        let indices: Vec<u32> = face.vertices.iter().map(|vertex| {
+            let key = (vertex.x.to_bits(), vertex.y.to_bits(), vertex.z.to_bits());
+            *shared.entry(key).or_insert_with(|| {
+                mesh.positions.push(*vertex);
+                (mesh.positions.len() - 1) as u32
+            })
+        }).collect();
+        for i in 1..indices.len() - 1 {
+            mesh.indices.extend_from_slice(&[indices[0], indices[i], indices[i + 1]]);
+        }
+    }
+    mesh
+}
@@ -0,0 +1,72 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use math::predicates::orient2d;
+use geometry::polygon::Polygon;
+use geometry::triangulate::triangulate;
+
+/// Splits a simple polygon into convex pieces, wound counterclockwise, that together cover it
+/// exactly, as physics engines and separating axis tests need.
+///
+/// Uses the Hertel–Mehlhorn algorithm: the polygon is triangulated by ear clipping, then each
+/// diagonal is removed in turn whenever the two pieces it separates merge into a convex one.
+/// This never yields more than four times the minimum number of convex pieces, and in practice
+/// comes close to it, in O(n²) time dominated by the triangulation. Polygons that are already
+/// convex come back as a single piece.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{convex_decomposition, Polygon};
+/// use candle::math::Vec2;
+/// let l_shape = Polygon::new(vec![
+///     Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 2.0),
+///     Vec2::new(2.0, 2.0), Vec2::new(2.0, 4.0), Vec2::new(0.0, 4.0),
+/// ]);
+/// let pieces = convex_decomposition(&l_shape);
+/// assert_eq!(pieces.len(), 2);
+/// assert!(pieces.iter().all(|piece| piece.is_convex()));
+/// assert_eq!(pieces.iter().map(|piece| piece.area()).sum::<f64>(), 12.0);
+/// ```
+pub fn convex_decomposition(polygon: &Polygon) -> Vec<Polygon> {
+    let mut pieces: Vec<Option<Vec<usize>>> = triangulate(polygon).into_iter().map(|t| Some(t.to_vec())).collect();
+    let mut owners = HashMap::new();
+    let mut diagonals = Vec::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        let piece = piece.as_ref().unwrap();
+        for i in 0..3 {
+            let edge = (piece[i], piece[(i + 1) % 3]);
+            owners.insert(edge, index);
+            if owners.contains_key(&(edge.1, edge.0)) {
+                diagonals.push(edge);
+            }
+        }
+    }
+
+    let points = polygon.points();
+    let convex = |prev: usize, vertex: usize, next: usize| orient2d(&points[prev], &points[vertex], &points[next]) != Ordering::Less;
+    for (a, b) in diagonals {
+        let (p, q) = (owners[&(a, b)], owners[&(b, a)]);
+        // Walk the first piece from b round to a, then the second from a round to b.
+        let first = rotated(pieces[p].as_ref().unwrap(), b);
+        let second = rotated(pieces[q].as_ref().unwrap(), a);
+        let (m, n) = (first.len(), second.len());
+        if !convex(first[m - 2], a, second[1]) || !convex(second[n - 2], b, first[1]) {
+            continue;
+        }
+        for i in 0..n {
+            owners.insert((second[i], second[(i + 1) % n]), p);
+        }
+        owners.remove(&(a, b));
+        owners.remove(&(b, a));
+        let mut merged = first;
+        merged.extend_from_slice(&second[1..n - 1]);
+        pieces[p] = Some(merged);
+        pieces[q] = None;
+    }
+    pieces.into_iter().flatten().map(|piece| Polygon::new(piece.into_iter().map(|i| points[i]).collect())).collect()
+}
+
+/// Returns the cycle of vertex indices rotated to start at `start`.
+fn rotated(piece: &[usize], start: usize) -> Vec<usize> {
+    let offset = piece.iter().position(|&vertex| vertex == start).unwrap();
+    piece[offset..].iter().chain(&piece[..offset]).cloned().collect()
+}
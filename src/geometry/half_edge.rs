@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::iter;
+use math::Vec3;
+use geometry::mesh::Mesh;
+
+/// One side of an edge of a `HalfEdgeMesh`, running from its `origin` vertex around its `face`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HalfEdge {
+    /// The vertex the half-edge starts from.
+    pub origin: usize,
+    /// The opposite half-edge, running the other way along the same edge.
+    pub twin: usize,
+    /// The next half-edge around the face, or along the boundary loop.
+    pub next: usize,
+    /// The previous half-edge around the face, or along the boundary loop.
+    pub prev: usize,
+    /// The face on the left of the half-edge, or `None` for half-edges on the outside of a
+    /// boundary.
+    pub face: Option<usize>,
+}
+
+/// A polygon mesh stored as half-edges, so that the faces around a vertex, the edges of a face
+/// and the neighbours across an edge are all reached in constant time per step, as mesh editing
+/// algorithms such as subdivision and decimation need.
+///
+/// Holes and open borders are closed by boundary half-edges with no face, linked into loops, so
+/// every half-edge has a twin and walking around a boundary vertex never stops short.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::HalfEdgeMesh;
+/// use candle::math::Vec3;
+/// let positions = vec![
+///     Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+/// ];
+/// let mesh = HalfEdgeMesh::from_polygons(positions, &[vec![0, 1, 2], vec![0, 2, 3]]).unwrap();
+/// assert_eq!(mesh.face_count(), 2);
+/// assert_eq!(mesh.half_edge_count(), 10);
+/// assert_eq!(mesh.face_vertices(1), vec![0, 2, 3]);
+/// let mut around = mesh.neighbors(0);
+/// around.sort();
+/// assert_eq!(around, vec![1, 2, 3]);
+/// assert_eq!(mesh.boundary_loops(), vec![vec![1, 0, 3, 2]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HalfEdgeMesh {
+    positions: Vec<Vec3>,
+    half_edges: Vec<HalfEdge>,
+    vertex_edges: Vec<Option<usize>>,
+    face_edges: Vec<usize>,
+}
+
+impl HalfEdgeMesh {
+
+    /// Builds a half-edge mesh from its positions and faces, each face listing the indices of
+    /// at least three vertices counterclockwise. Returns `None` if a face is too small or names a
+    /// missing vertex, or if the faces don't form a consistently wound manifold: an edge shared
+    /// by more than two faces or by two faces winding it the same way, or a vertex where two
+    /// separate fans of faces meet.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::HalfEdgeMesh;
+    /// use candle::math::Vec3;
+    /// let positions = vec![
+    ///     Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0),
+    ///     Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 0.0, -1.0),
+    /// ];
+    /// let tetrahedron = |a: usize, b: usize, c: usize| vec![vec![0, b, a], vec![0, a, c], vec![0, c, b], vec![a, b, c]];
+    /// assert!(HalfEdgeMesh::from_polygons(positions.clone(), &tetrahedron(1, 2, 3)).is_some());
+    /// // Two tetrahedra touching only at vertex 0.
+    /// let touching: Vec<Vec<usize>> = tetrahedron(1, 2, 3).into_iter().chain(tetrahedron(4, 5, 6)).collect();
+    /// assert!(HalfEdgeMesh::from_polygons(positions, &touching).is_none());
+    /// ```
+    pub fn from_polygons(positions: Vec<Vec3>, faces: &[Vec<usize>]) -> Option<HalfEdgeMesh> {
+        let mut half_edges = Vec::new();
+        let mut face_edges = Vec::with_capacity(faces.len());
+        let mut edges = HashMap::new();
+        for (face, vertices) in faces.iter().enumerate() {
+            let count = vertices.len();
+            if count < 3 {
+                return None;
+            }
+            let base = half_edges.len();
+            face_edges.push(base);
+            for (i, &origin) in vertices.iter().enumerate() {
+                let destination = vertices[(i + 1) % count];
+                if origin >= positions.len() || origin == destination ||
+                    edges.insert((origin, destination), base + i).is_some() {
+                    return None;
+                }
+                half_edges.push(HalfEdge {
+                    origin,
+                    twin: usize::MAX,
+                    next: base + (i + 1) % count,
+                    prev: base + (i + count - 1) % count,
+                    face: Some(face),
+                });
+            }
+        }
+
+        // Pair up the half-edges, closing unpaired ones with boundary half-edges.
+        let mut boundary_from = vec![None; positions.len()];
+        for index in 0..half_edges.len() {
+            let (origin, destination) = (half_edges[index].origin, half_edges[half_edges[index].next].origin);
+            half_edges[index].twin = match edges.get(&(destination, origin)) {
+                Some(&twin) => twin,
+                None => {
+                    let boundary = half_edges.len();
+                    if boundary_from[destination].replace(boundary).is_some() {
+                        return None;
+                    }
+                    half_edges.push(HalfEdge { origin: destination, twin: index, next: usize::MAX, prev: usize::MAX, face: None });
+                    boundary
+                }
+            };
+        }
+        // Each boundary half-edge continues with the one leaving the vertex it reaches.
+        for index in 0..half_edges.len() {
+            if half_edges[index].face.is_none() {
+                let destination = half_edges[half_edges[index].twin].origin;
+                let next = boundary_from[destination]?;
+                half_edges[index].next = next;
+                half_edges[next].prev = index;
+            }
+        }
+
+        let mut vertex_edges = vec![None; positions.len()];
+        let mut leaving = vec![0; positions.len()];
+        for (index, half_edge) in half_edges.iter().enumerate() {
+            vertex_edges[half_edge.origin].get_or_insert(index);
+            leaving[half_edge.origin] += 1;
+        }
+        let mesh = HalfEdgeMesh { positions, half_edges, vertex_edges, face_edges };
+        // Turning around a vertex where separate fans meet only reaches the half-edges of one.
+        if (0..mesh.positions.len()).any(|vertex| mesh.outgoing(vertex).count() < leaving[vertex]) {
+            return None;
+        }
+        Some(mesh)
+    }
+
+    /// Builds a half-edge mesh from the triangles of an indexed mesh, with the same requirements
+    /// as `from_polygons`. Normals and UVs are not carried over.
+    pub fn from_mesh(mesh: &Mesh) -> Option<HalfEdgeMesh> {
+        let faces: Vec<Vec<usize>> = mesh.triangles().map(|t| t.iter().map(|&i| i as usize).collect()).collect();
+        HalfEdgeMesh::from_polygons(mesh.positions.clone(), &faces)
+    }
+
+    /// Converts the mesh back to an indexed triangle mesh, splitting faces with more than three
+    /// vertices into fans around their first vertex.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::{HalfEdgeMesh, Mesh};
+    /// use candle::math::Vec3;
+    /// let positions = vec![
+    ///     Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let quad = HalfEdgeMesh::from_polygons(positions.clone(), &[vec![0, 1, 2, 3]]).unwrap();
+    /// let mesh = quad.to_mesh();
+    /// assert_eq!(mesh, Mesh::new(positions, vec![0, 1, 2, 0, 2, 3]));
+    /// assert_eq!(HalfEdgeMesh::from_mesh(&mesh).unwrap().face_count(), 2);
+    /// ```
+    pub fn to_mesh(&self) -> Mesh {
+        let mut indices = Vec::new();
+        for face in 0..self.face_edges.len() {
+            let vertices = self.face_vertices(face);
+            for i in 1..vertices.len() - 1 {
+                indices.extend_from_slice(&[vertices[0] as u32, vertices[i] as u32, vertices[i + 1] as u32]);
+            }
+        }
+        Mesh::new(self.positions.clone(), indices)
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the number of faces.
+    pub fn face_count(&self) -> usize {
+        self.face_edges.len()
+    }
+
+    /// Returns the number of half-edges, including those on the outside of boundaries.
+    pub fn half_edge_count(&self) -> usize {
+        self.half_edges.len()
+    }
+
+    /// Returns the vertex positions.
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// Returns the vertex positions mutably. The connectivity can't be changed through them.
+    pub fn positions_mut(&mut self) -> &mut [Vec3] {
+        &mut self.positions
+    }
+
+    /// Returns the half-edge at `index`.
+    pub fn half_edge(&self, index: usize) -> &HalfEdge {
+        &self.half_edges[index]
+    }
+
+    /// Returns the vertex the half-edge at `index` points to.
+    pub fn destination(&self, index: usize) -> usize {
+        self.half_edges[self.half_edges[index].twin].origin
+    }
+
+    /// Returns true if the half-edge at `index` lies on the outside of a boundary.
+    pub fn is_boundary_edge(&self, index: usize) -> bool {
+        self.half_edges[index].face.is_none()
+    }
+
+    /// Returns true if the vertex lies on a boundary. Vertices not used by any face are not on a
+    /// boundary.
+    pub fn is_boundary_vertex(&self, vertex: usize) -> bool {
+        self.outgoing(vertex).any(|index| self.is_boundary_edge(index))
+    }
+
+    /// Returns the half-edges around the face, in order.
+    pub fn face_loop(&self, face: usize) -> impl Iterator<Item = usize> + '_ {
+        self.cycle(self.face_edges[face])
+    }
+
+    /// Returns the vertices of the face, counterclockwise.
+    pub fn face_vertices(&self, face: usize) -> Vec<usize> {
+        self.face_loop(face).map(|index| self.half_edges[index].origin).collect()
+    }
+
+    /// Returns the half-edges leaving the vertex, turning around it from one to the next. For
+    /// boundary vertices this includes the boundary half-edge leaving it.
+    pub fn outgoing(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.vertex_edges[vertex];
+        iter::successors(start, move |&index| {
+            let next = self.half_edges[self.half_edges[index].prev].twin;
+            if Some(next) == start { None } else { Some(next) }
+        })
+    }
+
+    /// Returns the vertices sharing an edge with the vertex, turning around it: its one-ring.
+    pub fn neighbors(&self, vertex: usize) -> Vec<usize> {
+        self.outgoing(vertex).map(|index| self.destination(index)).collect()
+    }
+
+    /// Returns the faces around the vertex, turning around it.
+    pub fn vertex_faces(&self, vertex: usize) -> Vec<usize> {
+        self.outgoing(vertex).filter_map(|index| self.half_edges[index].face).collect()
+    }
+
+    /// Returns the vertices of every boundary loop, each following its boundary half-edges, which
+    /// wind clockwise around the hole they enclose.
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.half_edges.len()];
+        let mut loops = Vec::new();
+        for start in 0..self.half_edges.len() {
+            if visited[start] || !self.is_boundary_edge(start) {
+                continue;
+            }
+            let edges: Vec<usize> = self.cycle(start).collect();
+            for &index in &edges {
+                visited[index] = true;
+            }
+            loops.push(edges.into_iter().map(|index| self.half_edges[index].origin).collect());
+        }
+        loops
+    }
+
+    fn cycle(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        iter::successors(Some(start), move |&index| {
+            let next = self.half_edges[index].next;
+            if next == start { None } else { Some(next) }
+        })
+    }
+}
@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, BTreeMap};
+use math::Vec2;
+use math::predicates::{orient2d, incircle};
+use geometry::polygon::Polygon;
+use geometry::triangle::Triangle;
+
+/// Returns the convex hull of the points, counterclockwise from the lowest of the leftmost
+/// points, as computed by `convex_hull_indices`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{convex_hull, Polygon};
+/// use candle::math::Vec2;
+/// let points = [
+///     Vec2::new(2.0, 2.0), Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0),
+///     Vec2::new(4.0, 4.0), Vec2::new(2.0, 0.0), Vec2::new(0.0, 4.0),
+/// ];
+/// assert_eq!(convex_hull(&points), Polygon::new(vec![
+///     Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0),
+/// ]));
+/// ```
+pub fn convex_hull(points: &[Vec2]) -> Polygon {
+    Polygon::new(convex_hull_indices(points).into_iter().map(|i| points[i]).collect())
+}
+
+/// Returns the indices of the points on their convex hull, counterclockwise from the lowest of
+/// the leftmost points, using Andrew's monotone chain algorithm in O(n log n).
+///
+/// The result only depends on the points, not on their order, except for which index stands
+/// for duplicates: the lowest one is used. Points in the middle of hull edges are left out, so
+/// every returned vertex is a strict corner. Turns are classified exactly with
+/// `predicates::orient2d`. If all points are collinear the hull is the two ends of the segment
+/// they span, and if they are all equal it is a single point.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::convex_hull_indices;
+/// use candle::math::Vec2;
+/// let points = [
+///     Vec2::new(1.0, 1.0), Vec2::new(3.0, 0.0), Vec2::new(0.0, 0.0),
+///     Vec2::new(3.0, 0.0), Vec2::new(0.0, 3.0), Vec2::new(1.5, 1.5),
+/// ];
+/// assert_eq!(convex_hull_indices(&points), vec![2, 1, 4]);
+/// assert_eq!(convex_hull_indices(&[Vec2::new(2.0, 2.0), Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)]), vec![1, 0]);
+/// ```
+pub fn convex_hull_indices(points: &[Vec2]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    // The sort is stable, so the first of equal points keeps the lowest index.
+    order.sort_by(|&i, &j| compare(&points[i], &points[j]));
+    order.dedup_by(|later, earlier| points[*later] == points[*earlier]);
+    if order.len() < 3 {
+        return order;
+    }
+
+    // Lower chain left to right, then upper chain right to left, popping every vertex that
+    // doesn't make a strict left turn with the next one.
+    let mut hull: Vec<usize> = Vec::with_capacity(order.len() + 1);
+    for &index in order.iter() {
+        while hull.len() >= 2 && !turns_left(points, &hull, index) {
+            hull.pop();
+        }
+        hull.push(index);
+    }
+    let lower = hull.len() + 1;
+    for &index in order.iter().rev().skip(1) {
+        while hull.len() >= lower && !turns_left(points, &hull, index) {
+            hull.pop();
+        }
+        hull.push(index);
+    }
+    // The upper chain ends back at the first point.
+    hull.pop();
+    hull
+}
+
+/// Outlines the points more tightly than their convex hull, following concavities narrower
+/// than about `2 * alpha`: the boundary of their alpha shape, for outlining explored areas,
+/// crowds or scattered pickups. Returns outlines counterclockwise and holes clockwise, several
+/// of each if the points fall into separate clusters or surround empty areas wider than that.
+/// Where the shape pinches down to a single point, the outlines touching there share that
+/// vertex.
+///
+/// The points are triangulated into their Delaunay triangulation, and the triangles whose
+/// circumscribed circle has a radius of at most `alpha` are kept: every disc of that radius
+/// that fits between the points without containing any carves its way in. Points too far from
+/// all others to be part of a kept triangle are left out. An infinite `alpha` gives the convex
+/// hull, and smaller ones dig deeper until the shape falls apart. Takes O(n²) time in the worst
+/// case, and about O(n √n) for scattered points.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{concave_hull, convex_hull};
+/// use candle::math::Vec2;
+/// // A 5 by 5 grid of points with a notch cut into the top.
+/// let points: Vec<Vec2> = (0..25).map(|i| Vec2::new((i % 5) as f64, (i / 5) as f64))
+///     .filter(|p| p.x != 2.0 || p.y < 2.0)
+///     .collect();
+/// let outline = concave_hull(&points, 0.9);
+/// assert_eq!(outline.len(), 1);
+/// assert!(outline[0].contains(&Vec2::new(0.5, 3.5)));
+/// assert!(!outline[0].contains(&Vec2::new(2.0, 3.0)));
+/// assert_eq!(outline[0].area(), 11.0);
+/// assert_eq!(concave_hull(&points, f64::INFINITY)[0].area(), convex_hull(&points).area());
+/// ```
+pub fn concave_hull(points: &[Vec2], alpha: f64) -> Vec<Polygon> {
+    let triangles: Vec<[usize; 3]> = delaunay(points).into_iter().filter(|t| {
+        Triangle::new(points[t[0]], points[t[1]], points[t[2]]).circumcircle().is_some_and(|circle| circle.radius <= alpha)
+    }).collect();
+    let edges: HashSet<(usize, usize)> = triangles.iter().flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])]).collect();
+    let mut outgoing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for &(a, b) in edges.iter().filter(|&&(a, b)| !edges.contains(&(b, a))) {
+        outgoing.entry(a).or_default().push(b);
+    }
+
+    // Where outlines touch at a vertex, each continues along the first boundary edge clockwise
+    // from the one it came in by, which keeps them from crossing.
+    let mut outlines = Vec::new();
+    while let Some((&start, _)) = outgoing.iter().next() {
+        let mut outline = vec![start];
+        let mut from = start;
+        let mut at = take(&mut outgoing, start, |_| 0.0);
+        while at != start {
+            outline.push(at);
+            let back = points[from] - points[at];
+            let angle = |to: usize| {
+                let ahead = points[to] - points[at];
+                let turn = (back.x * ahead.y - back.y * ahead.x).atan2(back.dot(&ahead));
+                if turn < 0.0 { -turn } else { 2.0 * std::f64::consts::PI - turn }
+            };
+            from = at;
+            at = take(&mut outgoing, at, angle);
+        }
+        outlines.push(Polygon::new(outline.into_iter().map(|i| points[i]).collect()));
+    }
+    outlines
+}
+
+/// Removes and returns the edge leaving `vertex` that minimizes `key`.
+fn take<F>(outgoing: &mut BTreeMap<usize, Vec<usize>>, vertex: usize, key: F) -> usize
+    where F: Fn(usize) -> f64
+{
+    let ends = outgoing.get_mut(&vertex).unwrap();
+    let best = (0..ends.len()).min_by(|&i, &j| key(ends[i]).total_cmp(&key(ends[j]))).unwrap();
+    let end = ends.swap_remove(best);
+    if ends.is_empty() {
+        outgoing.remove(&vertex);
+    }
+    end
+}
+
+/// Returns the Delaunay triangulation of the points, as counterclockwise triangles of indices.
+/// Duplicates are triangulated once, under their lowest index.
+///
+/// The points are swept left to right, each joined to the hull edges it sees, then edges are
+/// flipped until every triangle's circumcircle is empty, as decided by `predicates::incircle`.
+fn delaunay(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| compare(&points[i], &points[j]));
+    order.dedup_by(|later, earlier| points[*later] == points[*earlier]);
+    let orient = |a: usize, b: usize, c: usize| orient2d(&points[a], &points[b], &points[c]);
+    let Some(apex) = (2..order.len()).find(|&k| orient(order[0], order[1], order[k]) != Ordering::Equal) else {
+        return Vec::new();
+    };
+
+    // Fan the collinear points before the first one off their line from it.
+    let mut triangles = Vec::new();
+    let mut hull: Vec<usize> = order[..apex].to_vec();
+    for pair in order[..apex].windows(2) {
+        triangles.push([pair[0], pair[1], order[apex]]);
+    }
+    if orient(order[0], order[1], order[apex]) == Ordering::Less {
+        for triangle in triangles.iter_mut() {
+            triangle.swap(0, 1);
+        }
+        hull.reverse();
+    }
+    hull.push(order[apex]);
+    if orient(hull[0], hull[1], hull[2]) == Ordering::Less {
+        hull.reverse();
+    }
+
+    // Each later point lies right of all the others, and sees a run of the hull's edges.
+    for &point in order[apex + 1..].iter() {
+        let count = hull.len();
+        let visible = |i: usize| orient(hull[i], hull[(i + 1) % count], point) == Ordering::Less;
+        let start = (0..count).find(|&i| visible(i) && !visible((i + count - 1) % count)).unwrap();
+        hull.rotate_left(start);
+        let run = (0..count).take_while(|&i| orient(hull[i], hull[(i + 1) % count], point) == Ordering::Less).count();
+        for i in 0..run {
+            triangles.push([hull[(i + 1) % count], hull[i], point]);
+        }
+        hull.splice(1..run, [point]);
+    }
+
+    // Lawson flips: replace the diagonal of any two triangles whose circumcircles contain the
+    // opposite vertex.
+    let mut owner: HashMap<(usize, usize), usize> = HashMap::new();
+    for (index, t) in triangles.iter().enumerate() {
+        for i in 0..3 {
+            owner.insert((t[i], t[(i + 1) % 3]), index);
+        }
+    }
+    let mut pending: Vec<(usize, usize)> = owner.keys().cloned().collect();
+    while let Some((a, b)) = pending.pop() {
+        let (Some(&first), Some(&second)) = (owner.get(&(a, b)), owner.get(&(b, a))) else {
+            continue;
+        };
+        let opposite = |t: [usize; 3]| t.iter().cloned().find(|&v| v != a && v != b).unwrap();
+        let (c, d) = (opposite(triangles[first]), opposite(triangles[second]));
+        if incircle(&points[a], &points[b], &points[c], &points[d]) != Ordering::Greater {
+            continue;
+        }
+        triangles[first] = [a, d, c];
+        triangles[second] = [d, b, c];
+        owner.remove(&(a, b));
+        owner.remove(&(b, a));
+        for (index, t) in [(first, triangles[first]), (second, triangles[second])] {
+            for i in 0..3 {
+                owner.insert((t[i], t[(i + 1) % 3]), index);
+            }
+        }
+        pending.extend_from_slice(&[(a, d), (d, b), (b, c), (c, a)]);
+    }
+    triangles
+}
+
+fn turns_left(points: &[Vec2], hull: &[usize], index: usize) -> bool {
+    let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+    orient2d(&points[a], &points[b], &points[index]) == Ordering::Greater
+}
+
+fn compare(a: &Vec2, b: &Vec2) -> Ordering {
+    // Adding zero turns -0.0 into 0.0, so that equal points always end up next to each other.
+    (a.x + 0.0).total_cmp(&(b.x + 0.0)).then((a.y + 0.0).total_cmp(&(b.y + 0.0)))
+}
@@ -0,0 +1,301 @@
+use std::cmp::Ordering;
+use math::Vec2;
+use math::predicates::orient2d;
+use collections::{Arena, Handle};
+
+/// A convex hull grown one point at a time. Points inside the current hull are rejected in
+/// O(h), where h is the number of hull vertices, and points outside only replace the chain of
+/// vertices they can see, so tracking the hull of a stream of points never rebuilds it from
+/// scratch. Orientation tests use `predicates::orient2d`, so nearly collinear points are
+/// classified exactly.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::IncrementalHull;
+/// use candle::math::Vec2;
+/// let mut hull = IncrementalHull::new();
+/// for point in &[Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0)] {
+///     hull.insert(*point);
+/// }
+/// assert!(!hull.insert(Vec2::new(2.0, 2.0)));
+/// assert!(hull.insert(Vec2::new(6.0, 2.0)));
+/// assert_eq!(hull.points().len(), 5);
+/// // A point past a corner along an edge swallows that corner.
+/// assert!(hull.insert(Vec2::new(-2.0, 0.0)));
+/// assert!(!hull.points().contains(&Vec2::new(0.0, 0.0)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalHull {
+    points: Vec<Vec2>,
+}
+
+impl IncrementalHull {
+
+    /// Allocates a new, empty IncrementalHull.
+    pub fn new() -> IncrementalHull {
+        IncrementalHull { points: Vec::new() }
+    }
+
+    /// Returns the vertices of the hull in counterclockwise order, without collinear points.
+    /// Until three non-collinear points are inserted the hull is a single point or the two ends
+    /// of a segment.
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Returns true if `point` lies inside or on the boundary of the hull.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        match self.points.len() {
+            0 => false,
+            1 => self.points[0] == *point,
+            2 => orient2d(&self.points[0], &self.points[1], point) == Ordering::Equal &&
+                within_segment(&self.points[0], &self.points[1], point),
+            count => (0..count).all(|i| !self.sees_edge(i, point)),
+        }
+    }
+
+    /// Adds `point` to the hull, returning true if the hull changed.
+    pub fn insert(&mut self, point: Vec2) -> bool {
+        if self.contains(&point) {
+            return false;
+        }
+        if self.points.len() < 3 {
+            let mut points = self.points.clone();
+            points.push(point);
+            self.points = monotone_chain(points);
+            return true;
+        }
+
+        // The edges visible from an outside point form one contiguous chain; its inner vertices
+        // are replaced by the point.
+        let count = self.points.len();
+        let visible: Vec<bool> = (0..count).map(|i| self.sees_edge(i, &point)).collect();
+        let first = (0..count).find(|&i| visible[i] && !visible[(i + count - 1) % count]).unwrap_or(0);
+        let mut last = first;
+        while visible[(last + 1) % count] && (last + 1) % count != first {
+            last = (last + 1) % count;
+        }
+
+        let mut points = Vec::with_capacity(count + 1);
+        let mut index = (last + 1) % count;
+        loop {
+            points.push(self.points[index]);
+            if index == first {
+                break;
+            }
+            index = (index + 1) % count;
+        }
+        points.push(point);
+        self.points = points;
+        true
+    }
+
+    /// Returns true if `point` is strictly outside the edge starting at vertex `index`, or on
+    /// its line beyond its ends.
+    fn sees_edge(&self, index: usize, point: &Vec2) -> bool {
+        let a = &self.points[index];
+        let b = &self.points[(index + 1) % self.points.len()];
+        match orient2d(a, b, point) {
+            Ordering::Less => true,
+            Ordering::Equal => !within_segment(a, b, point),
+            Ordering::Greater => false,
+        }
+    }
+}
+
+/// Tracks the bounding box and an enclosing circle of a changing set of points. Inserting a
+/// point grows both in O(1). Removing a point that lies inside the bounds is O(1) too; removing
+/// one that touched them leaves the bounds conservative (still enclosing every point, but
+/// possibly larger than needed) until `rebuild_threshold` such removals accumulate, at which
+/// point they are rebuilt from the remaining points.
+///
+/// The circle is grown like Ritter's bounding sphere: it always encloses every point, but is
+/// usually somewhat larger than the minimal enclosing circle.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::IncrementalBounds;
+/// use candle::math::Vec2;
+/// let mut bounds = IncrementalBounds::new(1);
+/// let a = bounds.insert(Vec2::new(0.0, 0.0));
+/// bounds.insert(Vec2::new(2.0, 1.0));
+/// let c = bounds.insert(Vec2::new(5.0, -1.0));
+/// assert_eq!(bounds.bounds(), Some((Vec2::new(0.0, -1.0), Vec2::new(5.0, 1.0))));
+///
+/// bounds.remove(c);
+/// assert_eq!(bounds.bounds(), Some((Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0))));
+/// bounds.remove(a);
+/// assert_eq!(bounds.bounds(), Some((Vec2::new(2.0, 1.0), Vec2::new(2.0, 1.0))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalBounds {
+    points: Arena<Vec2>,
+    bounds: Option<(Vec2, Vec2)>,
+    circle: Option<(Vec2, f64)>,
+    stale_removals: usize,
+    /// Number of removals of boundary points tolerated before the bounds are rebuilt.
+    pub rebuild_threshold: usize,
+}
+
+impl IncrementalBounds {
+
+    /// Allocates new, empty IncrementalBounds that rebuild after `rebuild_threshold` removals of
+    /// boundary points. A threshold of one keeps the bounds always tight.
+    pub fn new(rebuild_threshold: usize) -> IncrementalBounds {
+        IncrementalBounds { points: Arena::new(), bounds: None, circle: None, stale_removals: 0, rebuild_threshold }
+    }
+
+    /// Returns the number of points tracked.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if no points are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the `(min, max)` corners of the bounding box, or `None` if there are no points.
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        self.bounds
+    }
+
+    /// Returns the center and radius of a circle enclosing every point, or `None` if there are
+    /// no points.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::IncrementalBounds;
+    /// use candle::math::Vec2;
+    /// let mut bounds = IncrementalBounds::new(4);
+    /// let points = [Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(2.0, -2.0)];
+    /// for point in &points {
+    ///     bounds.insert(*point);
+    /// }
+    /// let (center, radius) = bounds.circle().unwrap();
+    /// assert!(points.iter().all(|p| (p - center).length() <= radius + 1e-9));
+    /// assert!(radius < 2.5);
+    /// ```
+    pub fn circle(&self) -> Option<(Vec2, f64)> {
+        self.circle
+    }
+
+    /// Returns true if the bounding box is known to be tight around the current points, which
+    /// is the case unless boundary points were removed since the last rebuild.
+    pub fn is_tight(&self) -> bool {
+        self.stale_removals == 0
+    }
+
+    /// Adds a point, returning the handle used to remove it.
+    pub fn insert(&mut self, point: Vec2) -> Handle<Vec2> {
+        self.grow(&point);
+        self.points.insert(point)
+    }
+
+    /// Removes the point of `handle`, returning it, or `None` if it was already removed.
+    pub fn remove(&mut self, handle: Handle<Vec2>) -> Option<Vec2> {
+        let point = self.points.remove(handle)?;
+        if self.points.is_empty() {
+            self.bounds = None;
+            self.circle = None;
+            self.stale_removals = 0;
+            return Some(point);
+        }
+        if self.touches_bounds(&point) {
+            self.stale_removals += 1;
+            if self.stale_removals >= self.rebuild_threshold {
+                self.rebuild();
+            }
+        }
+        Some(point)
+    }
+
+    /// Recomputes the bounds from the remaining points, making them tight.
+    pub fn rebuild(&mut self) {
+        self.bounds = None;
+        self.circle = None;
+        self.stale_removals = 0;
+        let points: Vec<Vec2> = self.points.iter().map(|(_, point)| *point).collect();
+        for point in &points {
+            self.grow_box(point);
+        }
+        // Centering the rebuilt circle on the box keeps it independent of the order the points
+        // were inserted in.
+        if let Some((min, max)) = self.bounds {
+            let center = (min + max) / 2.0;
+            let radius = points.iter().map(|point| (point - center).length()).fold(0.0, f64::max);
+            self.circle = Some((center, radius));
+        }
+    }
+
+    fn grow(&mut self, point: &Vec2) {
+        self.grow_box(point);
+        self.circle = Some(match self.circle {
+            None => (*point, 0.0),
+            Some((center, radius)) => {
+                let offset = point - center;
+                let distance = offset.length();
+                if distance <= radius {
+                    (center, radius)
+                } else {
+                    // The smallest circle enclosing the old circle and the point.
+                    let new_radius = (radius + distance) / 2.0;
+                    (center + offset * ((new_radius - radius) / distance), new_radius)
+                }
+            }
+        });
+    }
+
+    fn grow_box(&mut self, point: &Vec2) {
+        self.bounds = Some(match self.bounds {
+            None => (*point, *point),
+            Some((min, max)) => (
+                Vec2::new(min.x.min(point.x), min.y.min(point.y)),
+                Vec2::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        });
+    }
+
+    fn touches_bounds(&self, point: &Vec2) -> bool {
+        let on_box = self.bounds.is_some_and(|(min, max)| {
+            point.x == min.x || point.x == max.x || point.y == min.y || point.y == max.y
+        });
+        let on_circle = self.circle.is_some_and(|(center, radius)| {
+            (point - center).length() >= radius * (1.0 - 1e-9)
+        });
+        on_box || on_circle
+    }
+}
+
+fn within_segment(a: &Vec2, b: &Vec2, point: &Vec2) -> bool {
+    point.x >= a.x.min(b.x) && point.x <= a.x.max(b.x) && point.y >= a.y.min(b.y) && point.y <= a.y.max(b.y)
+}
+
+/// Andrew's monotone chain, returning the counterclockwise hull without collinear points.
+fn monotone_chain(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+    for point in &points {
+        push_convex(&mut hull, 0, point);
+    }
+    hull.pop();
+    let lower = hull.len();
+    for point in points.iter().rev() {
+        push_convex(&mut hull, lower, point);
+    }
+    hull.pop();
+    hull
+}
+
+/// Pushes `point` on the chain starting at `start`, first popping the vertices that would not
+/// make a strict left turn.
+fn push_convex(hull: &mut Vec<Vec2>, start: usize, point: &Vec2) {
+    while hull.len() >= start + 2 && orient2d(&hull[hull.len() - 2], &hull[hull.len() - 1], point) != Ordering::Greater {
+        hull.pop();
+    }
+    hull.push(*point);
+}
@@ -0,0 +1,243 @@
+use std::cmp::Ordering;
+use math::Vec2;
+use math::predicates::orient2d;
+
+/// An infinite line through `origin` along `direction`, which doesn't need to be normalized:
+/// parameters passed to `point_at` are measured in multiples of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+impl Line {
+
+    /// Allocates a new Line through `origin` along `direction`.
+    pub fn new(origin: Vec2, direction: Vec2) -> Line {
+        Line { origin, direction }
+    }
+
+    /// Allocates a new Line through `a` and `b`, directed from `a` to `b`.
+    pub fn through(a: Vec2, b: Vec2) -> Line {
+        Line { origin: a, direction: b - a }
+    }
+
+    /// Returns the point at `origin + direction * t`.
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        self.origin + self.direction * t
+    }
+
+    /// Returns the parameter of the point of the line closest to `point`, or zero if the
+    /// direction is zero.
+    pub fn project(&self, point: &Vec2) -> f64 {
+        project(&self.origin, &self.direction, point)
+    }
+
+    /// Returns the point of the line closest to `point`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Line;
+    /// use candle::math::Vec2;
+    /// let line = Line::through(Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0));
+    /// assert_eq!(line.closest_point(&Vec2::new(-5.0, 3.0)), Vec2::new(-5.0, 1.0));
+    /// ```
+    pub fn closest_point(&self, point: &Vec2) -> Vec2 {
+        self.point_at(self.project(point))
+    }
+
+    /// Returns the signed distance from `point` to the line, positive on the left of
+    /// `direction`, or the distance to `origin` if the direction is zero.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        let length = self.direction.length();
+        if length == 0.0 {
+            return (point - self.origin).length();
+        }
+        let offset = point - self.origin;
+        (self.direction.x * offset.y - self.direction.y * offset.x) / length
+    }
+
+    /// Returns which side of the line `point` lies on: `Greater` on the left of `direction`,
+    /// `Less` on the right and `Equal` on the line. The test is exact for lines built with
+    /// `through`, up to the rounding of `b - a`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use candle::geometry::Line;
+    /// use candle::math::Vec2;
+    /// let line = Line::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0));
+    /// assert_eq!(line.side(&Vec2::new(5.0, 1.0)), Ordering::Greater);
+    /// assert_eq!(line.side(&Vec2::new(-5.0, -1.0)), Ordering::Less);
+    /// assert_eq!(line.side(&Vec2::new(9.0, 0.0)), Ordering::Equal);
+    /// ```
+    pub fn side(&self, point: &Vec2) -> Ordering {
+        orient2d(&self.origin, &(self.origin + self.direction), point)
+    }
+}
+
+/// The segment between the points `a` and `b`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Segment;
+/// use candle::math::Vec2;
+/// let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+/// assert_eq!(segment.length(), 4.0);
+/// assert_eq!(segment.point_at(0.25), Vec2::new(1.0, 0.0));
+/// assert_eq!(segment.closest_point(&Vec2::new(2.0, 3.0)), Vec2::new(2.0, 0.0));
+/// assert_eq!(segment.closest_point(&Vec2::new(7.0, 4.0)), Vec2::new(4.0, 0.0));
+/// assert_eq!(segment.distance(&Vec2::new(7.0, 4.0)), 5.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub a: Vec2,
+    pub b: Vec2,
+}
+
+impl Segment {
+
+    /// Allocates a new Segment from `a` to `b`.
+    pub fn new(a: Vec2, b: Vec2) -> Segment {
+        Segment { a, b }
+    }
+
+    /// Returns the length of the segment.
+    pub fn length(&self) -> f64 {
+        (self.b - self.a).length()
+    }
+
+    /// Returns the vector from `a` to `b`.
+    pub fn direction(&self) -> Vec2 {
+        self.b - self.a
+    }
+
+    /// Returns the midpoint of the segment.
+    pub fn midpoint(&self) -> Vec2 {
+        (self.a + self.b) / 2.0
+    }
+
+    /// Returns the point at `a + (b - a) * t`, which lies on the segment for `t` in `[0, 1]`.
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        self.a + (self.b - self.a) * t
+    }
+
+    /// Returns the parameter in `[0, 1]` of the point of the segment closest to `point`.
+    pub fn project(&self, point: &Vec2) -> f64 {
+        project(&self.a, &(self.b - self.a), point).clamp(0.0, 1.0)
+    }
+
+    /// Returns the point of the segment closest to `point`.
+    pub fn closest_point(&self, point: &Vec2) -> Vec2 {
+        self.point_at(self.project(point))
+    }
+
+    /// Returns the distance from `point` to the segment.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        (point - self.closest_point(point)).length()
+    }
+
+    /// Returns which side of the line through the segment `point` lies on, exactly: `Greater` on
+    /// the left going from `a` to `b`, `Less` on the right and `Equal` if collinear.
+    pub fn side(&self, point: &Vec2) -> Ordering {
+        orient2d(&self.a, &self.b, point)
+    }
+
+    /// Returns the infinite line through the segment.
+    pub fn line(&self) -> Line {
+        Line::through(self.a, self.b)
+    }
+
+    /// Returns true if `point` lies exactly on the segment.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        self.side(point) == Ordering::Equal && within_box(&self.a, &self.b, point)
+    }
+
+    /// Returns true if the segments cross or touch, including collinear segments that overlap.
+    /// The test is exact.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Segment;
+    /// use candle::math::Vec2;
+    /// let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0));
+    /// assert!(segment.intersects(&Segment::new(Vec2::new(0.0, 4.0), Vec2::new(4.0, 0.0))));
+    /// assert!(segment.intersects(&Segment::new(Vec2::new(2.0, 2.0), Vec2::new(4.0, 0.0))));
+    /// assert!(!segment.intersects(&Segment::new(Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0))));
+    /// ```
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let straddles = |p: Ordering, q: Ordering| p != Ordering::Equal && p == q.reverse();
+        if straddles(other.side(&self.a), other.side(&self.b)) && straddles(self.side(&other.a), self.side(&other.b)) {
+            return true;
+        }
+        other.contains(&self.a) || other.contains(&self.b) || self.contains(&other.a) || self.contains(&other.b)
+    }
+}
+
+/// A ray starting at `origin` and extending forever along `direction`, which doesn't need to be
+/// normalized.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Ray2;
+/// use candle::math::Vec2;
+/// let ray = Ray2::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 2.0));
+/// assert_eq!(ray.point_at(1.5), Vec2::new(0.0, 3.0));
+/// assert_eq!(ray.closest_point(&Vec2::new(1.0, 4.0)), Vec2::new(0.0, 4.0));
+/// assert_eq!(ray.closest_point(&Vec2::new(1.0, -4.0)), Vec2::new(0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray2 {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+impl Ray2 {
+
+    /// Allocates a new Ray2 from `origin` along `direction`.
+    pub fn new(origin: Vec2, direction: Vec2) -> Ray2 {
+        Ray2 { origin, direction }
+    }
+
+    /// Returns the point at `origin + direction * t`, which lies on the ray for `t >= 0`.
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        self.origin + self.direction * t
+    }
+
+    /// Returns the non-negative parameter of the point of the ray closest to `point`.
+    pub fn project(&self, point: &Vec2) -> f64 {
+        project(&self.origin, &self.direction, point).max(0.0)
+    }
+
+    /// Returns the point of the ray closest to `point`.
+    pub fn closest_point(&self, point: &Vec2) -> Vec2 {
+        self.point_at(self.project(point))
+    }
+
+    /// Returns the distance from `point` to the ray.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        (point - self.closest_point(point)).length()
+    }
+
+    /// Returns which side of the ray's supporting line `point` lies on, like `Line::side`.
+    pub fn side(&self, point: &Vec2) -> Ordering {
+        orient2d(&self.origin, &(self.origin + self.direction), point)
+    }
+
+    /// Returns the infinite line the ray lies on.
+    pub fn line(&self) -> Line {
+        Line::new(self.origin, self.direction)
+    }
+}
+
+fn within_box(a: &Vec2, b: &Vec2, point: &Vec2) -> bool {
+    point.x >= a.x.min(b.x) && point.x <= a.x.max(b.x) && point.y >= a.y.min(b.y) && point.y <= a.y.max(b.y)
+}
+
+fn project(origin: &Vec2, direction: &Vec2, point: &Vec2) -> f64 {
+    let squared = direction.dot(direction);
+    if squared == 0.0 {
+        return 0.0;
+    }
+    (point - origin).dot(direction) / squared
+}
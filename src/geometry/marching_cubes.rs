@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use math::Vec3;
+use geometry::aabb3::Aabb3;
+use geometry::mesh::Mesh;
+
+/// The faces of a cube as cycles of its corners, counterclockwise seen from outside. Corner `i`
+/// sits at `(i & 1, (i >> 1) & 1, (i >> 2) & 1)`.
+const FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2], [1, 3, 7, 5], [0, 1, 5, 4], [2, 6, 7, 3], [0, 2, 3, 1], [4, 5, 7, 6],
+];
+
+/// Extracts the isosurface of a scalar field at level `iso` with marching cubes, as a mesh
+/// enclosing the regions where the samples are at least `iso`: its triangles face towards lower
+/// values, and its smooth normals are computed from them. `samples` holds a `size[0]` by
+/// `size[1]` by `size[2]` block of samples, x varying fastest, and sample `(x, y, z)` sits at
+/// point `(x, y, z)`. Samples outside the block count as below `iso`, so surfaces reaching its
+/// sides are closed along them.
+///
+/// Rather than looking the triangles of each cube up in the classic table, the surface's
+/// outline on each face of the cube is traced as in `marching_squares`, and the outlines are
+/// joined into polygons and split into fans. Faces where diagonally opposite corners are above
+/// the level are resolved by the average of their four samples, which both cubes sharing the
+/// face agree on, so the mesh has no cracks. Vertices on shared cube edges are shared.
+///
+/// # Panics
+/// Panics if `samples` doesn't hold exactly `size[0] * size[1] * size[2]` values.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{marching_cubes, HalfEdgeMesh};
+/// use candle::math::Vec3;
+/// // A single sample above the level gives an octahedron around it.
+/// let mut samples = vec![0.0; 27];
+/// samples[13] = 1.0;
+/// let mesh = marching_cubes(&samples, [3, 3, 3], 0.5);
+/// assert_eq!((mesh.vertex_count(), mesh.triangle_count()), (6, 8));
+/// assert!(mesh.positions.contains(&Vec3::new(1.5, 1.0, 1.0)));
+/// assert!(HalfEdgeMesh::from_mesh(&mesh).unwrap().boundary_loops().is_empty());
+/// ```
+pub fn marching_cubes(samples: &[f64], size: [usize; 3], iso: f64) -> Mesh {
+    assert_eq!(samples.len(), size[0] * size[1] * size[2], "sample count doesn't match the block size");
+    let sample = |x: i64, y: i64, z: i64| {
+        if x < 0 || y < 0 || z < 0 || x >= size[0] as i64 || y >= size[1] as i64 || z >= size[2] as i64 {
+            None
+        } else {
+            Some(samples[x as usize + size[0] * (y as usize + size[1] * z as usize)]).filter(|value| !value.is_nan())
+        }
+    };
+
+    let mut mesh = Mesh::default();
+    let mut vertices: HashMap<(i64, i64, i64, i64, i64, i64), u32> = HashMap::new();
+    for z in -1..size[2] as i64 {
+        for y in -1..size[1] as i64 {
+            for x in -1..size[0] as i64 {
+                let corners: Vec<(i64, i64, i64)> = (0..8).map(|i| (x + (i & 1), y + ((i >> 1) & 1), z + ((i >> 2) & 1))).collect();
+                let values: Vec<Option<f64>> = corners.iter().map(|&(cx, cy, cz)| sample(cx, cy, cz)).collect();
+                let inside: Vec<bool> = values.iter().map(|value| value.is_some_and(|value| value >= iso)).collect();
+                if inside.iter().all(|&state| state) || inside.iter().all(|&state| !state) {
+                    continue;
+                }
+
+                // Trace the outline on each face, linking the cube edge where it comes into the
+                // face to the one where it leaves, as `marching_squares` does.
+                let mut links: HashMap<(usize, usize), ((usize, usize), usize)> = HashMap::new();
+                for (face_index, face) in FACES.iter().enumerate() {
+                    let edge = |i: usize| (face[i], face[(i + 1) % 4]);
+                    let starts: Vec<usize> = (0..4).filter(|&i| inside[face[i]] && !inside[face[(i + 1) % 4]]).collect();
+                    let ends: Vec<usize> = (0..4).filter(|&i| !inside[face[i]] && inside[face[(i + 1) % 4]]).collect();
+                    for &start in &starts {
+                        let end = if ends.len() == 1 {
+                            ends[0]
+                        } else {
+                            let face_values: Option<Vec<f64>> = face.iter().map(|&corner| values[corner]).collect();
+                            let joined = face_values.is_some_and(|values| values.iter().sum::<f64>() / 4.0 >= iso);
+                            if joined { (start + 1) % 4 } else { (start + 3) % 4 }
+                        };
+                        // The neighbouring face runs along the shared edge the other way.
+                        let (a, b) = edge(end);
+                        links.insert(edge(start), ((b, a), face_index));
+                    }
+                }
+
+                while let Some(&first) = links.keys().min() {
+                    let (mut polygon, mut faces) = (Vec::new(), Vec::new());
+                    let mut edge = first;
+                    while let Some((next, face)) = links.remove(&edge) {
+                        let (a, b) = edge;
+                        let (from, to) = (corners[a], corners[b]);
+                        let key = if from < to { (from.0, from.1, from.2, to.0, to.1, to.2) } else { (to.0, to.1, to.2, from.0, from.1, from.2) };
+                        let index = *vertices.entry(key).or_insert_with(|| {
+                            let (p, q) = (Vec3::new(from.0 as f64, from.1 as f64, from.2 as f64), Vec3::new(to.0 as f64, to.1 as f64, to.2 as f64));
+                            mesh.positions.push(match (values[a], values[b]) {
+                                (Some(u), Some(v)) => p + (q - p) * ((iso - u) / (v - u)),
+                                (Some(_), None) => p,
+                                _ => q,
+                            });
+                            (mesh.positions.len() - 1) as u32
+                        });
+                        polygon.push(index);
+                        faces.push(face);
+                        edge = next;
+                    }
+                    // A polygon crossing a face twice would have some of its fan's diagonals lie
+                    // on that face, where the neighbouring cube's polygon can have the same ones:
+                    // fan those around their centroid instead.
+                    faces.sort_unstable();
+                    if faces.windows(2).any(|pair| pair[0] == pair[1]) {
+                        let centroid = polygon.iter().map(|&i| mesh.positions[i as usize]).sum::<Vec3>() / polygon.len() as f64;
+                        mesh.positions.push(centroid);
+                        polygon.insert(0, (mesh.positions.len() - 1) as u32);
+                        let first = polygon[1];
+                        polygon.push(first);
+                    }
+                    // The outlines run counterclockwise around the inside seen from outside the
+                    // cube, which winds the polygon to face the inside: flip it.
+                    for i in 1..polygon.len() - 1 {
+                        mesh.indices.extend_from_slice(&[polygon[0], polygon[i + 1], polygon[i]]);
+                    }
+                }
+            }
+        }
+    }
+    mesh.compute_normals();
+    mesh
+}
+
+/// Extracts the isosurface at level `iso` of the field `f` over `bounds`, divided into
+/// `resolution` cells along each axis, as `marching_cubes` does for the samples of `f` at the
+/// corners of the cells. The surface is closed where it meets the sides of `bounds`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{marching_cubes_fn, Aabb3};
+/// use candle::math::Vec3;
+/// let bounds = Aabb3::new(Vec3::new(-2.0, -2.0, -2.0), Vec3::new(2.0, 2.0, 2.0));
+/// let ball = marching_cubes_fn(&bounds, [16, 16, 16], 0.0, |p| 1.0 - p.length());
+/// assert!(ball.positions.iter().all(|p| (p.length() - 1.0).abs() < 0.05));
+/// ```
+pub fn marching_cubes_fn<F>(bounds: &Aabb3, resolution: [usize; 3], iso: f64, mut f: F) -> Mesh
+    where F: FnMut(&Vec3) -> f64
+{
+    let size = [resolution[0] + 1, resolution[1] + 1, resolution[2] + 1];
+    let extent = bounds.max - bounds.min;
+    let step = Vec3::new(extent.x / resolution[0].max(1) as f64, extent.y / resolution[1].max(1) as f64, extent.z / resolution[2].max(1) as f64);
+    let mut samples = Vec::with_capacity(size[0] * size[1] * size[2]);
+    for z in 0..size[2] {
+        for y in 0..size[1] {
+            for x in 0..size[0] {
+                samples.push(f(&Vec3::new(bounds.min.x + step.x * x as f64, bounds.min.y + step.y * y as f64, bounds.min.z + step.z * z as f64)));
+            }
+        }
+    }
+    let mut mesh = marching_cubes(&samples, size, iso);
+    mesh.map_positions(|p| Vec3::new(bounds.min.x + step.x * p.x, bounds.min.y + step.y * p.y, bounds.min.z + step.z * p.z));
+    mesh
+}
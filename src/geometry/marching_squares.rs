@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use math::{Vec2, Grid};
+use geometry::polyline::Polyline;
+
+/// Traces the contours of a scalar field at level `iso` with marching squares: the outlines of
+/// the regions where the samples are at least `iso`, for terrain coastlines, metaballs and the
+/// like. Sample `(x, y)` of the grid sits at point `(x, y)`.
+///
+/// Contours are closed, repeating their first point at the end but no other point twice in a
+/// row, and wind counterclockwise around the regions at or above `iso`, clockwise around holes
+/// in them. Samples outside the grid count as below `iso`, so regions reaching the edge of the
+/// grid are closed along it, as are NaN samples. With `interpolate` the contours cross each cell edge where the linear
+/// interpolation between its samples reaches `iso`; without it they cross at the middle of the
+/// edge, giving the blocky outlines of the classic algorithm. Saddle cells, where diagonally
+/// opposite corners are above the level, are joined if the average of the four samples is too.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::marching_squares;
+/// use candle::math::{Grid, Vec2};
+/// let field = Grid::from_fn(3, 3, |x, y| if (x, y) == (1, 1) { 1.0 } else { 0.0 });
+/// let contours = marching_squares(&field, 0.5, true);
+/// assert_eq!(contours.len(), 1);
+/// assert_eq!(contours[0].points(), &[
+///     Vec2::new(0.5, 1.0), Vec2::new(1.0, 0.5), Vec2::new(1.5, 1.0), Vec2::new(1.0, 1.5), Vec2::new(0.5, 1.0),
+/// ]);
+/// let skewed = marching_squares(&field, 0.75, true);
+/// assert_eq!(skewed[0].points()[0], Vec2::new(0.75, 1.0));
+///
+/// // A region filling the grid is closed along its edge, through each corner once.
+/// let full = Grid::from_fn(3, 3, |_, _| 1.0);
+/// let contours = marching_squares(&full, 0.5, true);
+/// assert_eq!(contours.len(), 1);
+/// let points = contours[0].points();
+/// assert_eq!(points.len(), 9);
+/// assert_eq!(points.first(), points.last());
+/// assert!(points.windows(2).all(|pair| pair[0] != pair[1]));
+/// ```
+pub fn marching_squares(grid: &Grid<f64>, iso: f64, interpolate: bool) -> Vec<Polyline> {
+    let (width, height) = (grid.width() as i64, grid.height() as i64);
+    let sample = |x: i64, y: i64| {
+        if x < 0 || y < 0 || x >= width || y >= height {
+            None
+        } else {
+            Some(grid[(x as usize, y as usize)]).filter(|value| !value.is_nan())
+        }
+    };
+    let inside = |x: i64, y: i64| sample(x, y).is_some_and(|value| value >= iso);
+
+    // Edges are named by their lower left sample and whether they run up from it; each cell
+    // links the crossing where the contour enters it to the one where it leaves.
+    let mut links: HashMap<(i64, i64, bool), (i64, i64, bool)> = HashMap::new();
+    let mut order = Vec::new();
+    for y in -1..height {
+        for x in -1..width {
+            let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+            let edges = [(x, y, false), (x + 1, y, true), (x, y + 1, false), (x, y, true)];
+            let states: Vec<bool> = corners.iter().map(|&(cx, cy)| inside(cx, cy)).collect();
+            // Walking round the cell counterclockwise, the contour comes into it across the edges
+            // going from inside to outside, and leaves across those going the other way.
+            let starts: Vec<usize> = (0..4).filter(|&i| states[i] && !states[(i + 1) % 4]).collect();
+            let ends: Vec<usize> = (0..4).filter(|&i| !states[i] && states[(i + 1) % 4]).collect();
+            for &start in &starts {
+                let end = if ends.len() == 1 {
+                    ends[0]
+                } else {
+                    let values: Option<Vec<f64>> = corners.iter().map(|&(cx, cy)| sample(cx, cy)).collect();
+                    let joined = values.is_some_and(|values| values.iter().sum::<f64>() / 4.0 >= iso);
+                    if joined { (start + 1) % 4 } else { (start + 3) % 4 }
+                };
+                links.insert(edges[start], edges[end]);
+                order.push(edges[start]);
+            }
+        }
+    }
+
+    let crossing = |(x, y, up): (i64, i64, bool)| {
+        let (to_x, to_y) = if up { (x, y + 1) } else { (x + 1, y) };
+        let (from, to) = (Vec2::new(x as f64, y as f64), Vec2::new(to_x as f64, to_y as f64));
+        match (sample(x, y), sample(to_x, to_y)) {
+            (Some(a), Some(b)) => if interpolate { from + (to - from) * ((iso - a) / (b - a)) } else { (from + to) / 2.0 },
+            (Some(_), None) => from,
+            _ => to,
+        }
+    };
+    let mut contours = Vec::new();
+    for start in order {
+        if !links.contains_key(&start) {
+            continue;
+        }
+        let mut points = vec![crossing(start)];
+        let mut edge = start;
+        while let Some(next) = links.remove(&edge) {
+            // Both edges meeting at a corner of the grid cross at that corner, as do the edges
+            // meeting at a sample exactly at the level, which would repeat the point.
+            let point = crossing(next);
+            if points.last() != Some(&point) {
+                points.push(point);
+            }
+            edge = next;
+        }
+        contours.push(Polyline::new(points));
+    }
+    contours
+}
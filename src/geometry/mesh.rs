@@ -0,0 +1,176 @@
+use math::{Vec2, Vec3};
+use geometry::aabb3::Aabb3;
+use geometry::sphere::Sphere;
+
+/// An indexed triangle mesh: every three `indices` name the `positions` of a triangle's
+/// vertices, wound counterclockwise when seen from the front. Per-vertex `normals` and `uvs`
+/// are optional, but when present must have one entry per position.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Mesh;
+/// use candle::math::Vec3;
+/// let mut quad = Mesh::new(
+///     vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)],
+///     vec![0, 1, 2, 0, 2, 3],
+/// );
+/// assert_eq!(quad.triangle_count(), 2);
+/// quad.compute_normals();
+/// assert_eq!(quad.normals.as_ref().unwrap()[0], Vec3::new(0.0, 0.0, 1.0));
+/// assert_eq!(quad.bounds().unwrap().max, Vec3::new(1.0, 1.0, 0.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mesh {
+    pub positions: Vec<Vec3>,
+    pub indices: Vec<u32>,
+    pub normals: Option<Vec<Vec3>>,
+    pub uvs: Option<Vec<Vec2>>,
+}
+
+impl Mesh {
+
+    /// Allocates a new Mesh from its positions and triangle indices, without normals or UVs.
+    pub fn new(positions: Vec<Vec3>, indices: Vec<u32>) -> Mesh {
+        Mesh { positions, indices, normals: None, uvs: None }
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns the vertex indices of each triangle.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]])
+    }
+
+    /// Returns the positions of the vertices of the triangle at `index`.
+    pub fn triangle(&self, index: usize) -> [Vec3; 3] {
+        let t = &self.indices[index * 3..index * 3 + 3];
+        [self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]]
+    }
+
+    /// Returns true if the indices come in whole triangles and all name existing positions, and
+    /// the normals and UVs, if any, have one entry per position.
+    pub fn is_valid(&self) -> bool {
+        let count = self.positions.len();
+        self.indices.len().is_multiple_of(3) &&
+            self.indices.iter().all(|&index| (index as usize) < count) &&
+            self.normals.as_ref().is_none_or(|normals| normals.len() == count) &&
+            self.uvs.as_ref().is_none_or(|uvs| uvs.len() == count)
+    }
+
+    /// Computes smooth vertex normals, replacing any existing ones: each vertex gets the
+    /// average of the normals of the triangles around it, weighted by their areas so that
+    /// slivers barely count. Vertices not used by any triangle get a zero normal.
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vec3::ZERO; self.positions.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let (a, b, c) = (self.positions[triangle[0] as usize], self.positions[triangle[1] as usize], self.positions[triangle[2] as usize]);
+            // The cross product's length is twice the area, which provides the weighting.
+            let normal = (b - a).cross(&(c - a));
+            for &index in triangle {
+                normals[index as usize] = normals[index as usize] + normal;
+            }
+        }
+        for normal in normals.iter_mut() {
+            let length = normal.length();
+            if length > 0.0 {
+                *normal = *normal / length;
+            }
+        }
+        self.normals = Some(normals);
+    }
+
+    /// Returns the bounding box of the positions, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Aabb3> {
+        Aabb3::from_points(&self.positions)
+    }
+
+    /// Returns a bounding sphere of the positions, as `Sphere::from_points` computes it, or
+    /// `None` if there are none.
+    pub fn bounding_sphere(&self) -> Option<Sphere> {
+        Sphere::from_points(&self.positions)
+    }
+
+    /// Moves every position by `offset`.
+    pub fn translate(&mut self, offset: &Vec3) {
+        for position in self.positions.iter_mut() {
+            *position = *position + offset;
+        }
+    }
+
+    /// Scales every position by `factors` along each axis, about the origin. Normals are scaled
+    /// by the inverse factors and renormalized so they stay perpendicular to the surface, and
+    /// mirroring along an odd number of axes flips the triangles to keep them facing outwards.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Mesh;
+    /// use candle::math::Vec3;
+    /// let mut mesh = Mesh::new(vec![Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)], vec![0, 1, 2]);
+    /// mesh.scale(&Vec3::new(-2.0, 1.0, 1.0));
+    /// assert_eq!(mesh.positions[1], Vec3::new(-2.0, 0.0, 0.0));
+    /// assert_eq!(mesh.indices, vec![0, 2, 1]);
+    /// ```
+    pub fn scale(&mut self, factors: &Vec3) {
+        for position in self.positions.iter_mut() {
+            *position = Vec3::new(position.x * factors.x, position.y * factors.y, position.z * factors.z);
+        }
+        if let Some(normals) = self.normals.as_mut() {
+            for normal in normals.iter_mut() {
+                let scaled = Vec3::new(normal.x / factors.x, normal.y / factors.y, normal.z / factors.z);
+                let length = scaled.length();
+                *normal = if length > 0.0 && length.is_finite() { scaled / length } else { Vec3::ZERO };
+            }
+        }
+        if factors.x * factors.y * factors.z < 0.0 {
+            for triangle in self.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+
+    /// Rotates every position and normal counterclockwise by `angle` radians around the unit
+    /// `axis` through the origin, seen from the tip of the axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use candle::geometry::Mesh;
+    /// use candle::math::{Vec3, ApproxEq};
+    /// let mut mesh = Mesh::new(vec![Vec3::new(1.0, 0.0, 0.0)], vec![]);
+    /// mesh.rotate(&Vec3::new(0.0, 0.0, 1.0), PI / 2.0);
+    /// assert!(mesh.positions[0].approx_eq_eps(Vec3::new(0.0, 1.0, 0.0), Vec3::new(1e-12, 1e-12, 1e-12)));
+    /// ```
+    pub fn rotate(&mut self, axis: &Vec3, angle: f64) {
+        let (sin, cos) = angle.sin_cos();
+        // Rodrigues' rotation formula.
+        let rotate = |v: &Vec3| *v * cos + axis.cross(v) * sin + *axis * (axis.dot(v) * (1.0 - cos));
+        for position in self.positions.iter_mut() {
+            *position = rotate(position);
+        }
+        if let Some(normals) = self.normals.as_mut() {
+            for normal in normals.iter_mut() {
+                *normal = rotate(normal);
+            }
+        }
+    }
+
+    /// Replaces every position with `f(position)`, for transforms the other methods don't
+    /// cover. Existing normals are recomputed from the new positions, since a general map
+    /// doesn't say how they should change.
+    pub fn map_positions<F>(&mut self, f: F)
+        where F: FnMut(&Vec3) -> Vec3
+    {
+        self.positions = self.positions.iter().map(f).collect();
+        if self.normals.is_some() {
+            self.compute_normals();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+mod incremental;
+mod rect;
+mod circle;
+mod line;
+mod triangle;
+mod polygon;
+mod polyline;
+mod ellipse;
+mod arc;
+mod capsule;
+mod obb;
+mod rounded_rect;
+mod plane;
+mod aabb3;
+mod sphere;
+mod ray3;
+mod mesh;
+mod half_edge;
+mod hull;
+mod calipers;
+mod triangulate;
+mod decompose;
+mod marching_squares;
+mod marching_cubes;
+mod voronoi;
+mod visibility;
+mod skeleton;
+#[cfg(feature = "obj")]
+pub mod obj;
+pub mod csg;
+pub mod intersect;
+pub mod closest;
+pub mod offset;
+pub mod sdf;
+
+pub use self::incremental::{IncrementalHull, IncrementalBounds};
+pub use self::rect::Rect;
+pub use self::circle::Circle;
+pub use self::line::{Line, Segment, Ray2};
+pub use self::triangle::Triangle;
+pub use self::polygon::{Polygon, FillRule};
+pub use self::polyline::{Polyline, PolylineProjection};
+pub use self::ellipse::Ellipse;
+pub use self::arc::{Arc, ArcDirection};
+pub use self::capsule::Capsule2;
+pub use self::obb::Obb;
+pub use self::rounded_rect::RoundedRect;
+pub use self::plane::Plane;
+pub use self::aabb3::Aabb3;
+pub use self::sphere::Sphere;
+pub use self::ray3::{Ray3, TriangleHit};
+pub use self::mesh::Mesh;
+pub use self::half_edge::{HalfEdgeMesh, HalfEdge};
+pub use self::hull::{convex_hull, convex_hull_indices, concave_hull};
+pub use self::calipers::{min_area_obb, min_width};
+pub use self::triangulate::{triangulate, triangulate_with_holes};
+pub use self::decompose::convex_decomposition;
+pub use self::marching_squares::marching_squares;
+pub use self::marching_cubes::{marching_cubes, marching_cubes_fn};
+pub use self::voronoi::{voronoi, lloyd_relaxation, VoronoiCell};
+pub use self::visibility::visibility_polygon;
+pub use self::skeleton::{StraightSkeleton, SkeletonNode};
@@ -0,0 +1,118 @@
+use math::Vec2;
+use math::stats;
+use geometry::rect::Rect;
+
+/// An oriented bounding box: a rectangle centered on `center`, extending `half_extents.x` along
+/// its first axis and `half_extents.y` along its second, rotated counterclockwise by `rotation`
+/// radians. Points on the boundary belong to it.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::PI;
+/// use candle::geometry::{Obb, Rect};
+/// use candle::math::Vec2;
+/// let plank = Obb::new(Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.5), PI / 4.0);
+/// assert!(plank.contains(&Vec2::new(1.5, 1.5)));
+/// assert!(!plank.contains(&Vec2::new(1.5, -1.5)));
+/// assert!(plank.intersects_rect(&Rect::new(Vec2::new(1.5, 1.5), Vec2::new(3.0, 3.0))));
+/// assert!(!plank.intersects_rect(&Rect::new(Vec2::new(1.0, -3.0), Vec2::new(3.0, -1.0))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+    pub center: Vec2,
+    pub half_extents: Vec2,
+    pub rotation: f64,
+}
+
+impl Obb {
+
+    /// Allocates a new Obb, using the absolute values of the half extents.
+    pub fn new(center: Vec2, half_extents: Vec2, rotation: f64) -> Obb {
+        Obb { center, half_extents: Vec2::new(half_extents.x.abs(), half_extents.y.abs()), rotation }
+    }
+
+    /// Allocates a new Obb covering the same area as the axis-aligned `rect`.
+    pub fn from_rect(rect: &Rect) -> Obb {
+        Obb { center: rect.center(), half_extents: rect.size() / 2.0, rotation: 0.0 }
+    }
+
+    /// Fits a box around the points, aligned with their principal axes as computed by
+    /// `stats::pca`. This isn't the minimum-area box, but is close for elongated point sets and
+    /// takes O(n). Returns `None` if there are no points.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Obb;
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let points = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(3.0, 3.0)];
+    /// let obb = Obb::from_points(&points).unwrap();
+    /// assert!(obb.center.approx_eq_eps(Vec2::new(1.5, 1.5), Vec2::new(1e-12, 1e-12)));
+    /// assert!(obb.half_extents.x.approx_eq_eps(4.5f64.sqrt(), 1e-12));
+    /// assert!(obb.half_extents.y.approx_eq_eps(0.0, 1e-12));
+    /// ```
+    pub fn from_points(points: &[Vec2]) -> Option<Obb> {
+        let pca = stats::pca(points)?;
+        let (mut min, mut max) = (pca.to_local(&points[0]), pca.to_local(&points[0]));
+        for point in &points[1..] {
+            let local = pca.to_local(point);
+            min = Vec2::new(min.x.min(local.x), min.y.min(local.y));
+            max = Vec2::new(max.x.max(local.x), max.y.max(local.y));
+        }
+        let middle = (min + max) / 2.0;
+        let center = pca.mean + pca.axes.0 * middle.x + pca.axes.1 * middle.y;
+        let rotation = pca.axes.0.y.atan2(pca.axes.0.x);
+        Some(Obb { center, half_extents: (max - min) / 2.0, rotation })
+    }
+
+    /// Returns the unit vectors along the first and second axes of the box.
+    pub fn axes(&self) -> (Vec2, Vec2) {
+        let (sin, cos) = self.rotation.sin_cos();
+        (Vec2::new(cos, sin), Vec2::new(-sin, cos))
+    }
+
+    /// Returns the four corners counterclockwise, starting from the one at the negative end of
+    /// both axes.
+    pub fn corners(&self) -> [Vec2; 4] {
+        let (u, v) = self.axes();
+        let (u, v) = (u * self.half_extents.x, v * self.half_extents.y);
+        [self.center - u - v, self.center + u - v, self.center + u + v, self.center - u + v]
+    }
+
+    /// Returns the area of the box.
+    pub fn area(&self) -> f64 {
+        4.0 * self.half_extents.x * self.half_extents.y
+    }
+
+    /// Returns true if `point` lies inside or on the box.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        let (u, v) = self.axes();
+        let offset = point - self.center;
+        offset.dot(&u).abs() <= self.half_extents.x && offset.dot(&v).abs() <= self.half_extents.y
+    }
+
+    /// Returns true if the boxes overlap or touch, using the separating axis theorem on the
+    /// four axes of the two boxes.
+    pub fn intersects(&self, other: &Obb) -> bool {
+        let offset = other.center - self.center;
+        let (a, b) = (self.axes(), other.axes());
+        [a.0, a.1, b.0, b.1].iter().all(|axis| {
+            offset.dot(axis).abs() <= self.projected_radius(axis) + other.projected_radius(axis)
+        })
+    }
+
+    /// Returns true if the box overlaps or touches the rectangle.
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        self.intersects(&Obb::from_rect(rect))
+    }
+
+    /// Returns the smallest axis-aligned rectangle containing the box.
+    pub fn bounds(&self) -> Rect {
+        let half = Vec2::new(self.projected_radius(&Vec2::new(1.0, 0.0)), self.projected_radius(&Vec2::new(0.0, 1.0)));
+        Rect::from_center_size(self.center, half * 2.0)
+    }
+
+    fn projected_radius(&self, axis: &Vec2) -> f64 {
+        let (u, v) = self.axes();
+        self.half_extents.x * axis.dot(&u).abs() + self.half_extents.y * axis.dot(&v).abs()
+    }
+}
@@ -0,0 +1,213 @@
+//! Reading and writing meshes in the Wavefront OBJ format, enabled by the `obj` feature.
+//!
+//! Only geometry is supported: positions, texture coordinates, normals and polygonal faces,
+//! grouped into objects by `o` and `g` statements. Materials, smoothing groups, lines and
+//! curves are ignored.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use math::{Vec2, Vec3};
+use geometry::mesh::Mesh;
+
+/// A named mesh of an OBJ file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObjObject {
+    pub name: String,
+    pub mesh: Mesh,
+}
+
+/// Reads the objects of an OBJ file. Faces are split into triangle fans, and OBJ's separate
+/// position, texture coordinate and normal indices are merged into shared vertices, one per
+/// distinct combination. An object gets UVs or normals if any of its face vertices reference
+/// them, with zeros filled in for those that don't. Faces before any `o` or `g` statement go to
+/// an object with an empty name, and objects without faces are skipped.
+///
+/// Malformed statements and out-of-range indices are reported as `InvalidData` errors naming
+/// the offending line.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::obj;
+/// use candle::math::Vec3;
+/// let source = "\
+/// o quad
+/// v 0 0 0
+/// v 1 0 0
+/// v 1 1 0
+/// v 0 1 0
+/// vn 0 0 1
+/// f 1//1 2//1 3//1 4//1
+/// ";
+/// let objects = obj::load(source.as_bytes()).unwrap();
+/// assert_eq!(objects[0].name, "quad");
+/// assert_eq!(objects[0].mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+/// assert_eq!(objects[0].mesh.normals.as_ref().unwrap()[3], Vec3::new(0.0, 0.0, 1.0));
+/// assert!(obj::load("f 1 2 3".as_bytes()).is_err());
+/// ```
+pub fn load<R: BufRead>(reader: R) -> io::Result<Vec<ObjObject>> {
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut normals = Vec::new();
+    let mut objects = Vec::new();
+    let mut builder = ObjectBuilder::new(String::new());
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, message));
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let values = parse_floats(tokens, 3).ok_or_else(|| invalid("expected a position"))?;
+                positions.push(Vec3::new(values[0], values[1], values[2]));
+            }
+            Some("vt") => {
+                let values = parse_floats(tokens, 1).ok_or_else(|| invalid("expected texture coordinates"))?;
+                uvs.push(Vec2::new(values[0], values.get(1).cloned().unwrap_or(0.0)));
+            }
+            Some("vn") => {
+                let values = parse_floats(tokens, 3).ok_or_else(|| invalid("expected a normal"))?;
+                normals.push(Vec3::new(values[0], values[1], values[2]));
+            }
+            Some("f") => {
+                let mut face = Vec::new();
+                for token in tokens {
+                    let vertex = parse_face_vertex(token, positions.len(), uvs.len(), normals.len())
+                        .ok_or_else(|| invalid("invalid face vertex"))?;
+                    face.push(builder.vertex(vertex, &positions, &uvs, &normals));
+                }
+                if face.len() < 3 {
+                    return Err(invalid("faces need at least three vertices"));
+                }
+                for i in 1..face.len() - 1 {
+                    builder.indices.extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                }
+            }
+            Some("o") | Some("g") => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+                objects.extend(builder.finish());
+                builder = ObjectBuilder::new(name);
+            }
+            _ => {}
+        }
+    }
+    objects.extend(builder.finish());
+    Ok(objects)
+}
+
+/// Writes the objects as an OBJ file, each under an `o` statement. Texture coordinates and
+/// normals are written for the meshes that have them.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{obj, Mesh};
+/// use candle::geometry::obj::ObjObject;
+/// use candle::math::Vec3;
+/// let mesh = Mesh::new(vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.5)], vec![0, 1, 2]);
+/// let objects = vec![ObjObject { name: "triangle".to_string(), mesh }];
+/// let mut file = Vec::new();
+/// obj::save(&mut file, &objects).unwrap();
+/// assert_eq!(String::from_utf8(file.clone()).unwrap(), "o triangle\nv 0 0 0\nv 1 0 0\nv 0 1 0.5\nf 1 2 3\n");
+/// assert_eq!(obj::load(&file[..]).unwrap(), objects);
+/// ```
+pub fn save<W: Write>(mut writer: W, objects: &[ObjObject]) -> io::Result<()> {
+    let mut offset = 1;
+    for object in objects {
+        let mesh = &object.mesh;
+        writeln!(writer, "o {}", object.name)?;
+        for p in &mesh.positions {
+            writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+        }
+        for uv in mesh.uvs.iter().flatten() {
+            writeln!(writer, "vt {} {}", uv.x, uv.y)?;
+        }
+        for n in mesh.normals.iter().flatten() {
+            writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+        for triangle in mesh.triangles() {
+            write!(writer, "f")?;
+            for &index in &triangle {
+                let index = index as usize + offset;
+                match (mesh.uvs.is_some(), mesh.normals.is_some()) {
+                    (false, false) => write!(writer, " {}", index)?,
+                    (true, false) => write!(writer, " {}/{}", index, index)?,
+                    (false, true) => write!(writer, " {}//{}", index, index)?,
+                    (true, true) => write!(writer, " {}/{}/{}", index, index, index)?,
+                }
+            }
+            writeln!(writer)?;
+        }
+        offset += mesh.positions.len();
+    }
+    Ok(())
+}
+
+/// The zero-based position, texture coordinate and normal indices of a face vertex.
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+struct ObjectBuilder {
+    name: String,
+    vertices: HashMap<FaceVertex, u32>,
+    mesh: Mesh,
+    uvs: Vec<Option<Vec2>>,
+    normals: Vec<Option<Vec3>>,
+    indices: Vec<u32>,
+}
+
+impl ObjectBuilder {
+
+    fn new(name: String) -> ObjectBuilder {
+        ObjectBuilder { name, vertices: HashMap::new(), mesh: Mesh::default(), uvs: Vec::new(), normals: Vec::new(), indices: Vec::new() }
+    }
+
+    fn vertex(&mut self, vertex: FaceVertex, positions: &[Vec3], uvs: &[Vec2], normals: &[Vec3]) -> u32 {
+        let ObjectBuilder { vertices, mesh, uvs: object_uvs, normals: object_normals, .. } = self;
+        *vertices.entry(vertex).or_insert_with(|| {
+            mesh.positions.push(positions[vertex.0]);
+            object_uvs.push(vertex.1.map(|i| uvs[i]));
+            object_normals.push(vertex.2.map(|i| normals[i]));
+            (mesh.positions.len() - 1) as u32
+        })
+    }
+
+    fn finish(self) -> Option<ObjObject> {
+        if self.indices.is_empty() {
+            return None;
+        }
+        let mut mesh = self.mesh;
+        mesh.indices = self.indices;
+        if self.uvs.iter().any(Option::is_some) {
+            mesh.uvs = Some(self.uvs.into_iter().map(|uv| uv.unwrap_or(Vec2::new(0.0, 0.0))).collect());
+        }
+        if self.normals.iter().any(Option::is_some) {
+            mesh.normals = Some(self.normals.into_iter().map(|normal| normal.unwrap_or(Vec3::ZERO)).collect());
+        }
+        Some(ObjObject { name: self.name, mesh })
+    }
+}
+
+fn parse_floats<'a, I: Iterator<Item = &'a str>>(tokens: I, required: usize) -> Option<Vec<f64>> {
+    let values = tokens.map(|token| token.parse().ok()).collect::<Option<Vec<f64>>>()?;
+    if values.len() < required { None } else { Some(values) }
+}
+
+/// Parses a face vertex such as `3`, `3/1`, `3//2` or `3/1/2`, resolving the one-based and
+/// negative, relative indices of OBJ to zero-based ones.
+fn parse_face_vertex(token: &str, positions: usize, uvs: usize, normals: usize) -> Option<FaceVertex> {
+    let mut parts = token.split('/');
+    let position = resolve_index(parts.next()?, positions)?;
+    let uv = match parts.next() {
+        Some("") | None => None,
+        Some(part) => Some(resolve_index(part, uvs)?),
+    };
+    let normal = match parts.next() {
+        Some("") | None => None,
+        Some(part) => Some(resolve_index(part, normals)?),
+    };
+    Some((position, uv, normal))
+}
+
+fn resolve_index(token: &str, count: usize) -> Option<usize> {
+    let index: i64 = token.parse().ok()?;
+    let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+    if resolved < 0 || resolved >= count as i64 { None } else { Some(resolved as usize) }
+}
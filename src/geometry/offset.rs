@@ -0,0 +1,210 @@
+//! Offsetting: growing or shrinking polygons by a distance, and buffering polylines into the
+//! polygon covering everything within a radius of them, as needed to inflate obstacles by an
+//! agent's size or to draw thick outlines.
+//!
+//! Each edge is moved along its normal, and the gaps opened at corners are filled in with the
+//! chosen `Join`. Where the moved edges overlap instead, they are cut at their intersection.
+//! The results aren't cleaned up: offsetting inwards by more than a feature's thickness, or
+//! buffering a polyline around turns tighter than its radius, leaves self-intersecting loops
+//! that `Polygon::is_simple` detects. `StraightSkeleton::inset` shrinks polygons without
+//! folding, splitting them apart where they pinch.
+
+use math::Vec2;
+use geometry::arc::Arc;
+use geometry::polygon::Polygon;
+use geometry::polyline::Polyline;
+
+/// How the gap opened between two offset edges at a corner is filled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Join {
+    /// Extends both edges until they meet, unless the resulting spike would reach further than
+    /// `limit` times the offset distance from the corner, in which case the corner is beveled.
+    /// A limit of 2 bevels corners sharper than 60°.
+    Miter { limit: f64 },
+    /// Rounds the corner with an arc around it, flattened to within `tolerance`.
+    Round { tolerance: f64 },
+    /// Cuts the corner with a straight edge between the ends of the offset edges.
+    Bevel,
+}
+
+/// How the ends of a buffered polyline are closed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cap {
+    /// Closes the end with a straight edge through the endpoint.
+    Butt,
+    /// Extends the end by the radius before closing it.
+    Square,
+    /// Closes the end with a half circle around the endpoint, flattened to within `tolerance`.
+    Round { tolerance: f64 },
+}
+
+/// Offsets the polygon outwards by `distance`, or inwards if it is negative, whichever way it
+/// winds. The result winds counterclockwise. Repeated vertices are ignored, and polygons left
+/// with fewer than three vertices are returned as they are.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Polygon;
+/// use candle::geometry::offset::{self, Join};
+/// use candle::math::Vec2;
+/// let square = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0)]);
+/// let grown = offset::polygon(&square, 1.0, Join::Miter { limit: 2.0 });
+/// assert_eq!(grown.points(), &[Vec2::new(-1.0, -1.0), Vec2::new(5.0, -1.0), Vec2::new(5.0, 5.0), Vec2::new(-1.0, 5.0)]);
+/// assert_eq!(offset::polygon(&square, 1.0, Join::Bevel).len(), 8);
+/// assert_eq!(offset::polygon(&square, -1.0, Join::Bevel).area(), 4.0);
+///
+/// let rounded = offset::polygon(&square, 1.0, Join::Round { tolerance: 0.001 });
+/// assert!((rounded.area() - (16.0 + 16.0 + std::f64::consts::PI)).abs() < 0.01);
+/// ```
+pub fn polygon(polygon: &Polygon, distance: f64, join: Join) -> Polygon {
+    let mut points = ring(polygon.points());
+    if points.len() < 3 {
+        return polygon.clone();
+    }
+    if polygon.signed_area() < 0.0 {
+        points.reverse();
+    }
+    let count = points.len();
+    let mut result = Vec::with_capacity(count * 2);
+    for i in 0..count {
+        let (prev, next) = (points[(i + count - 1) % count], points[(i + 1) % count]);
+        corner(&mut result, &prev, &points[i], &next, distance, join);
+    }
+    Polygon::new(result)
+}
+
+/// Buffers the polyline by `radius`, returning the polygon covering the points within `radius`
+/// of it, wound counterclockwise, with corners filled in by `join` and ends closed by `cap`.
+/// Repeated vertices are ignored. A polyline with a single distinct vertex gives a disc for
+/// round caps, a square for square caps and an empty polygon for butt caps, as does a
+/// non-positive radius.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Polyline;
+/// use candle::geometry::offset::{self, Join, Cap};
+/// use candle::math::Vec2;
+/// let path = Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0)]);
+/// let road = offset::polyline(&path, 1.0, Join::Miter { limit: 2.0 }, Cap::Butt);
+/// assert_eq!(road.points(), &[
+///     Vec2::new(0.0, -1.0), Vec2::new(5.0, -1.0), Vec2::new(5.0, 4.0),
+///     Vec2::new(3.0, 4.0), Vec2::new(3.0, 1.0), Vec2::new(0.0, 1.0),
+/// ]);
+/// assert_eq!(road.area(), 16.0);
+/// let capped = offset::polyline(&path, 1.0, Join::Miter { limit: 2.0 }, Cap::Square);
+/// assert_eq!(capped.area(), 20.0);
+/// ```
+pub fn polyline(polyline: &Polyline, radius: f64, join: Join, cap: Cap) -> Polygon {
+    let points = distinct(polyline.points());
+    if points.is_empty() || radius <= 0.0 {
+        return Polygon::default();
+    }
+    let count = points.len();
+    if count == 1 {
+        let center = points[0];
+        return match cap {
+            Cap::Butt => Polygon::default(),
+            Cap::Square => Polygon::new(vec![
+                center + Vec2::new(-radius, -radius), center + Vec2::new(radius, -radius),
+                center + Vec2::new(radius, radius), center + Vec2::new(-radius, radius),
+            ]),
+            Cap::Round { tolerance } => {
+                let mut circle = Arc::new(center, radius, 0.0, 2.0 * std::f64::consts::PI).flatten(tolerance).into_points();
+                circle.pop();
+                Polygon::new(circle)
+            }
+        };
+    }
+
+    // Down the right side, around the end, back up the left side and around the start.
+    let mut result = Vec::with_capacity(count * 4);
+    let reversed: Vec<Vec2> = points.iter().rev().cloned().collect();
+    for side in [&points, &reversed].iter() {
+        result.push(side[0] + right_normal(&side[0], &side[1]) * radius);
+        for i in 1..count - 1 {
+            corner(&mut result, &side[i - 1], &side[i], &side[i + 1], radius, join);
+        }
+        let (before, end) = (side[count - 2], side[count - 1]);
+        let normal = right_normal(&before, &end);
+        let forward = Vec2::new(-normal.y, normal.x);
+        let (from, to) = (end + normal * radius, end - normal * radius);
+        match cap {
+            Cap::Butt => result.extend_from_slice(&[from, to]),
+            Cap::Square => result.extend_from_slice(&[from + forward * radius, to + forward * radius]),
+            Cap::Round { tolerance } => {
+                result.push(from);
+                let start = normal.y.atan2(normal.x);
+                let arc = Arc::new(end, radius, start, std::f64::consts::PI).flatten(tolerance).into_points();
+                result.extend_from_slice(&arc[1..arc.len() - 1]);
+                result.push(to);
+            }
+        }
+    }
+    Polygon::new(ring(&result))
+}
+
+/// Appends the offset of the corner at `point`, between the edges from `prev` and to `next`, to
+/// `result`: a single point where the offset edges cross, or the join between them.
+fn corner(result: &mut Vec<Vec2>, prev: &Vec2, point: &Vec2, next: &Vec2, distance: f64, join: Join) {
+    let (incoming, outgoing) = (point - prev, next - point);
+    let (n0, n1) = (right_normal(prev, point), right_normal(point, next));
+    let (from, to) = (point + n0 * distance, point + n1 * distance);
+    let turn = incoming.x * outgoing.y - incoming.y * outgoing.x;
+    let reverses = turn == 0.0 && incoming.dot(&outgoing) < 0.0;
+
+    // Where the corner turns towards the offset side, the offset edges overlap: cut them where
+    // they cross.
+    if turn * distance <= 0.0 && !reverses {
+        if turn == 0.0 {
+            result.push(from);
+        } else {
+            let t = ((to - from).x * outgoing.y - (to - from).y * outgoing.x) / turn;
+            result.push(from + incoming * t);
+        }
+        return;
+    }
+    match join {
+        Join::Bevel => result.extend_from_slice(&[from, to]),
+        Join::Miter { limit } => {
+            let cosine = n0.dot(&n1);
+            // The miter's tip lies 1 / cos(θ / 2) offset distances from the corner, where θ is
+            // the angle between the normals.
+            if !reverses && 2.0 / (1.0 + cosine) <= limit * limit {
+                result.push(point + (n0 + n1) * (distance / (1.0 + cosine)));
+            } else {
+                result.extend_from_slice(&[from, to]);
+            }
+        }
+        Join::Round { tolerance } => {
+            let (u, v) = (from - point, to - point);
+            let sweep = (u.x * v.y - u.y * v.x).atan2(u.dot(&v));
+            let sweep = if reverses { std::f64::consts::PI * distance.signum() } else { sweep };
+            let arc = Arc::new(*point, distance.abs(), u.y.atan2(u.x), sweep).flatten(tolerance).into_points();
+            result.push(from);
+            result.extend_from_slice(&arc[1..arc.len() - 1]);
+            result.push(to);
+        }
+    }
+}
+
+/// Returns the unit normal on the right of the direction from `a` to `b`, which points out of a
+/// counterclockwise polygon.
+fn right_normal(a: &Vec2, b: &Vec2) -> Vec2 {
+    let direction = (b - a).normalized();
+    Vec2::new(direction.y, -direction.x)
+}
+
+fn distinct(points: &[Vec2]) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    points.dedup();
+    points
+}
+
+/// Drops repeated vertices from a closed ring, including a last one repeating the first.
+fn ring(points: &[Vec2]) -> Vec<Vec2> {
+    let mut points = distinct(points);
+    while points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    points
+}
@@ -0,0 +1,100 @@
+use math::Vec3;
+
+/// A plane in 3D: the points `p` for which `normal.dot(p) + d` is zero. The `normal` has unit
+/// length, so that expression is the signed distance from `p` to the plane, positive on the side
+/// the normal points to.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Plane;
+/// use candle::math::Vec3;
+/// let floor = Plane::from_point_normal(&Vec3::new(0.0, 1.0, 0.0), &Vec3::new(0.0, 2.0, 0.0));
+/// assert_eq!(floor.d, -1.0);
+/// assert_eq!(floor.distance(&Vec3::new(5.0, 3.0, -2.0)), 2.0);
+/// assert_eq!(floor.project(&Vec3::new(5.0, 3.0, -2.0)), Vec3::new(5.0, 1.0, -2.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f64,
+}
+
+impl Plane {
+
+    /// Allocates a new Plane from the equation `normal.dot(p) + d = 0`, scaling both so the
+    /// normal has unit length. A zero normal describes no plane and gives NaN values.
+    pub fn new(normal: Vec3, d: f64) -> Plane {
+        let length = normal.length();
+        Plane { normal: normal / length, d: d / length }
+    }
+
+    /// Allocates a new Plane through `point`, facing `normal`, which doesn't need to be
+    /// normalized.
+    pub fn from_point_normal(point: &Vec3, normal: &Vec3) -> Plane {
+        let normal = normal.normalized();
+        Plane { normal, d: -normal.dot(point) }
+    }
+
+    /// Allocates a new Plane through the three points, facing the side from which they turn
+    /// counterclockwise. Returns `None` if the points are collinear.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Plane;
+    /// use candle::math::Vec3;
+    /// let plane = Plane::from_points(&Vec3::new(0.0, 0.0, 2.0), &Vec3::new(1.0, 0.0, 2.0), &Vec3::new(0.0, 1.0, 2.0)).unwrap();
+    /// assert_eq!(plane.normal, Vec3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(plane.d, -2.0);
+    /// assert_eq!(Plane::from_points(&Vec3::ZERO, &Vec3::new(1.0, 1.0, 1.0), &Vec3::new(2.0, 2.0, 2.0)), None);
+    /// ```
+    pub fn from_points(a: &Vec3, b: &Vec3, c: &Vec3) -> Option<Plane> {
+        let normal = (b - a).cross(&(c - a));
+        if normal.length() == 0.0 {
+            return None;
+        }
+        Some(Plane::from_point_normal(a, &normal))
+    }
+
+    /// Returns the signed distance from `point` to the plane, positive on the side the normal
+    /// points to.
+    pub fn distance(&self, point: &Vec3) -> f64 {
+        self.normal.dot(point) + self.d
+    }
+
+    /// Returns the point of the plane closest to `point`.
+    pub fn project(&self, point: &Vec3) -> Vec3 {
+        point - self.normal * self.distance(point)
+    }
+
+    /// Returns the same plane facing the other way.
+    pub fn flipped(&self) -> Plane {
+        Plane { normal: -self.normal, d: -self.d }
+    }
+
+    /// Returns the parameter `t` at which the ray `origin + direction * t` crosses the plane, or
+    /// `None` if the ray is parallel to the plane or crosses it behind its origin. Rays starting
+    /// on the plane hit it at zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Plane;
+    /// use candle::math::Vec3;
+    /// let floor = Plane::new(Vec3::new(0.0, 1.0, 0.0), 0.0);
+    /// let origin = Vec3::new(1.0, 4.0, 0.0);
+    /// assert_eq!(floor.intersect_ray(&origin, &Vec3::new(1.0, -2.0, 0.0)), Some(2.0));
+    /// assert_eq!(floor.intersect_ray(&origin, &Vec3::new(0.0, 1.0, 0.0)), None);
+    /// assert_eq!(floor.intersect_ray(&origin, &Vec3::new(1.0, 0.0, 0.0)), None);
+    /// ```
+    pub fn intersect_ray(&self, origin: &Vec3, direction: &Vec3) -> Option<f64> {
+        let speed = self.normal.dot(direction);
+        let distance = self.distance(origin);
+        if distance == 0.0 {
+            return Some(0.0);
+        }
+        if speed == 0.0 {
+            return None;
+        }
+        let t = -distance / speed;
+        if t < 0.0 { None } else { Some(t) }
+    }
+}
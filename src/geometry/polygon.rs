@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use math::Vec2;
+use math::predicates::orient2d;
+use geometry::line::Segment;
+use geometry::rect::Rect;
+use geometry::circle::Circle;
+
+/// A polygon stored as a closed ring of vertices: the last vertex connects back to the first, so
+/// it must not be repeated. The ring may wind either way; algorithms that need a particular
+/// winding should call `make_counterclockwise` first.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use candle::geometry::Polygon;
+/// use candle::math::Vec2;
+/// let square = Polygon::new(vec![
+///     Vec2::new(0.0, 0.0), Vec2::new(0.0, 2.0), Vec2::new(2.0, 2.0), Vec2::new(2.0, 0.0),
+/// ]);
+/// assert_eq!(square.signed_area(), -4.0);
+/// assert_eq!(square.winding(), Ordering::Less);
+/// assert_eq!(square.centroid(), Some(Vec2::new(1.0, 1.0)));
+/// assert_eq!(square.perimeter(), 8.0);
+/// assert!(square.is_convex());
+/// assert!(square.contains(&Vec2::new(1.0, 1.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon {
+    points: Vec<Vec2>,
+}
+
+impl Polygon {
+
+    /// Allocates a new Polygon from its ring of vertices.
+    pub fn new(points: Vec<Vec2>) -> Polygon {
+        Polygon { points }
+    }
+
+    /// Returns the vertices of the ring.
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Returns the vertices of the ring mutably.
+    pub fn points_mut(&mut self) -> &mut Vec<Vec2> {
+        &mut self.points
+    }
+
+    /// Consumes the polygon, returning its vertices.
+    pub fn into_points(self) -> Vec<Vec2> {
+        self.points
+    }
+
+    /// Returns the number of vertices.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the polygon has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the edges of the ring, the last one closing it back to the first vertex.
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let count = self.points.len();
+        (0..count).map(move |i| Segment::new(self.points[i], self.points[(i + 1) % count]))
+    }
+
+    /// Returns the area enclosed by the ring, positive if it winds counterclockwise and negative
+    /// if it winds clockwise. Vertices are taken relative to the first one, which keeps the
+    /// result accurate for polygons far from the origin.
+    pub fn signed_area(&self) -> f64 {
+        let origin = match self.points.first() {
+            Some(&origin) => origin,
+            None => return 0.0,
+        };
+        self.points.windows(2).map(|pair| {
+            let (a, b) = (pair[0] - origin, pair[1] - origin);
+            a.x * b.y - a.y * b.x
+        }).sum::<f64>() / 2.0
+    }
+
+    /// Returns the area enclosed by the ring.
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Returns the winding of the ring: `Greater` if counterclockwise, `Less` if clockwise and
+    /// `Equal` if it encloses no area.
+    pub fn winding(&self) -> Ordering {
+        self.signed_area().partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+    }
+
+    /// Reverses the ring if it winds clockwise.
+    pub fn make_counterclockwise(&mut self) {
+        if self.winding() == Ordering::Less {
+            self.points.reverse();
+        }
+    }
+
+    /// Reverses the ring if it winds counterclockwise.
+    pub fn make_clockwise(&mut self) {
+        if self.winding() == Ordering::Greater {
+            self.points.reverse();
+        }
+    }
+
+    /// Returns the centroid of the enclosed area, or `None` if it encloses no area.
+    pub fn centroid(&self) -> Option<Vec2> {
+        let origin = *self.points.first()?;
+        let mut sum = Vec2::new(0.0, 0.0);
+        let mut twice_area = 0.0;
+        for pair in self.points.windows(2) {
+            let (a, b) = (pair[0] - origin, pair[1] - origin);
+            let cross = a.x * b.y - a.y * b.x;
+            sum = sum + (a + b) * cross;
+            twice_area += cross;
+        }
+        if twice_area == 0.0 {
+            return None;
+        }
+        Some(origin + sum / (3.0 * twice_area))
+    }
+
+    /// Returns the length of the ring, including the closing edge.
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|edge| edge.length()).sum()
+    }
+
+    /// Returns the bounding rectangle of the vertices, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::from_points(&self.points)
+    }
+
+    /// Returns true if the polygon is convex: every corner turns the same way and the ring goes
+    /// around only once. Collinear vertices are allowed. Polygons with fewer than three vertices
+    /// or no area are not convex.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polygon;
+    /// use candle::math::Vec2;
+    /// let arrow = Polygon::new(vec![
+    ///     Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(0.0, 2.0), Vec2::new(1.0, 1.0),
+    /// ]);
+    /// assert!(!arrow.is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        let count = self.points.len();
+        let winding = self.winding();
+        if count < 3 || winding == Ordering::Equal {
+            return false;
+        }
+        let mut turning = 0.0;
+        for i in 0..count {
+            let (a, b, c) = (self.points[i], self.points[(i + 1) % count], self.points[(i + 2) % count]);
+            let turn = orient2d(&a, &b, &c);
+            if turn == winding.reverse() {
+                return false;
+            }
+            let (ab, bc) = (b - a, c - b);
+            turning += (ab.x * bc.y - ab.y * bc.x).atan2(ab.dot(&bc));
+        }
+        // A star turns the same way at every corner but goes around more than once.
+        turning.abs() < 3.0 * PI
+    }
+
+    /// Returns the indices of the first two edges found to intersect, or `None` if the ring is
+    /// simple. Edge `i` runs from vertex `i` to the next one. Consecutive edges may only share
+    /// their common vertex, so repeated vertices and edges folding back onto the previous one
+    /// count as intersections. The tests are exact and take O(n²).
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polygon;
+    /// use candle::math::Vec2;
+    /// let bowtie = Polygon::new(vec![
+    ///     Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(2.0, 0.0), Vec2::new(0.0, 2.0),
+    /// ]);
+    /// assert_eq!(bowtie.self_intersection(), Some((0, 2)));
+    /// assert!(!bowtie.is_simple());
+    /// ```
+    pub fn self_intersection(&self) -> Option<(usize, usize)> {
+        let edges: Vec<Segment> = self.edges().collect();
+        let count = edges.len();
+        for i in 0..count {
+            let edge = &edges[i];
+            if edge.a == edge.b {
+                return Some((i, (i + 1) % count));
+            }
+            let next = &edges[(i + 1) % count];
+            if count > 2 && edge.side(&next.b) == Ordering::Equal && (next.b - edge.b).dot(&(edge.a - edge.b)) > 0.0 {
+                return Some((i, (i + 1) % count));
+            }
+            // Skip the two neighbours, which share a vertex with this edge.
+            for (j, other) in edges.iter().enumerate().skip(i + 2) {
+                if i == 0 && j == count - 1 {
+                    continue;
+                }
+                if edge.intersects(other) {
+                    return Some((i, j));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns true if the polygon has at least three vertices and its edges only meet at their
+    /// shared vertices.
+    pub fn is_simple(&self) -> bool {
+        self.points.len() >= 3 && self.self_intersection().is_none()
+    }
+
+    /// Returns true if `point` lies inside the polygon or on its boundary. Self-intersecting
+    /// polygons use the non-zero winding rule. The test is exact.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        self.contains_with(point, FillRule::NonZero)
+    }
+
+    /// Returns true if `point` lies inside the polygon under `rule`, or on its boundary. The two
+    /// rules only differ for self-intersecting polygons, in the regions they wind around more
+    /// than once. The test is exact.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::{Polygon, FillRule};
+    /// use candle::math::Vec2;
+    /// // A pentagram, whose center is wound around twice.
+    /// let star = Polygon::new((0..5).map(|i| {
+    ///     let angle = std::f64::consts::PI * (0.5 + 0.8 * i as f64);
+    ///     Vec2::new(angle.cos(), angle.sin())
+    /// }).collect());
+    /// let center = Vec2::new(0.0, 0.0);
+    /// assert!(star.contains_with(&center, FillRule::NonZero));
+    /// assert!(!star.contains_with(&center, FillRule::EvenOdd));
+    /// assert!(star.contains_with(&star.points()[0], FillRule::EvenOdd));
+    /// ```
+    pub fn contains_with(&self, point: &Vec2, rule: FillRule) -> bool {
+        let mut winding = 0i32;
+        for edge in self.edges() {
+            if edge.contains(point) {
+                return true;
+            }
+            if edge.a.y <= point.y {
+                if edge.b.y > point.y && edge.side(point) == Ordering::Greater {
+                    winding += 1;
+                }
+            } else if edge.b.y <= point.y && edge.side(point) == Ordering::Less {
+                winding -= 1;
+            }
+        }
+        match rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Returns true if the disc lies inside the polygon, touching its boundary from inside
+    /// included: its center must be inside and no edge may pass closer to it than its radius.
+    /// Every edge counts as boundary, so for self-intersecting polygons, discs crossing edges
+    /// that lie within the filled region are rejected too.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::{Polygon, Circle};
+    /// use candle::math::Vec2;
+    /// let square = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0)]);
+    /// assert!(square.contains_circle(&Circle::new(Vec2::new(2.0, 2.0), 2.0)));
+    /// assert!(!square.contains_circle(&Circle::new(Vec2::new(1.0, 2.0), 1.5)));
+    /// ```
+    pub fn contains_circle(&self, circle: &Circle) -> bool {
+        self.contains(&circle.center) && self.edges().all(|edge| edge.distance(&circle.center) >= circle.radius)
+    }
+}
+
+/// How `Polygon::contains_with` decides which regions of a self-intersecting polygon are inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Points the boundary winds around a non-zero number of times are inside.
+    NonZero,
+    /// Points the boundary winds around an odd number of times are inside.
+    EvenOdd,
+}
+
+impl From<Vec<Vec2>> for Polygon {
+    fn from(points: Vec<Vec2>) -> Polygon {
+        Polygon::new(points)
+    }
+}
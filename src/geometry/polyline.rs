@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use math::Vec2;
+use geometry::line::Segment;
+
+/// An open chain of vertices, such as a path, a stroke or a road, with its cumulative arc length
+/// precomputed so distance queries along it take O(log n).
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Polyline;
+/// use candle::math::Vec2;
+/// let path = Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0), Vec2::new(3.0, 4.0)]);
+/// assert_eq!(path.length(), 7.0);
+/// assert_eq!(path.cumulative_lengths(), &[0.0, 3.0, 7.0]);
+/// assert_eq!(path.point_at_distance(5.0), Some(Vec2::new(3.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polyline {
+    points: Vec<Vec2>,
+    lengths: Vec<f64>,
+}
+
+/// The point of a polyline closest to a query point, as returned by `Polyline::project`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolylineProjection {
+    /// The closest point on the polyline.
+    pub point: Vec2,
+    /// The index of the segment holding the point, which runs from vertex `segment` to the next.
+    pub segment: usize,
+    /// The arc length from the start of the polyline to the point.
+    pub arc_length: f64,
+    /// The distance from the query point to the polyline.
+    pub distance: f64,
+}
+
+impl Polyline {
+
+    /// Allocates a new Polyline through the points, in order.
+    pub fn new(points: Vec<Vec2>) -> Polyline {
+        let mut lengths = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                total += (point - points[i - 1]).length();
+            }
+            lengths.push(total);
+        }
+        Polyline { points, lengths }
+    }
+
+    /// Returns the vertices.
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Consumes the polyline, returning its vertices.
+    pub fn into_points(self) -> Vec<Vec2> {
+        self.points
+    }
+
+    /// Returns the number of vertices.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the polyline has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the arc length from the first vertex to each vertex.
+    pub fn cumulative_lengths(&self) -> &[f64] {
+        &self.lengths
+    }
+
+    /// Returns the total length.
+    pub fn length(&self) -> f64 {
+        self.lengths.last().cloned().unwrap_or(0.0)
+    }
+
+    /// Returns the segments between consecutive vertices.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.points.windows(2).map(|pair| Segment::new(pair[0], pair[1]))
+    }
+
+    /// Returns the point at the arc length `distance` from the first vertex, clamped to the ends
+    /// of the polyline, or `None` if it has no vertices.
+    pub fn point_at_distance(&self, distance: f64) -> Option<Vec2> {
+        let last = self.points.len().checked_sub(1)?;
+        if last == 0 {
+            return Some(self.points[0]);
+        }
+        let distance = distance.clamp(0.0, self.length());
+        // The first vertex at or past the distance ends the segment holding it.
+        let end = self.lengths.partition_point(|&length| length < distance).clamp(1, last);
+        let span = self.lengths[end] - self.lengths[end - 1];
+        let t = if span > 0.0 { (distance - self.lengths[end - 1]) / span } else { 0.0 };
+        Some(Segment::new(self.points[end - 1], self.points[end]).point_at(t))
+    }
+
+    /// Returns `count` points spaced evenly by arc length from the first vertex to the last,
+    /// which evens out the density of hand-drawn strokes or paths with uneven vertices. Returns
+    /// an empty polyline if it has no vertices or `count` is zero, and the first vertex alone
+    /// if `count` is one.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polyline;
+    /// use candle::math::Vec2;
+    /// let path = Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(4.0, 0.0)]);
+    /// let even = path.resample(5);
+    /// assert_eq!(even.points()[1], Vec2::new(1.0, 0.0));
+    /// assert_eq!(even.points()[3], Vec2::new(3.0, 0.0));
+    /// ```
+    pub fn resample(&self, count: usize) -> Polyline {
+        if self.points.is_empty() || count == 0 {
+            return Polyline::default();
+        }
+        if count == 1 {
+            return Polyline::new(vec![self.points[0]]);
+        }
+        let step = self.length() / (count - 1) as f64;
+        Polyline::new((0..count).filter_map(|i| self.point_at_distance(step * i as f64)).collect())
+    }
+
+    /// Simplifies the polyline with the Douglas–Peucker algorithm: the endpoints are kept, along
+    /// with, recursively, the vertex furthest from the segment joining the current pair of kept
+    /// ones while it lies further than `tolerance`. Every dropped vertex is within `tolerance`
+    /// of the result, which makes this the method of choice when the error must stay bounded.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polyline;
+    /// use candle::math::Vec2;
+    /// let stroke = Polyline::new(vec![
+    ///     Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.1), Vec2::new(2.0, -0.1), Vec2::new(3.0, 5.0),
+    ///     Vec2::new(4.0, 6.0), Vec2::new(5.0, 7.0),
+    /// ]);
+    /// assert_eq!(stroke.simplified(0.5).points(), &[Vec2::new(0.0, 0.0), Vec2::new(2.0, -0.1), Vec2::new(3.0, 5.0), Vec2::new(5.0, 7.0)]);
+    /// ```
+    pub fn simplified(&self, tolerance: f64) -> Polyline {
+        let count = self.points.len();
+        if count < 3 {
+            return self.clone();
+        }
+        let mut keep = vec![false; count];
+        keep[0] = true;
+        keep[count - 1] = true;
+        let mut pending = vec![(0, count - 1)];
+        while let Some((first, last)) = pending.pop() {
+            let chord = Segment::new(self.points[first], self.points[last]);
+            let furthest = (first + 1..last)
+                .map(|i| (chord.distance(&self.points[i]), i))
+                .max_by(|p, q| p.0.total_cmp(&q.0));
+            if let Some((distance, index)) = furthest {
+                if distance > tolerance {
+                    keep[index] = true;
+                    pending.push((first, index));
+                    pending.push((index, last));
+                }
+            }
+        }
+        Polyline::new(self.points.iter().zip(keep).filter(|&(_, kept)| kept).map(|(point, _)| *point).collect())
+    }
+
+    /// Simplifies the polyline with the Visvalingam–Whyatt algorithm: the vertex forming the
+    /// smallest triangle with its neighbours is dropped, over and over, while that triangle's
+    /// area is below `min_area`. The endpoints are kept. Measuring importance by area rather
+    /// than distance removes small wiggles more evenly, which tends to look more natural for
+    /// hand-drawn strokes, but puts no bound on how far the result strays.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polyline;
+    /// use candle::math::Vec2;
+    /// let stroke = Polyline::new(vec![
+    ///     Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.1), Vec2::new(2.0, -0.1), Vec2::new(3.0, 5.0),
+    ///     Vec2::new(4.0, 6.0), Vec2::new(5.0, 7.0),
+    /// ]);
+    /// assert_eq!(stroke.simplified_by_area(0.5).points(), &[Vec2::new(0.0, 0.0), Vec2::new(2.0, -0.1), Vec2::new(3.0, 5.0), Vec2::new(5.0, 7.0)]);
+    /// ```
+    pub fn simplified_by_area(&self, min_area: f64) -> Polyline {
+        let count = self.points.len();
+        if count < 3 {
+            return self.clone();
+        }
+        let area = |a: usize, b: usize, c: usize| {
+            let (ab, ac) = (self.points[b] - self.points[a], self.points[c] - self.points[a]);
+            (ab.x * ac.y - ab.y * ac.x).abs() / 2.0
+        };
+        let mut prev: Vec<usize> = (0..count).map(|i| i.wrapping_sub(1)).collect();
+        let mut next: Vec<usize> = (1..=count).collect();
+        let mut areas: Vec<f64> = (0..count)
+            .map(|i| if i == 0 || i == count - 1 { f64::INFINITY } else { area(i - 1, i, i + 1) })
+            .collect();
+        let mut heap: BinaryHeap<Candidate> = (1..count - 1).map(|index| Candidate { area: areas[index], index }).collect();
+        while let Some(Candidate { area: smallest, index }) = heap.pop() {
+            if smallest >= min_area {
+                break;
+            }
+            if smallest != areas[index] {
+                continue;
+            }
+            areas[index] = f64::NAN;
+            let (p, n) = (prev[index], next[index]);
+            next[p] = n;
+            prev[n] = p;
+            // A neighbour's triangle can't get less important than the one just removed, or
+            // vertices would be dropped in an order that depends on earlier removals.
+            for &neighbor in &[p, n] {
+                if neighbor != 0 && neighbor != count - 1 {
+                    areas[neighbor] = area(prev[neighbor], neighbor, next[neighbor]).max(smallest);
+                    heap.push(Candidate { area: areas[neighbor], index: neighbor });
+                }
+            }
+        }
+        Polyline::new(self.points.iter().zip(areas).filter(|&(_, area)| !area.is_nan()).map(|(point, _)| *point).collect())
+    }
+
+    /// Finds the point of the polyline closest to `point`, or returns `None` if it has no
+    /// vertices. Ties go to the earliest segment.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Polyline;
+    /// use candle::math::Vec2;
+    /// let path = Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0)]);
+    /// let projection = path.project(&Vec2::new(6.0, 3.0)).unwrap();
+    /// assert_eq!(projection.point, Vec2::new(4.0, 3.0));
+    /// assert_eq!(projection.segment, 1);
+    /// assert_eq!(projection.arc_length, 7.0);
+    /// assert_eq!(projection.distance, 2.0);
+    /// ```
+    pub fn project(&self, point: &Vec2) -> Option<PolylineProjection> {
+        let first = *self.points.first()?;
+        let mut best = PolylineProjection { point: first, segment: 0, arc_length: 0.0, distance: (point - first).length() };
+        for (i, segment) in self.segments().enumerate() {
+            let t = segment.project(point);
+            let closest = segment.point_at(t);
+            let distance = (point - closest).length();
+            if distance < best.distance {
+                let arc_length = self.lengths[i] + (self.lengths[i + 1] - self.lengths[i]) * t;
+                best = PolylineProjection { point: closest, segment: i, arc_length, distance };
+            }
+        }
+        Some(best)
+    }
+
+    /// Returns the point of the polyline closest to `point`, or `None` if it has no vertices.
+    pub fn closest_point(&self, point: &Vec2) -> Option<Vec2> {
+        self.project(point).map(|projection| projection.point)
+    }
+
+    /// Returns the distance from `point` to the polyline, or `None` if it has no vertices.
+    pub fn distance(&self, point: &Vec2) -> Option<f64> {
+        self.project(point).map(|projection| projection.distance)
+    }
+}
+
+impl From<Vec<Vec2>> for Polyline {
+    fn from(points: Vec<Vec2>) -> Polyline {
+        Polyline::new(points)
+    }
+}
+
+/// A vertex queued for removal by `Polyline::simplified_by_area`, ordered so that the
+/// `BinaryHeap` pops the smallest area first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        other.area.total_cmp(&self.area).then(other.index.cmp(&self.index))
+    }
+}
@@ -0,0 +1,107 @@
+use math::{Vec3, Barycentric};
+use geometry::aabb3::Aabb3;
+use geometry::sphere::Sphere;
+use geometry::plane::Plane;
+
+/// A ray in 3D starting at `origin` and extending forever along `direction`, which doesn't need
+/// to be normalized: hit distances are measured in multiples of it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Ray3, Aabb3, Sphere};
+/// use candle::math::Vec3;
+/// let ray = Ray3::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0));
+/// assert_eq!(ray.point_at(2.0), Vec3::new(0.0, 0.0, -3.0));
+/// let unit = Aabb3::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+/// assert_eq!(ray.intersect_aabb(&unit), Some(4.0));
+/// assert_eq!(ray.intersect_sphere(&Sphere::new(Vec3::ZERO, 2.0)), Some(3.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray3 {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+/// Where a ray hits a triangle, as returned by `Ray3::intersect_triangle`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit {
+    /// The ray parameter of the hit point, in multiples of the ray's direction.
+    pub distance: f64,
+    /// The barycentric coordinates of the hit point with respect to the triangle's vertices,
+    /// for interpolating normals, UVs or other vertex attributes.
+    pub barycentric: Barycentric,
+}
+
+impl Ray3 {
+
+    /// Allocates a new Ray3 from `origin` along `direction`.
+    pub fn new(origin: Vec3, direction: Vec3) -> Ray3 {
+        Ray3 { origin, direction }
+    }
+
+    /// Returns the point at `origin + direction * t`.
+    pub fn point_at(&self, t: f64) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// Intersects the ray with the triangle `(a, b, c)` using the Möller–Trumbore algorithm,
+    /// which needs no precomputed plane. Both faces of the triangle are hit, and hits behind the
+    /// origin or on degenerate triangles are rejected. Edges are inclusive.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Ray3;
+    /// use candle::math::{Vec3, ApproxEq};
+    /// let (a, b, c) = (Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0), Vec3::new(0.0, 4.0, 0.0));
+    /// let ray = Ray3::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.0, -1.0));
+    /// let hit = ray.intersect_triangle(&a, &b, &c).unwrap();
+    /// assert_eq!(hit.distance, 3.0);
+    /// assert!(hit.barycentric.u.approx_eq(0.25));
+    /// assert!(hit.barycentric.v.approx_eq(0.25));
+    /// assert!(hit.barycentric.w.approx_eq(0.5));
+    ///
+    /// let miss = Ray3::new(Vec3::new(3.0, 3.0, 3.0), Vec3::new(0.0, 0.0, -1.0));
+    /// assert_eq!(miss.intersect_triangle(&a, &b, &c), None);
+    /// ```
+    pub fn intersect_triangle(&self, a: &Vec3, b: &Vec3, c: &Vec3) -> Option<TriangleHit> {
+        let (ab, ac) = (b - a, c - a);
+        let p = self.direction.cross(&ac);
+        let determinant = ab.dot(&p);
+        if determinant == 0.0 {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+        let offset = self.origin - a;
+        let v = offset.dot(&p) * inverse;
+        if !(0.0..=1.0).contains(&v) {
+            return None;
+        }
+        let q = offset.cross(&ab);
+        let w = self.direction.dot(&q) * inverse;
+        if w < 0.0 || v + w > 1.0 {
+            return None;
+        }
+        let distance = ac.dot(&q) * inverse;
+        if distance < 0.0 {
+            return None;
+        }
+        Some(TriangleHit { distance, barycentric: Barycentric::new(1.0 - v - w, v, w) })
+    }
+
+    /// Returns the ray parameter at which the ray enters the box, zero if it starts inside, or
+    /// `None` if it misses.
+    pub fn intersect_aabb(&self, aabb: &Aabb3) -> Option<f64> {
+        aabb.intersect_ray(&self.origin, &self.direction)
+    }
+
+    /// Returns the ray parameter at which the ray enters the ball, zero if it starts inside, or
+    /// `None` if it misses.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f64> {
+        sphere.intersect_ray(&self.origin, &self.direction)
+    }
+
+    /// Returns the ray parameter at which the ray crosses the plane, or `None` if it never does.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f64> {
+        plane.intersect_ray(&self.origin, &self.direction)
+    }
+}
@@ -0,0 +1,180 @@
+use math::Vec2;
+use geometry::polygon::Polygon;
+
+/// An axis-aligned rectangle spanning from its `min` corner to its `max` corner. Points on the
+/// edges belong to the rectangle, so a rectangle with `min == max` is a single point.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::Rect;
+/// use candle::math::Vec2;
+/// let room = Rect::from_center_size(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// let door = Rect::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 0.0));
+/// assert_eq!(room.intersection(&door), Some(Rect::new(Vec2::new(1.0, -1.0), Vec2::new(2.0, 0.0))));
+/// assert_eq!(room.union(&door).min, Vec2::new(-2.0, -2.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+
+    /// Allocates a new Rect between two opposite corners, given in any order.
+    pub fn new(a: Vec2, b: Vec2) -> Rect {
+        Rect { min: Vec2::new(a.x.min(b.x), a.y.min(b.y)), max: Vec2::new(a.x.max(b.x), a.y.max(b.y)) }
+    }
+
+    /// Allocates a new Rect centered on `center` with the given width and height, whose
+    /// absolute values are used.
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Rect {
+        let half = Vec2::new(size.x.abs(), size.y.abs()) / 2.0;
+        Rect { min: center - half, max: center + half }
+    }
+
+    /// Allocates the smallest Rect containing all the points, or returns `None` if there are
+    /// none.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Rect;
+    /// use candle::math::Vec2;
+    /// let points = [Vec2::new(1.0, 5.0), Vec2::new(-2.0, 3.0), Vec2::new(4.0, 4.0)];
+    /// let bounds = Rect::from_points(&points).unwrap();
+    /// assert_eq!(bounds, Rect::new(Vec2::new(-2.0, 3.0), Vec2::new(4.0, 5.0)));
+    /// ```
+    pub fn from_points(points: &[Vec2]) -> Option<Rect> {
+        let first = *points.first()?;
+        let mut rect = Rect { min: first, max: first };
+        for point in &points[1..] {
+            rect.include(point);
+        }
+        Some(rect)
+    }
+
+    /// Returns the center of the rectangle.
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns the width and height of the rectangle.
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Returns the width of the rectangle.
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    /// Returns the height of the rectangle.
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the area of the rectangle.
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Returns the four corners counterclockwise, starting from `min`.
+    pub fn corners(&self) -> [Vec2; 4] {
+        [self.min, Vec2::new(self.max.x, self.min.y), self.max, Vec2::new(self.min.x, self.max.y)]
+    }
+
+    /// Returns true if `point` lies inside or on the edges of the rectangle.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    /// Returns true if `other` lies entirely inside the rectangle.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Returns true if every vertex of the polygon lies inside or on the edges of the rectangle,
+    /// which, the rectangle being convex, puts the whole polygon inside. An empty polygon is
+    /// contained by any rectangle.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::{Rect, Polygon};
+    /// use candle::math::Vec2;
+    /// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0));
+    /// assert!(rect.contains_polygon(&Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0), Vec2::new(1.0, 4.0)])));
+    /// assert!(!rect.contains_polygon(&Polygon::new(vec![Vec2::new(1.0, 1.0), Vec2::new(5.0, 2.0), Vec2::new(1.0, 3.0)])));
+    /// ```
+    pub fn contains_polygon(&self, polygon: &Polygon) -> bool {
+        polygon.points().iter().all(|point| self.contains(point))
+    }
+
+    /// Returns true if the rectangles overlap or touch.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    /// Returns the overlap of the rectangles, or `None` if they don't intersect. Rectangles that
+    /// only touch overlap in a degenerate rectangle of zero width or height.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect {
+            min: Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        })
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Vec2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Vec2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Returns the rectangle grown by `margin` on every side, or shrunk if it is negative. A
+    /// rectangle shrunk past its size collapses to its center along that axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Rect;
+    /// use candle::math::Vec2;
+    /// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+    /// assert_eq!(rect.expand(1.0), Rect::new(Vec2::new(-1.0, -1.0), Vec2::new(5.0, 3.0)));
+    /// assert_eq!(rect.expand(-1.5).height(), 0.0);
+    /// ```
+    pub fn expand(&self, margin: f64) -> Rect {
+        let center = self.center();
+        let half = Vec2::new((self.width() / 2.0 + margin).max(0.0), (self.height() / 2.0 + margin).max(0.0));
+        Rect { min: center - half, max: center + half }
+    }
+
+    /// Grows the rectangle just enough to contain `point`.
+    pub fn include(&mut self, point: &Vec2) {
+        self.min = Vec2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Vec2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    /// Returns the point of the rectangle closest to `point`, which is `point` itself if it is
+    /// inside.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Rect;
+    /// use candle::math::Vec2;
+    /// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+    /// assert_eq!(rect.closest_point(&Vec2::new(6.0, 1.0)), Vec2::new(4.0, 1.0));
+    /// assert_eq!(rect.closest_point(&Vec2::new(-1.0, 5.0)), Vec2::new(0.0, 2.0));
+    /// assert_eq!(rect.distance(&Vec2::new(7.0, 6.0)), 5.0);
+    /// ```
+    pub fn closest_point(&self, point: &Vec2) -> Vec2 {
+        Vec2::new(point.x.clamp(self.min.x, self.max.x), point.y.clamp(self.min.y, self.max.y))
+    }
+
+    /// Returns the distance from `point` to the rectangle, zero if it is inside.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        (point - self.closest_point(point)).length()
+    }
+}
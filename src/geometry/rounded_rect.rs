@@ -0,0 +1,93 @@
+use std::f64::consts::PI;
+use math::Vec2;
+use geometry::rect::Rect;
+use geometry::arc::Arc;
+use geometry::polygon::Polygon;
+
+/// A rectangle whose corners are rounded with quarter circles of `radius`. Points on the
+/// boundary belong to it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{RoundedRect, Rect};
+/// use candle::math::Vec2;
+/// let button = RoundedRect::new(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 4.0)), 2.0);
+/// assert!(button.contains(&Vec2::new(5.0, 0.0)));
+/// assert!(!button.contains(&Vec2::new(0.2, 0.2)));
+/// assert_eq!(button.distance(&Vec2::new(5.0, 2.0)), -2.0);
+/// assert_eq!(button.distance(&Vec2::new(13.0, 2.0)), 3.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundedRect {
+    pub rect: Rect,
+    pub radius: f64,
+}
+
+impl RoundedRect {
+
+    /// Allocates a new RoundedRect, clamping `radius` between zero and half the shorter side so
+    /// the corners never overlap.
+    pub fn new(rect: Rect, radius: f64) -> RoundedRect {
+        let radius = radius.min(rect.width().min(rect.height()) / 2.0).max(0.0);
+        RoundedRect { rect, radius }
+    }
+
+    /// Returns the area of the rounded rectangle.
+    pub fn area(&self) -> f64 {
+        self.rect.area() - (4.0 - PI) * self.radius * self.radius
+    }
+
+    /// Returns true if `point` lies inside or on the rounded rectangle.
+    pub fn contains(&self, point: &Vec2) -> bool {
+        self.distance(point) <= 0.0
+    }
+
+    /// Returns the signed distance from `point` to the boundary: negative inside the rounded
+    /// rectangle and positive outside.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        let offset = point - self.rect.center();
+        let inner = self.rect.size() / 2.0 - Vec2::new(self.radius, self.radius);
+        let q = Vec2::new(offset.x.abs() - inner.x, offset.y.abs() - inner.y);
+        let outside = Vec2::new(q.x.max(0.0), q.y.max(0.0)).length();
+        outside + q.x.max(q.y).min(0.0) - self.radius
+    }
+
+    /// Approximates the outline with a counterclockwise polygon whose corner chords stray at
+    /// most `tolerance` from the arcs, as `Arc::flatten` does. Without rounding, this is the
+    /// rectangle's four corners.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::{RoundedRect, Rect};
+    /// use candle::math::Vec2;
+    /// let button = RoundedRect::new(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 4.0)), 2.0);
+    /// let outline = button.to_polygon(0.05);
+    /// assert!(outline.len() > 8);
+    /// assert!(outline.area() < button.area());
+    /// assert!(outline.is_convex());
+    /// ```
+    pub fn to_polygon(&self, tolerance: f64) -> Polygon {
+        if self.radius == 0.0 {
+            return Polygon::new(self.rect.corners().to_vec());
+        }
+        let (min, max, r) = (self.rect.min, self.rect.max, self.radius);
+        let centers = [
+            Vec2::new(max.x - r, min.y + r),
+            Vec2::new(max.x - r, max.y - r),
+            Vec2::new(min.x + r, max.y - r),
+            Vec2::new(min.x + r, min.y + r),
+        ];
+        let mut points = Vec::new();
+        for (i, center) in centers.iter().enumerate() {
+            let arc = Arc::new(*center, r, -PI / 2.0 + i as f64 * PI / 2.0, PI / 2.0);
+            points.extend(arc.flatten(tolerance).into_points());
+        }
+        // Corners rounded over a whole side leave no straight edge, so neighbouring arcs share
+        // their ends.
+        points.dedup_by(|a, b| a == b);
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Polygon::new(points)
+    }
+}
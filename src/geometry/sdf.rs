@@ -0,0 +1,300 @@
+//! Signed distance functions: the distance from a point to a shape's boundary, negative inside
+//! the shape and positive outside, and combinators building new shapes out of those distances,
+//! so procedural shapes can be composed as plain functions of a point.
+//!
+//! The combinators work on distances rather than shapes: evaluate each part at the point, then
+//! combine the results. Union, intersection and subtraction keep the sign exact but only bound
+//! the distance from the outside of the combined shape, which is enough for contouring and
+//! sphere tracing.
+//!
+//! # Examples
+//! ```
+//! use candle::geometry::{Circle, Rect};
+//! use candle::geometry::sdf;
+//! use candle::math::Vec2;
+//! // A plate with a hole punched through it.
+//! let plate = Rect::new(Vec2::new(-2.0, -1.0), Vec2::new(2.0, 1.0));
+//! let hole = Circle::new(Vec2::new(0.0, 0.0), 0.5);
+//! let shape = |p: &Vec2| sdf::subtraction(sdf::rect(p, &plate), sdf::circle(p, &hole));
+//! assert_eq!(shape(&Vec2::new(0.0, 0.0)), 0.5);
+//! assert_eq!(shape(&Vec2::new(1.5, 0.0)), -0.5);
+//! assert_eq!(sdf::normal(&shape, &Vec2::new(2.0, 0.0), 1e-6), Vec2::new(1.0, 0.0));
+//! ```
+
+use math::{Vec2, Grid};
+use math::roots::{self, Tolerance};
+use geometry::rect::Rect;
+use geometry::circle::Circle;
+use geometry::line::Segment;
+use geometry::polygon::Polygon;
+use geometry::capsule::Capsule2;
+use geometry::marching_squares::marching_squares;
+
+/// Returns the signed distance from `point` to the circle.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Circle};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(1.0, 1.0), 2.0);
+/// assert_eq!(sdf::circle(&Vec2::new(1.0, 1.0), &circle), -2.0);
+/// assert_eq!(sdf::circle(&Vec2::new(4.0, 5.0), &circle), 3.0);
+/// ```
+pub fn circle(point: &Vec2, circle: &Circle) -> f64 {
+    circle.distance(point)
+}
+
+/// Returns the signed distance from `point` to the rectangle, exact inside as well as outside.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Rect};
+/// use candle::math::Vec2;
+/// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// assert_eq!(sdf::rect(&Vec2::new(1.0, 1.0), &rect), -1.0);
+/// assert_eq!(sdf::rect(&Vec2::new(7.0, 6.0), &rect), 5.0);
+/// ```
+pub fn rect(point: &Vec2, rect: &Rect) -> f64 {
+    let half = rect.size() / 2.0;
+    let offset = point - rect.center();
+    let (dx, dy) = (offset.x.abs() - half.x, offset.y.abs() - half.y);
+    Vec2::new(dx.max(0.0), dy.max(0.0)).length() + dx.max(dy).min(0.0)
+}
+
+/// Returns the distance from `point` to the segment. A segment has no inside, so the distance
+/// is never negative; `round` or `annular` give it some thickness.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Segment};
+/// use candle::math::Vec2;
+/// let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+/// assert_eq!(sdf::segment(&Vec2::new(2.0, -3.0), &segment), 3.0);
+/// assert_eq!(sdf::segment(&Vec2::new(7.0, 4.0), &segment), 5.0);
+/// ```
+pub fn segment(point: &Vec2, segment: &Segment) -> f64 {
+    segment.distance(point)
+}
+
+/// Returns the signed distance from `point` to the polygon, which may wind either way and be
+/// concave. Its inside is decided by `Polygon::contains`. Empty polygons are infinitely far
+/// away.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Polygon};
+/// use candle::math::Vec2;
+/// let triangle = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 4.0)]);
+/// assert_eq!(sdf::polygon(&Vec2::new(1.0, 1.0), &triangle), -1.0);
+/// assert_eq!(sdf::polygon(&Vec2::new(-3.0, 2.0), &triangle), 3.0);
+/// ```
+pub fn polygon(point: &Vec2, polygon: &Polygon) -> f64 {
+    let distance = polygon.edges().map(|edge| edge.distance(point)).fold(f64::INFINITY, f64::min);
+    if polygon.contains(point) { -distance } else { distance }
+}
+
+/// Returns the signed distance from `point` to the capsule.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Capsule2, Segment};
+/// use candle::math::Vec2;
+/// let capsule = Capsule2::new(Segment::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 2.0)), 0.5);
+/// assert_eq!(sdf::capsule(&Vec2::new(0.0, 1.0), &capsule), -0.5);
+/// assert_eq!(sdf::capsule(&Vec2::new(0.0, 4.0), &capsule), 1.5);
+/// ```
+pub fn capsule(point: &Vec2, capsule: &Capsule2) -> f64 {
+    capsule.distance(point)
+}
+
+/// Combines the distances to two shapes into the distance to their union.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::sdf;
+/// assert_eq!(sdf::union(-1.0, 2.0), -1.0);
+/// ```
+pub fn union(a: f64, b: f64) -> f64 {
+    a.min(b)
+}
+
+/// Combines the distances to two shapes into the distance to their intersection.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::sdf;
+/// assert_eq!(sdf::intersection(-1.0, 2.0), 2.0);
+/// ```
+pub fn intersection(a: f64, b: f64) -> f64 {
+    a.max(b)
+}
+
+/// Combines the distances to two shapes into the distance to the first with the second cut out
+/// of it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::sdf;
+/// assert_eq!(sdf::subtraction(-1.0, -0.5), 0.5);
+/// assert_eq!(sdf::subtraction(-1.0, 2.0), -1.0);
+/// ```
+pub fn subtraction(a: f64, b: f64) -> f64 {
+    a.max(-b)
+}
+
+/// Combines the distances to two shapes into the distance to their union with the seam between
+/// them filleted, blending over distances up to `k` from it. A `k` of zero or less gives the
+/// plain `union`.
+///
+/// Uses the polynomial smooth minimum, which only differs from the plain one where the two
+/// distances are within `k` of each other, and by at most `k / 4`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::sdf;
+/// assert_eq!(sdf::smooth_union(1.0, 1.0, 0.4), 0.9);
+/// assert_eq!(sdf::smooth_union(1.0, 3.0, 0.4), 1.0);
+/// assert_eq!(sdf::smooth_union(1.0, 1.0, 0.0), 1.0);
+/// ```
+pub fn smooth_union(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return union(a, b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+/// Rounds a shape off by growing it by `radius`: its corners become arcs, and a segment becomes
+/// a capsule.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Rect};
+/// use candle::math::Vec2;
+/// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+/// let corner = Vec2::new(3.0, 3.0);
+/// assert_eq!(sdf::round(sdf::rect(&corner, &rect), 1.0), 2.0f64.sqrt() - 1.0);
+/// ```
+pub fn round(distance: f64, radius: f64) -> f64 {
+    distance - radius
+}
+
+/// Hollows a shape out into the ring of points within `thickness` of its boundary, `2 *
+/// thickness` wide: a circle becomes an annulus.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Circle};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(0.0, 0.0), 2.0);
+/// assert_eq!(sdf::annular(sdf::circle(&Vec2::new(0.0, 0.0), &circle), 0.5), 1.5);
+/// assert_eq!(sdf::annular(sdf::circle(&Vec2::new(2.25, 0.0), &circle), 0.5), -0.25);
+/// ```
+pub fn annular(distance: f64, thickness: f64) -> f64 {
+    distance.abs() - thickness
+}
+
+/// Estimates the gradient of the distance function `f` at `point` by central differences `step`
+/// apart. Where `f` is an exact distance the gradient has unit length and points away from the
+/// nearest boundary point.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Circle};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(0.0, 0.0), 1.0);
+/// let gradient = sdf::gradient(|p| sdf::circle(p, &circle), &Vec2::new(0.0, 3.0), 1e-6);
+/// assert!((gradient - Vec2::new(0.0, 1.0)).length() < 1e-6);
+/// ```
+pub fn gradient<F>(f: F, point: &Vec2, step: f64) -> Vec2
+    where F: Fn(&Vec2) -> f64
+{
+    let (dx, dy) = (Vec2::new(step, 0.0), Vec2::new(0.0, step));
+    Vec2::new(f(&(point + dx)) - f(&(point - dx)), f(&(point + dy)) - f(&(point - dy))) / (2.0 * step)
+}
+
+/// Estimates the outward normal of the shape described by the distance function `f` at
+/// `point`, as its normalized `gradient`.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Rect};
+/// use candle::math::Vec2;
+/// let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// assert_eq!(sdf::normal(|p| sdf::rect(p, &rect), &Vec2::new(2.0, 2.0), 1e-6), Vec2::new(0.0, 1.0));
+/// ```
+pub fn normal<F>(f: F, point: &Vec2, step: f64) -> Vec2
+    where F: Fn(&Vec2) -> f64
+{
+    gradient(f, point, step).normalized()
+}
+
+/// Traces the boundary of the shape described by the distance function `f` within `bounds` as
+/// polygons, so shapes composed from distances can be handed to physics or rendering. The
+/// bounds are sampled at the corners of `resolution` cells along each axis and contoured with
+/// `marching_squares`, then each crossing is moved onto the exact zero of `f` along its cell
+/// edge with Brent's method, rather than where linear interpolation puts it.
+///
+/// Outlines wind counterclockwise and holes clockwise, without repeating a vertex. The shape is
+/// cut off at the bounds, and features smaller than a cell can be missed or merged.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{sdf, Circle, Rect};
+/// use candle::math::Vec2;
+/// let circle = Circle::new(Vec2::new(0.0, 0.0), 1.0);
+/// let bounds = Rect::new(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+/// let ring = |p: &Vec2| sdf::annular(sdf::circle(p, &circle), 0.25);
+/// let polygons = sdf::contour(ring, &bounds, [16, 16]);
+/// assert_eq!(polygons.len(), 2);
+/// assert!(polygons.iter().flat_map(|polygon| polygon.points()).all(|p| ring(p).abs() < 1e-9));
+/// let area: f64 = polygons.iter().map(|polygon| polygon.signed_area()).sum();
+/// assert!((area - std::f64::consts::PI).abs() < 0.05);
+/// assert!(polygons.iter().all(|polygon| polygon.is_simple()));
+///
+/// // The circle passes through samples, and is cut off by the bounds of a quarter of it.
+/// let quarter = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+/// let polygons = sdf::contour(|p| sdf::circle(p, &circle), &quarter, [8, 8]);
+/// assert_eq!(polygons.len(), 1);
+/// assert!(polygons[0].is_simple());
+/// ```
+pub fn contour<F>(f: F, bounds: &Rect, resolution: [usize; 2]) -> Vec<Polygon>
+    where F: Fn(&Vec2) -> f64
+{
+    let size = bounds.size();
+    let step = Vec2::new(size.x / resolution[0].max(1) as f64, size.y / resolution[1].max(1) as f64);
+    let to_world = |x: f64, y: f64| Vec2::new(bounds.min.x + step.x * x, bounds.min.y + step.y * y);
+    // Marching squares traces the regions at or above the level, so the distances are negated.
+    let grid = Grid::from_fn(resolution[0] + 1, resolution[1] + 1, |x, y| -f(&to_world(x as f64, y as f64)));
+
+    let snap = |p: Vec2| {
+        // Crossings lie on a cell edge, keeping the coordinate across it exact.
+        let (from, to) = if p.x == p.x.floor() && p.y != p.y.floor() {
+            (to_world(p.x, p.y.floor()), to_world(p.x, p.y.floor() + 1.0))
+        } else if p.y == p.y.floor() && p.x != p.x.floor() {
+            (to_world(p.x.floor(), p.y), to_world(p.x.floor() + 1.0, p.y))
+        } else {
+            return to_world(p.x, p.y);
+        };
+        let along = |t: f64| f(&(from + (to - from) * t));
+        match roots::brent(along, 0.0, 1.0, &Tolerance::default()) {
+            Some(t) => from + (to - from) * t,
+            None => to_world(p.x, p.y),
+        }
+    };
+    // Crossings snapped onto the same zero, such as those around a sample on the boundary or a
+    // corner of the bounds, are merged so the polygons don't repeat a vertex.
+    let merge = 1e-9 * step.length();
+    marching_squares(&grid, 0.0, true).into_iter().filter_map(|contour| {
+        let mut points: Vec<Vec2> = Vec::new();
+        for point in contour.into_points().into_iter().map(snap) {
+            if points.last().is_none_or(|last| (point - last).length() > merge) {
+                points.push(point);
+            }
+        }
+        while points.len() > 1 && (points[points.len() - 1] - points[0]).length() <= merge {
+            points.pop();
+        }
+        if points.len() >= 3 { Some(Polygon::new(points)) } else { None }
+    }).collect()
+}
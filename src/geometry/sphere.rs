@@ -0,0 +1,146 @@
+use std::f64::consts::PI;
+use math::Vec3;
+use geometry::aabb3::Aabb3;
+
+/// A sphere, or a ball when testing containment: points on the surface belong to it.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{Aabb3, Sphere};
+/// use candle::math::Vec3;
+/// let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 2.0);
+/// assert!(sphere.contains(&Vec3::new(0.0, 0.0, 2.0)));
+/// assert!(sphere.intersects(&Sphere::new(Vec3::new(3.0, 0.0, 0.0), 1.0)));
+/// assert_eq!(sphere.bounds(), Aabb3::new(Vec3::new(-2.0, -2.0, -2.0), Vec3::new(2.0, 2.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f64,
+}
+
+impl Sphere {
+
+    /// Allocates a new Sphere, using the absolute value of `radius`.
+    pub fn new(center: Vec3, radius: f64) -> Sphere {
+        Sphere { center, radius: radius.abs() }
+    }
+
+    /// Allocates a sphere containing all the points with Ritter's algorithm: it starts from two
+    /// points far apart and grows to take in any point left outside. The result takes O(n) and is
+    /// usually within a few percent of the smallest enclosing sphere. Returns `None` if there
+    /// are no points.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Sphere;
+    /// use candle::math::Vec3;
+    /// let points = [Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.5, 0.5)];
+    /// let sphere = Sphere::from_points(&points).unwrap();
+    /// assert_eq!(sphere, Sphere::new(Vec3::ZERO, 1.0));
+    /// assert!(points.iter().all(|point| sphere.contains(point)));
+    /// ```
+    pub fn from_points(points: &[Vec3]) -> Option<Sphere> {
+        let first = *points.first()?;
+        let farthest = |from: &Vec3| *points.iter()
+            .max_by(|a, b| (*a - from).length().total_cmp(&(*b - from).length()))
+            .unwrap_or(&first);
+        let a = farthest(&first);
+        let b = farthest(&a);
+        let mut sphere = Sphere { center: (a + b) / 2.0, radius: (b - a).length() / 2.0 };
+        for point in points {
+            sphere = sphere.include(point);
+        }
+        Some(sphere)
+    }
+
+    /// Returns the volume of the ball.
+    pub fn volume(&self) -> f64 {
+        4.0 / 3.0 * PI * self.radius.powi(3)
+    }
+
+    /// Returns the smallest axis-aligned box containing the sphere.
+    pub fn bounds(&self) -> Aabb3 {
+        Aabb3::from_center_half_extents(self.center, Vec3::new(self.radius, self.radius, self.radius))
+    }
+
+    /// Returns true if `point` lies inside or on the sphere.
+    pub fn contains(&self, point: &Vec3) -> bool {
+        let offset = point - self.center;
+        offset.dot(&offset) <= self.radius * self.radius
+    }
+
+    /// Returns true if `other` lies entirely inside the sphere.
+    pub fn contains_sphere(&self, other: &Sphere) -> bool {
+        (other.center - self.center).length() + other.radius <= self.radius
+    }
+
+    /// Returns true if the balls overlap or touch.
+    pub fn intersects(&self, other: &Sphere) -> bool {
+        let offset = other.center - self.center;
+        let reach = self.radius + other.radius;
+        offset.dot(&offset) <= reach * reach
+    }
+
+    /// Returns true if the ball overlaps or touches the box.
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        aabb.intersects_sphere(self)
+    }
+
+    /// Returns the smallest sphere containing both spheres.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Sphere;
+    /// use candle::math::Vec3;
+    /// let a = Sphere::new(Vec3::new(-2.0, 0.0, 0.0), 1.0);
+    /// let b = Sphere::new(Vec3::new(3.0, 0.0, 0.0), 2.0);
+    /// assert_eq!(a.union(&b), Sphere::new(Vec3::new(1.0, 0.0, 0.0), 4.0));
+    /// ```
+    pub fn union(&self, other: &Sphere) -> Sphere {
+        if self.contains_sphere(other) {
+            return *self;
+        }
+        if other.contains_sphere(self) {
+            return *other;
+        }
+        let offset = other.center - self.center;
+        let distance = offset.length();
+        let radius = (distance + self.radius + other.radius) / 2.0;
+        Sphere { center: self.center + offset * ((radius - self.radius) / distance), radius }
+    }
+
+    /// Returns the smallest sphere containing this one and `point`, moving the center towards
+    /// the point by as little as possible.
+    pub fn include(&self, point: &Vec3) -> Sphere {
+        self.union(&Sphere { center: *point, radius: 0.0 })
+    }
+
+    /// Returns the smallest parameter `t >= 0` at which the ray `origin + direction * t` enters
+    /// the ball, or `None` if it misses. Rays starting inside hit at zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Sphere;
+    /// use candle::math::Vec3;
+    /// let sphere = Sphere::new(Vec3::new(0.0, 0.0, 10.0), 2.0);
+    /// assert_eq!(sphere.intersect_ray(&Vec3::ZERO, &Vec3::new(0.0, 0.0, 1.0)), Some(8.0));
+    /// assert_eq!(sphere.intersect_ray(&Vec3::ZERO, &Vec3::new(0.0, 0.0, -1.0)), None);
+    /// assert_eq!(sphere.intersect_ray(&Vec3::new(3.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 1.0)), None);
+    /// ```
+    pub fn intersect_ray(&self, origin: &Vec3, direction: &Vec3) -> Option<f64> {
+        if self.contains(origin) {
+            return Some(0.0);
+        }
+        // Solve |origin + direction * t - center|² = radius² for t.
+        let offset = origin - self.center;
+        let a = direction.dot(direction);
+        let half_b = offset.dot(direction);
+        let c = offset.dot(&offset) - self.radius * self.radius;
+        let discriminant = half_b * half_b - a * c;
+        if a == 0.0 || discriminant < 0.0 || half_b > 0.0 {
+            return None;
+        }
+        Some((-half_b - discriminant.sqrt()) / a)
+    }
+}
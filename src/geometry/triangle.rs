@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+use math::{Vec2, Barycentric};
+use math::predicates::orient2d;
+use geometry::circle::Circle;
+
+/// A triangle with vertices `a`, `b` and `c`, in either winding.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use candle::geometry::Triangle;
+/// use candle::math::Vec2;
+/// let triangle = Triangle::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 3.0));
+/// assert_eq!(triangle.area(), 6.0);
+/// assert_eq!(triangle.winding(), Ordering::Greater);
+/// assert!(triangle.contains(&Vec2::new(1.0, 1.0)));
+/// assert_eq!(triangle.circumcenter(), Some(Vec2::new(2.0, 1.5)));
+/// assert_eq!(triangle.incircle().unwrap().radius, 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub c: Vec2,
+}
+
+impl Triangle {
+
+    /// Allocates a new Triangle from its vertices.
+    pub fn new(a: Vec2, b: Vec2, c: Vec2) -> Triangle {
+        Triangle { a, b, c }
+    }
+
+    /// Returns the area of the triangle, positive if the vertices turn counterclockwise and
+    /// negative if they turn clockwise.
+    pub fn signed_area(&self) -> f64 {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        (ab.x * ac.y - ab.y * ac.x) / 2.0
+    }
+
+    /// Returns the area of the triangle.
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Returns the winding of the vertices, computed exactly: `Greater` if they turn
+    /// counterclockwise, `Less` if clockwise and `Equal` if the triangle is degenerate.
+    pub fn winding(&self) -> Ordering {
+        orient2d(&self.a, &self.b, &self.c)
+    }
+
+    /// Returns the lengths of the sides opposite to `a`, `b` and `c`, in that order.
+    pub fn side_lengths(&self) -> (f64, f64, f64) {
+        ((self.c - self.b).length(), (self.a - self.c).length(), (self.b - self.a).length())
+    }
+
+    /// Returns the perimeter of the triangle.
+    pub fn perimeter(&self) -> f64 {
+        let (a, b, c) = self.side_lengths();
+        a + b + c
+    }
+
+    /// Returns the centroid, the average of the vertices.
+    pub fn centroid(&self) -> Vec2 {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    /// Returns the barycentric coordinates of `point`, or `None` if the triangle is degenerate.
+    pub fn barycentric(&self, point: &Vec2) -> Option<Barycentric> {
+        Barycentric::from_point(point, &self.a, &self.b, &self.c)
+    }
+
+    /// Returns true if `point` lies inside the triangle or on its edges. Degenerate triangles
+    /// contain no points. The test is exact, for either winding.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::geometry::Triangle;
+    /// use candle::math::Vec2;
+    /// let triangle = Triangle::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 3.0), Vec2::new(3.0, 0.0));
+    /// assert!(triangle.contains(&Vec2::new(1.0, 1.0)));
+    /// assert!(triangle.contains(&Vec2::new(1.0, 2.0)));
+    /// assert!(!triangle.contains(&Vec2::new(1.5, 1.6)));
+    /// ```
+    pub fn contains(&self, point: &Vec2) -> bool {
+        let winding = self.winding();
+        if winding == Ordering::Equal {
+            return false;
+        }
+        [orient2d(&self.a, &self.b, point), orient2d(&self.b, &self.c, point), orient2d(&self.c, &self.a, point)]
+            .iter()
+            .all(|&side| side != winding.reverse())
+    }
+
+    /// Returns the circumcenter, the point equidistant from the three vertices, or `None` if the
+    /// triangle is degenerate.
+    pub fn circumcenter(&self) -> Option<Vec2> {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        let denom = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+        if denom == 0.0 {
+            return None;
+        }
+        let (ab2, ac2) = (ab.dot(&ab), ac.dot(&ac));
+        let offset = Vec2::new(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / denom;
+        Some(self.a + offset)
+    }
+
+    /// Returns the circumcircle, through the three vertices, or `None` if the triangle is
+    /// degenerate.
+    pub fn circumcircle(&self) -> Option<Circle> {
+        let center = self.circumcenter()?;
+        Some(Circle::new(center, (self.a - center).length()))
+    }
+
+    /// Returns the incenter, the point equidistant from the three sides, or `None` if the
+    /// triangle is degenerate.
+    pub fn incenter(&self) -> Option<Vec2> {
+        let (a, b, c) = self.side_lengths();
+        let perimeter = a + b + c;
+        if self.signed_area() == 0.0 || perimeter == 0.0 {
+            return None;
+        }
+        Some((self.a * a + self.b * b + self.c * c) / perimeter)
+    }
+
+    /// Returns the incircle, tangent to the three sides, or `None` if the triangle is
+    /// degenerate.
+    pub fn incircle(&self) -> Option<Circle> {
+        let center = self.incenter()?;
+        Some(Circle::new(center, 2.0 * self.area() / self.perimeter()))
+    }
+}
@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use math::Vec2;
+use math::predicates::orient2d;
+use geometry::line::Segment;
+use geometry::polygon::Polygon;
+
+/// Triangulates a simple polygon by ear clipping, returning triangles as indices into its
+/// points, always wound counterclockwise whatever the polygon's winding. A polygon of n
+/// vertices gives n - 2 triangles, fewer if some vertices are collinear with their neighbours.
+/// Runs in O(n²) time; corners are classified exactly with `predicates::orient2d`.
+///
+/// Polygons that aren't simple are triangulated on a best-effort basis: the process always
+/// finishes, but the triangles may overlap or leave gaps.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{triangulate, Polygon};
+/// use candle::math::Vec2;
+/// let l_shape = Polygon::new(vec![
+///     Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 2.0),
+///     Vec2::new(2.0, 2.0), Vec2::new(2.0, 4.0), Vec2::new(0.0, 4.0),
+/// ]);
+/// let triangles = triangulate(&l_shape);
+/// assert_eq!(triangles.len(), 4);
+/// let area: f64 = triangles.iter()
+///     .map(|t| Polygon::new(t.iter().map(|&i| l_shape.points()[i]).collect()).signed_area())
+///     .sum();
+/// assert_eq!(area, 12.0);
+/// ```
+pub fn triangulate(polygon: &Polygon) -> Vec<[usize; 3]> {
+    triangulate_with_holes(polygon, &[])
+}
+
+/// Triangulates a simple polygon with holes by ear clipping. The holes must lie inside the
+/// polygon without touching it or each other, and may wind either way. Triangles index the
+/// polygon's points followed by those of each hole in turn, and are wound counterclockwise.
+///
+/// Each hole is first joined to the outline by a bridge to the nearest outline vertex it can
+/// see, starting from the hole reaching furthest right, which turns the region into a single
+/// ring that ear clipping can handle. A polygon of n vertices with h holes gives n + 2h - 2
+/// triangles.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{triangulate_with_holes, Polygon};
+/// use candle::math::Vec2;
+/// let square = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(6.0, 0.0), Vec2::new(6.0, 6.0), Vec2::new(0.0, 6.0)]);
+/// let hole = Polygon::new(vec![Vec2::new(2.0, 2.0), Vec2::new(4.0, 2.0), Vec2::new(4.0, 4.0), Vec2::new(2.0, 4.0)]);
+/// let triangles = triangulate_with_holes(&square, &[hole.clone()]);
+/// assert_eq!(triangles.len(), 8);
+/// let points: Vec<Vec2> = square.points().iter().chain(hole.points()).cloned().collect();
+/// let area: f64 = triangles.iter()
+///     .map(|t| Polygon::new(t.iter().map(|&i| points[i]).collect()).signed_area())
+///     .sum();
+/// assert_eq!(area, 32.0);
+/// ```
+pub fn triangulate_with_holes(polygon: &Polygon, holes: &[Polygon]) -> Vec<[usize; 3]> {
+    let mut points: Vec<Vec2> = polygon.points().to_vec();
+    let mut ring: Vec<usize> = (0..points.len()).collect();
+    if polygon.winding() == Ordering::Less {
+        ring.reverse();
+    }
+
+    // Holes as rings of indices into `points`, wound clockwise.
+    let mut hole_rings: Vec<Vec<usize>> = Vec::with_capacity(holes.len());
+    for hole in holes.iter().filter(|hole| hole.len() >= 3) {
+        let base = points.len();
+        points.extend_from_slice(hole.points());
+        let mut hole_ring: Vec<usize> = (base..points.len()).collect();
+        if hole.winding() == Ordering::Greater {
+            hole_ring.reverse();
+        }
+        hole_rings.push(hole_ring);
+    }
+    if ring.len() < 3 {
+        return Vec::new();
+    }
+
+    let rightmost = |hole: &[usize]| (0..hole.len()).max_by(|&i, &j| {
+        let (p, q) = (points[hole[i]], points[hole[j]]);
+        p.x.total_cmp(&q.x).then(q.y.total_cmp(&p.y))
+    }).unwrap();
+    hole_rings.sort_by(|p, q| points[q[rightmost(q)]].x.total_cmp(&points[p[rightmost(p)]].x));
+    for h in 0..hole_rings.len() {
+        let start = rightmost(&hole_rings[h]);
+        let bridge = find_bridge(&points, &ring, &hole_rings[h..], hole_rings[h][start]);
+        let hole = &hole_rings[h];
+        let mut spliced: Vec<usize> = (0..=hole.len()).map(|k| hole[(start + k) % hole.len()]).collect();
+        spliced.push(ring[bridge]);
+        ring.splice(bridge + 1..bridge + 1, spliced);
+    }
+    clip_ears(&points, &ring)
+}
+
+/// Returns the position in `ring` of the vertex nearest to `points[from]` that the segment from
+/// it reaches without touching any edge of the ring or of the holes, other than at its ends.
+fn find_bridge(points: &[Vec2], ring: &[usize], holes: &[Vec<usize>], from: usize) -> usize {
+    let origin = points[from];
+    let mut candidates: Vec<usize> = (0..ring.len()).collect();
+    candidates.sort_by(|&i, &j| {
+        let (p, q) = (points[ring[i]] - origin, points[ring[j]] - origin);
+        p.dot(&p).total_cmp(&q.dot(&q))
+    });
+    let edges = |indices: &[usize]| {
+        let count = indices.len();
+        (0..count).map(move |i| (points[indices[i]], points[indices[(i + 1) % count]])).collect::<Vec<_>>()
+    };
+    let mut blockers = edges(ring);
+    for hole in holes {
+        blockers.extend(edges(hole));
+    }
+    let nearest = candidates.iter().cloned().find(|&candidate| {
+        let target = points[ring[candidate]];
+        let bridge = Segment::new(origin, target);
+        blockers.iter().all(|&(a, b)| {
+            a == origin || b == origin || a == target || b == target || !bridge.intersects(&Segment::new(a, b))
+        })
+    }).unwrap_or(0);
+
+    // A vertex already used by an earlier bridge appears in the ring more than once, and the
+    // bridge must leave from the copy whose corner it passes through, or the ring would cross
+    // itself.
+    let target = points[ring[nearest]];
+    let count = ring.len();
+    (0..count).filter(|&i| points[ring[i]] == target).find(|&i| {
+        let (prev, next) = (points[ring[(i + count - 1) % count]], points[ring[(i + 1) % count]]);
+        let (left_of_next, right_of_prev) = (orient2d(&target, &next, &origin) == Ordering::Greater, orient2d(&target, &prev, &origin) == Ordering::Less);
+        if orient2d(&prev, &target, &next) == Ordering::Greater {
+            left_of_next && right_of_prev
+        } else {
+            left_of_next || right_of_prev
+        }
+    }).unwrap_or(nearest)
+}
+
+/// Clips ears off a counterclockwise ring of indices into `points` until a triangle remains.
+fn clip_ears(points: &[Vec2], ring: &[usize]) -> Vec<[usize; 3]> {
+    let count = ring.len();
+    let mut triangles = Vec::with_capacity(count.saturating_sub(2));
+    let mut prev: Vec<usize> = (0..count).map(|i| (i + count - 1) % count).collect();
+    let mut next: Vec<usize> = (0..count).map(|i| (i + 1) % count).collect();
+    let corner = |prev: &[usize], next: &[usize], i: usize| {
+        orient2d(&points[ring[prev[i]]], &points[ring[i]], &points[ring[next[i]]])
+    };
+
+    let (mut remaining, mut current, mut stalled) = (count, 0, 0);
+    while remaining > 3 {
+        if is_ear(points, ring, &prev, &next, current) {
+            triangles.push([ring[prev[current]], ring[current], ring[next[current]]]);
+        } else if stalled <= remaining {
+            stalled += 1;
+            current = next[current];
+            continue;
+        } else {
+            // No ear left, which only happens for rings that aren't simple: drop a vertex in
+            // line with its neighbours if there is one, or force the current corner off.
+            let mut vertex = current;
+            for _ in 0..remaining {
+                if corner(&prev, &next, vertex) == Ordering::Equal {
+                    current = vertex;
+                    break;
+                }
+                vertex = next[vertex];
+            }
+            if corner(&prev, &next, current) == Ordering::Greater {
+                triangles.push([ring[prev[current]], ring[current], ring[next[current]]]);
+            }
+        }
+        let (p, n) = (prev[current], next[current]);
+        next[p] = n;
+        prev[n] = p;
+        remaining -= 1;
+        stalled = 0;
+        current = p;
+    }
+    if corner(&prev, &next, current) == Ordering::Greater {
+        triangles.push([ring[prev[current]], ring[current], ring[next[current]]]);
+    }
+    triangles
+}
+
+/// Returns true if the corner at ring position `i` is convex and no other remaining vertex lies
+/// inside or on its triangle. Vertices sharing the position of a corner, as the two ends of a
+/// hole's bridge do, don't count.
+fn is_ear(points: &[Vec2], ring: &[usize], prev: &[usize], next: &[usize], i: usize) -> bool {
+    let (a, b, c) = (points[ring[prev[i]]], points[ring[i]], points[ring[next[i]]]);
+    if orient2d(&a, &b, &c) != Ordering::Greater {
+        return false;
+    }
+    let mut vertex = next[next[i]];
+    while vertex != prev[i] {
+        let point = points[ring[vertex]];
+        let reflex = orient2d(&points[ring[prev[vertex]]], &point, &points[ring[next[vertex]]]) != Ordering::Greater;
+        if reflex && point != a && point != b && point != c &&
+            orient2d(&a, &b, &point) != Ordering::Less &&
+            orient2d(&b, &c, &point) != Ordering::Less &&
+            orient2d(&c, &a, &point) != Ordering::Less {
+            return false;
+        }
+        vertex = next[vertex];
+    }
+    true
+}
@@ -0,0 +1,127 @@
+use math::Vec2;
+use geometry::rect::Rect;
+use geometry::polygon::Polygon;
+
+/// The region of a Voronoi diagram closer to one site than to any other, as returned by
+/// `voronoi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoronoiCell {
+    /// The site the cell belongs to.
+    pub site: Vec2,
+    /// The cell's outline, convex and wound counterclockwise. Empty if the site lies so far
+    /// outside the bounds that none of its region is left.
+    pub polygon: Polygon,
+    /// The indices of the sites whose cells share an edge with this one, in increasing order.
+    pub neighbors: Vec<usize>,
+}
+
+/// Computes the Voronoi diagram of the sites, clipped to `bounds`: one cell per site, in the
+/// same order, with the indices of the cells sharing an edge with it.
+///
+/// Each cell is found by cutting the bounds down with the half-planes closer to its site than
+/// to each of the others, nearest first, stopping as soon as the remaining sites are too far
+/// away to cut the cell any further. For evenly spread sites that leaves only a handful of cuts
+/// per cell, after an O(n log n) sort, so the diagram takes O(n² log n) time. Sites at the same
+/// position get the same cell, and are not each other's neighbours.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{voronoi, Rect};
+/// use candle::math::Vec2;
+/// let bounds = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// let sites = [Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0)];
+/// let cells = voronoi(&sites, &bounds);
+/// assert_eq!(cells[0].polygon.points(), &[Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0)]);
+/// assert_eq!(cells[0].neighbors, vec![1]);
+/// assert_eq!(cells[1].polygon.area(), 4.0);
+/// ```
+pub fn voronoi(sites: &[Vec2], bounds: &Rect) -> Vec<VoronoiCell> {
+    let mut cells: Vec<VoronoiCell> = sites.iter().map(|site| {
+        let mut others: Vec<(f64, usize)> = sites.iter().enumerate()
+            .filter(|&(_, other)| other != site)
+            .map(|(index, other)| ((other - site).length(), index))
+            .collect();
+        others.sort_by(|p, q| p.0.total_cmp(&q.0));
+
+        // Vertices of the cell, each with the site across the edge starting at it, or `None`
+        // for edges along the bounds.
+        let mut cell: Vec<(Vec2, Option<usize>)> = bounds.corners().iter().map(|&corner| (corner, None)).collect();
+        for &(distance, index) in others.iter() {
+            let reach = cell.iter().map(|&(point, _)| (point - site).length()).fold(0.0, f64::max);
+            if distance > 2.0 * reach {
+                break;
+            }
+            cell = clip(&cell, site, &sites[index], index);
+        }
+
+        let mut neighbors: Vec<usize> = cell.iter().filter_map(|&(_, neighbor)| neighbor).collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        VoronoiCell { site: *site, polygon: Polygon::new(cell.into_iter().map(|(point, _)| point).collect()), neighbors }
+    }).collect();
+
+    // Cells meeting at a vertex shared with other cells can end up with an edge too short to
+    // survive rounding on one side only, so adjacency is made symmetric.
+    for i in 0..cells.len() {
+        for k in 0..cells[i].neighbors.len() {
+            let j = cells[i].neighbors[k];
+            if let Err(position) = cells[j].neighbors.binary_search(&i) {
+                cells[j].neighbors.insert(position, i);
+            }
+        }
+    }
+    cells
+}
+
+/// Moves each site to the centroid of its Voronoi cell within `bounds`, `iterations` times,
+/// which spreads the sites out evenly while keeping some of their randomness: Lloyd's
+/// relaxation. Sites whose cells are empty stay where they are.
+///
+/// # Examples
+/// ```
+/// use candle::geometry::{lloyd_relaxation, Rect};
+/// use candle::math::Vec2;
+/// let bounds = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+/// let relaxed = lloyd_relaxation(&[Vec2::new(0.5, 0.5), Vec2::new(1.5, 1.5)], &bounds, 50);
+/// assert!((relaxed[0] - Vec2::new(1.0, 1.0)).length() < 1e-3);
+/// assert!((relaxed[1] - Vec2::new(3.0, 1.0)).length() < 1e-3);
+/// ```
+pub fn lloyd_relaxation(sites: &[Vec2], bounds: &Rect, iterations: usize) -> Vec<Vec2> {
+    let mut sites = sites.to_vec();
+    for _ in 0..iterations {
+        sites = voronoi(&sites, bounds).into_iter()
+            .map(|cell| cell.polygon.centroid().unwrap_or(cell.site))
+            .collect();
+    }
+    sites
+}
+
+/// Cuts the convex `cell` down to the points at least as close to `site` as to `other`.
+fn clip(cell: &[(Vec2, Option<usize>)], site: &Vec2, other: &Vec2, index: usize) -> Vec<(Vec2, Option<usize>)> {
+    let direction = other - site;
+    let middle = (site + other) / 2.0;
+    let side = |point: &Vec2| (point - middle).dot(&direction);
+    let mut clipped: Vec<(Vec2, Option<usize>)> = Vec::with_capacity(cell.len() + 1);
+    // A vertex landing on the previous one replaces it, as the edge leaving it is the new one.
+    let mut push = |vertex: (Vec2, Option<usize>)| match clipped.last_mut() {
+        Some(last) if last.0 == vertex.0 => *last = vertex,
+        _ => clipped.push(vertex),
+    };
+    for (i, &(point, edge)) in cell.iter().enumerate() {
+        let next = cell[(i + 1) % cell.len()].0;
+        let (from, to) = (side(&point), side(&next));
+        if from <= 0.0 {
+            push((point, edge));
+        }
+        if (from <= 0.0) != (to <= 0.0) {
+            let crossing = point + (next - point) * (from / (from - to));
+            // Leaving the half-plane, the cell continues along the bisector; entering it, along
+            // the rest of the old edge.
+            push((crossing, if from <= 0.0 { Some(index) } else { edge }));
+        }
+    }
+    if clipped.len() > 1 && clipped[0].0 == clipped[clipped.len() - 1].0 {
+        clipped.pop();
+    }
+    clipped
+}
@@ -1 +1,11 @@
+#[cfg(feature = "approx")]
+extern crate approx;
+
 pub mod math;
+pub mod lod;
+pub mod mesh;
+pub mod render_math;
+pub mod sim;
+pub mod collections;
+pub mod geometry;
+pub mod spatial;
@@ -0,0 +1,3 @@
+mod select;
+
+pub use self::select::{projected_size, select};
@@ -0,0 +1,62 @@
+/// Returns the projected size of a bounding sphere of the given `radius` at `distance` from a
+/// perspective camera with a vertical field of view of `fov_y` radians, as a fraction of the
+/// viewport height. A value of `1.0` means the bound covers the whole screen vertically.
+/// Bounds that contain the camera return infinity.
+///
+/// # Examples
+/// ```
+/// use candle::lod;
+/// use candle::math::ApproxEq;
+/// use std::f64::consts::PI;
+/// // With a 90 degree field of view the screen is 2 units tall at distance 1.
+/// assert!(lod::projected_size(1.0, 10.0, PI / 2.0).approx_eq_eps(0.1, 1e-2));
+/// assert!(lod::projected_size(1.0, 0.5, PI / 2.0).is_infinite());
+/// ```
+pub fn projected_size(radius: f64, distance: f64, fov_y: f64) -> f64 {
+    if distance <= radius {
+        return f64::INFINITY;
+    }
+    // Angular diameter of the sphere compared against the field of view, projected onto the
+    // image plane so the result is exact at the center of the screen.
+    let half_angle = (radius / distance).asin();
+    half_angle.tan() / (fov_y * 0.5).tan()
+}
+
+/// Selects the level of detail for an object of the given projected `size`. `thresholds` holds
+/// the minimum projected size of each level from the most to the least detailed, so level `i`
+/// is used while `size >= thresholds[i]`, and `thresholds.len()` is returned for objects smaller
+/// than every threshold (the coarsest level, or culled).
+///
+/// When the level used in the previous frame is given, thresholds are widened by the
+/// `hysteresis` fraction against switching: moving to a more detailed level requires exceeding
+/// its threshold by that fraction, and moving to a coarser one requires falling below the current
+/// threshold by it. This prevents popping back and forth when the size hovers around a threshold.
+///
+/// # Examples
+/// ```
+/// use candle::lod;
+/// let thresholds = [0.5, 0.2, 0.05];
+/// assert_eq!(lod::select(0.6, &thresholds, 0.1, None), 0);
+/// assert_eq!(lod::select(0.3, &thresholds, 0.1, None), 1);
+/// assert_eq!(lod::select(0.01, &thresholds, 0.1, None), 3);
+///
+/// // Slightly above the threshold of level 0 isn't enough to leave level 1...
+/// assert_eq!(lod::select(0.52, &thresholds, 0.1, Some(1)), 1);
+/// assert_eq!(lod::select(0.56, &thresholds, 0.1, Some(1)), 0);
+/// // ...and slightly below it isn't enough to leave level 0.
+/// assert_eq!(lod::select(0.48, &thresholds, 0.1, Some(0)), 0);
+/// assert_eq!(lod::select(0.44, &thresholds, 0.1, Some(0)), 1);
+/// ```
+pub fn select(size: f64, thresholds: &[f64], hysteresis: f64, previous: Option<usize>) -> usize {
+    for (level, threshold) in thresholds.iter().enumerate() {
+        let threshold = match previous {
+            Some(previous) if level < previous => threshold * (1.0 + hysteresis),
+            Some(_) => threshold * (1.0 - hysteresis),
+            None => *threshold,
+        };
+        if size >= threshold {
+            return level;
+        }
+    }
+    thresholds.len()
+}
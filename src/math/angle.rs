@@ -0,0 +1,182 @@
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::f64::consts::PI;
+use math::vec2::Vec2;
+
+/// An angle stored in radians and always wrapped to the `(-π, π]` range, so headings can be
+/// added, subtracted and compared without manually normalizing raw numbers.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Angle {
+    radians: f64,
+}
+
+impl Angle {
+
+    /// Allocates a new Angle from the given radians, wrapping it to `(-π, π]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// use std::f64::consts::PI;
+    /// assert!(Angle::from_radians(PI / 2.0).radians().approx_eq(PI / 2.0));
+    /// assert!(Angle::from_radians(3.0 * PI / 2.0).radians().approx_eq(-PI / 2.0));
+    /// assert!(Angle::from_radians(-PI).radians().approx_eq(PI));
+    /// ```
+    pub fn from_radians(radians: f64) -> Angle {
+        Angle { radians: wrap(radians) }
+    }
+
+    /// Allocates a new Angle from the given degrees, wrapping it to `(-180, 180]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// assert!(Angle::from_degrees(90.0).to_degrees().approx_eq(90.0));
+    /// assert!(Angle::from_degrees(270.0).to_degrees().approx_eq(-90.0));
+    /// ```
+    pub fn from_degrees(degrees: f64) -> Angle {
+        Angle::from_radians(degrees.to_radians())
+    }
+
+    /// Returns the angle of the vector measured counterclockwise from the positive x axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, Vec2, ApproxEq};
+    /// assert!(Angle::from_vec2(&Vec2::new(0.0, 2.0)).to_degrees().approx_eq(90.0));
+    /// assert!(Angle::from_vec2(&Vec2::new(-1.0, 0.0)).to_degrees().approx_eq(180.0));
+    /// ```
+    pub fn from_vec2(vec: &Vec2) -> Angle {
+        Angle::from_radians(vec.y.atan2(vec.x))
+    }
+
+    /// Returns the angle in radians, in the `(-π, π]` range.
+    pub fn radians(&self) -> f64 {
+        self.radians
+    }
+
+    /// Returns the angle in degrees, in the `(-180, 180]` range.
+    pub fn to_degrees(&self) -> f64 {
+        self.radians.to_degrees()
+    }
+
+    /// Returns the unit vector pointing in the direction of the angle.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, Vec2, ApproxEq};
+    /// assert!(Angle::from_degrees(90.0).to_vec2().approx_eq(Vec2::new(0.0, 1.0)));
+    /// ```
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.radians.cos(), self.radians.sin())
+    }
+
+    /// Returns the sine of the angle.
+    pub fn sin(&self) -> f64 {
+        self.radians.sin()
+    }
+
+    /// Returns the cosine of the angle.
+    pub fn cos(&self) -> f64 {
+        self.radians.cos()
+    }
+
+    /// Returns the signed rotation in radians that takes this angle to `other` along the
+    /// shortest arc. Positive values are counterclockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// let from = Angle::from_degrees(170.0);
+    /// let to = Angle::from_degrees(-170.0);
+    /// assert!(from.shortest_difference(&to).to_degrees().approx_eq_eps(20.0, 1e-9));
+    /// assert!(to.shortest_difference(&from).to_degrees().approx_eq_eps(-20.0, 1e-9));
+    /// ```
+    pub fn shortest_difference(&self, other: &Angle) -> f64 {
+        wrap(other.radians - self.radians)
+    }
+
+    /// Interpolates between this angle and `other` along the shortest arc, where `t` of `0.0`
+    /// returns this angle and `1.0` returns `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// let from = Angle::from_degrees(170.0);
+    /// let to = Angle::from_degrees(-170.0);
+    /// assert!(from.lerp(&to, 0.5).to_degrees().approx_eq(180.0));
+    /// assert!(from.lerp(&to, 0.75).to_degrees().approx_eq(-175.0));
+    /// ```
+    pub fn lerp(&self, other: &Angle, t: f64) -> Angle {
+        Angle::from_radians(self.radians + self.shortest_difference(other) * t)
+    }
+}
+
+/// Wraps the radians to the `(-π, π]` range.
+fn wrap(radians: f64) -> f64 {
+    let wrapped = (radians + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped == -PI { PI } else { wrapped }
+}
+
+impl Add<Angle> for Angle {
+    type Output = Angle;
+
+    /// Adds the two angles, wrapping the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// let sum = Angle::from_degrees(170.0) + Angle::from_degrees(20.0);
+    /// assert!(sum.to_degrees().approx_eq(-170.0));
+    /// ```
+    fn add(self, other: Angle) -> Angle {
+        Angle::from_radians(self.radians + other.radians)
+    }
+}
+
+impl Sub<Angle> for Angle {
+    type Output = Angle;
+
+    /// Substracts the two angles, wrapping the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// let diff = Angle::from_degrees(-170.0) - Angle::from_degrees(20.0);
+    /// assert!(diff.to_degrees().approx_eq(170.0));
+    /// ```
+    fn sub(self, other: Angle) -> Angle {
+        Angle::from_radians(self.radians - other.radians)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle::from_radians(-self.radians)
+    }
+}
+
+impl Mul<f64> for Angle {
+    type Output = Angle;
+
+    /// Scales the angle, wrapping the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Angle, ApproxEq};
+    /// let scaled = Angle::from_degrees(100.0) * 2.0;
+    /// assert!(scaled.to_degrees().approx_eq(-160.0));
+    /// ```
+    fn mul(self, value: f64) -> Angle {
+        Angle::from_radians(self.radians * value)
+    }
+}
+
+impl Div<f64> for Angle {
+    type Output = Angle;
+
+    fn div(self, value: f64) -> Angle {
+        Angle::from_radians(self.radians / value)
+    }
+}
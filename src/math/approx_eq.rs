@@ -1,16 +1,243 @@
-use std::f64;
-
 pub trait ApproxEq<T> {
+    /// Returns true if the values are approximately equal, accepting either an absolute
+    /// difference or a difference relative to the larger magnitude of up to EPSILON. The
+    /// relative part keeps the comparison meaningful for large magnitudes, where EPSILON alone
+    /// is smaller than the gap between neighbouring floats.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// assert!((0.1 + 0.2).approx_eq(0.3));
+    /// assert!((1.0e10 + 1.0e-6).approx_eq(1.0e10));
+    /// assert!(!1.0.approx_eq(1.0001));
+    /// assert!((0.1f32 + 0.2f32).approx_eq(0.3f32));
+    /// ```
     fn approx_eq(self, other: T) -> bool;
+
+    /// Returns true if the absolute difference between the values is lower than `eps`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// assert!(1.0.approx_eq_eps(1.05, 0.1));
+    /// assert!(!1.0f32.approx_eq_eps(1.5f32, 0.1f32));
+    /// ```
     fn approx_eq_eps(self, other: T, eps: T) -> bool;
+
+    /// Returns true if the difference between the values is at most `max_rel` times the
+    /// larger of their magnitudes.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// assert!(1000.0.approx_eq_rel(1001.0, 0.01));
+    /// assert!(!1.0.approx_eq_rel(2.0, 0.01));
+    /// assert!(1000.0f32.approx_eq_rel(1001.0f32, 0.01));
+    /// ```
+    fn approx_eq_rel(self, other: T, max_rel: f64) -> bool;
+
+    /// Returns true if the values are at most `max_ulps` representable floats apart. Values
+    /// with different signs are only equal if both are zero, and NaN is never equal to anything.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// let next = f64::from_bits(1.0f64.to_bits() + 2);
+    /// assert!(1.0.approx_eq_ulps(next, 2));
+    /// assert!(!1.0.approx_eq_ulps(next, 1));
+    /// assert!(0.0.approx_eq_ulps(-0.0, 0));
+    ///
+    /// let next = f32::from_bits(1.0f32.to_bits() + 1);
+    /// assert!(1.0f32.approx_eq_ulps(next, 1));
+    /// ```
+    fn approx_eq_ulps(self, other: T, max_ulps: u64) -> bool;
+}
+
+macro_rules! impl_approx_eq_float {
+    ($float:ident) => {
+        impl ApproxEq<$float> for $float {
+            fn approx_eq(self, other: Self) -> bool {
+                self.approx_eq_eps(other, $float::EPSILON) || self.approx_eq_rel(other, $float::EPSILON as f64)
+            }
+
+            fn approx_eq_eps(self, other: Self, eps: Self) -> bool {
+                (self - other).abs() < eps
+            }
+
+            fn approx_eq_rel(self, other: Self, max_rel: f64) -> bool {
+                if self == other {
+                    return true;
+                }
+                let largest = self.abs().max(other.abs());
+                (self - other).abs() <= largest * max_rel as $float
+            }
+
+            fn approx_eq_ulps(self, other: Self, max_ulps: u64) -> bool {
+                if self.is_nan() || other.is_nan() {
+                    return false;
+                }
+                if self == other {
+                    return true;
+                }
+                if self.is_sign_positive() != other.is_sign_positive() {
+                    return false;
+                }
+                let distance = (self.to_bits() as i64 - other.to_bits() as i64).unsigned_abs();
+                distance <= max_ulps
+            }
+        }
+    }
 }
 
-impl ApproxEq<f64> for f64 {
-    fn approx_eq(self, other: Self) -> bool {
-        self.approx_eq_eps(other, f64::EPSILON)
+impl_approx_eq_float!(f32);
+impl_approx_eq_float!(f64);
+
+impl<T: ApproxEq<T> + Copy> ApproxEq<&[T]> for &[T] {
+    /// Returns true if both slices have the same length and every pair of elements is
+    /// approximately equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let values = [0.1 + 0.2, 1.0];
+    /// assert!(values[..].approx_eq(&[0.3, 1.0][..]));
+    /// assert!(!values[..].approx_eq(&[0.3][..]));
+    ///
+    /// let polyline = vec![Vec2::new(0.0, 0.0), Vec2::new(0.1 + 0.2, 1.0)];
+    /// let expected = vec![Vec2::new(0.0, 0.0), Vec2::new(0.3, 1.0)];
+    /// assert!(polyline.as_slice().approx_eq(expected.as_slice()));
+    /// ```
+    fn approx_eq(self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq(*b))
     }
 
-    fn approx_eq_eps(self, other: Self, eps: Self) -> bool {
-        (self - other).abs() < eps
+    /// Compares every pair of elements with the tolerance at the same index of `eps`, which
+    /// must have the same length as both slices.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// let values = [1.0, 2.0];
+    /// assert!(values[..].approx_eq_eps(&[1.05, 2.5][..], &[0.1, 1.0][..]));
+    /// assert!(!values[..].approx_eq_eps(&[1.05, 2.5][..], &[0.1, 0.1][..]));
+    /// ```
+    fn approx_eq_eps(self, other: &[T], eps: &[T]) -> bool {
+        self.len() == other.len() && self.len() == eps.len() &&
+            self.iter().zip(other).zip(eps).all(|((a, b), e)| a.approx_eq_eps(*b, *e))
+    }
+
+    fn approx_eq_rel(self, other: &[T], max_rel: f64) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq_rel(*b, max_rel))
+    }
+
+    fn approx_eq_ulps(self, other: &[T], max_ulps: u64) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq_ulps(*b, max_ulps))
+    }
+}
+
+impl<T: ApproxEq<T> + Copy, const N: usize> ApproxEq<[T; N]> for [T; N] {
+    /// Returns true if every pair of elements is approximately equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// let matrix = [1.0, 0.1 + 0.2, 0.0, 1.0];
+    /// assert!(matrix.approx_eq([1.0, 0.3, 0.0, 1.0]));
+    /// assert!(matrix.approx_eq_eps([1.0, 0.25, 0.0, 1.0], [0.1; 4]));
+    /// ```
+    fn approx_eq(self, other: [T; N]) -> bool {
+        self[..].approx_eq(&other[..])
     }
+
+    fn approx_eq_eps(self, other: [T; N], eps: [T; N]) -> bool {
+        self[..].approx_eq_eps(&other[..], &eps[..])
+    }
+
+    fn approx_eq_rel(self, other: [T; N], max_rel: f64) -> bool {
+        self[..].approx_eq_rel(&other[..], max_rel)
+    }
+
+    fn approx_eq_ulps(self, other: [T; N], max_ulps: u64) -> bool {
+        self[..].approx_eq_ulps(&other[..], max_ulps)
+    }
+}
+
+impl<A: ApproxEq<A>, B: ApproxEq<B>> ApproxEq<(A, B)> for (A, B) {
+    /// Returns true if both pairs of elements are approximately equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// assert!((0.1 + 0.2, 1.0).approx_eq((0.3, 1.0)));
+    /// assert!(!(1.0, 2.0).approx_eq_eps((1.0, 2.5), (0.1, 0.1)));
+    /// ```
+    fn approx_eq(self, other: (A, B)) -> bool {
+        self.0.approx_eq(other.0) && self.1.approx_eq(other.1)
+    }
+
+    fn approx_eq_eps(self, other: (A, B), eps: (A, B)) -> bool {
+        self.0.approx_eq_eps(other.0, eps.0) && self.1.approx_eq_eps(other.1, eps.1)
+    }
+
+    fn approx_eq_rel(self, other: (A, B), max_rel: f64) -> bool {
+        self.0.approx_eq_rel(other.0, max_rel) && self.1.approx_eq_rel(other.1, max_rel)
+    }
+
+    fn approx_eq_ulps(self, other: (A, B), max_ulps: u64) -> bool {
+        self.0.approx_eq_ulps(other.0, max_ulps) && self.1.approx_eq_ulps(other.1, max_ulps)
+    }
+}
+
+/// Asserts that two values are approximately equal using `ApproxEq`, printing both values and
+/// their difference when they are not. An explicit absolute tolerance can be given with
+/// `eps = ...`, which compares with `approx_eq_eps` instead.
+///
+/// The compared values must implement `Copy`, `Debug` and `Sub`.
+///
+/// # Examples
+/// ```
+/// #[macro_use] extern crate candle;
+/// use candle::math::Vec2;
+/// # fn main() {
+/// assert_approx_eq!(0.1 + 0.2, 0.3);
+/// assert_approx_eq!(1.0, 1.05, eps = 0.1);
+/// assert_approx_eq!(Vec2::new(1.0, 2.0), Vec2::new(1.01, 2.01), eps = Vec2::new(0.1, 0.1));
+/// # }
+/// ```
+///
+/// ```should_panic
+/// #[macro_use] extern crate candle;
+/// # fn main() {
+/// // panics with "assertion failed: `left.approx_eq(right)` left: `1.0`, right: `1.1`, diff: ..."
+/// assert_approx_eq!(1.0, 1.1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr) => ({
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !$crate::math::ApproxEq::approx_eq(*left_val, *right_val) {
+                    panic!("assertion failed: `left.approx_eq(right)`\n  left: `{:?}`,\n right: `{:?}`,\n  diff: `{:?}`",
+                           *left_val, *right_val, *left_val - *right_val)
+                }
+            }
+        }
+    });
+    ($left:expr, $right:expr,) => (
+        assert_approx_eq!($left, $right)
+    );
+    ($left:expr, $right:expr, eps = $eps:expr) => ({
+        match (&$left, &$right, &$eps) {
+            (left_val, right_val, eps_val) => {
+                if !$crate::math::ApproxEq::approx_eq_eps(*left_val, *right_val, *eps_val) {
+                    panic!("assertion failed: `left.approx_eq_eps(right, eps)`\n  left: `{:?}`,\n right: `{:?}`,\n  diff: `{:?}`,\n   eps: `{:?}`",
+                           *left_val, *right_val, *left_val - *right_val, *eps_val)
+                }
+            }
+        }
+    });
+    ($left:expr, $right:expr, eps = $eps:expr,) => (
+        assert_approx_eq!($left, $right, eps = $eps)
+    );
 }
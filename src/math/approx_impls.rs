@@ -0,0 +1,270 @@
+//! Implementations of the `approx` crate comparison traits for the math types, enabled by the
+//! `approx` feature.
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use math::vec2::Vec2;
+use math::vec3::Vec3;
+use math::complex::Complex;
+use math::mat2::Mat2;
+use math::mat3::Mat3;
+
+impl AbsDiffEq for Vec2 {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Vec2;
+    /// # fn main() {
+    /// assert_abs_diff_eq!(Vec2::new(1.0, 2.0), Vec2::new(1.05, 1.95), epsilon = 0.1);
+    /// # }
+    /// ```
+    fn abs_diff_eq(&self, other: &Vec2, epsilon: f64) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon) && self.y.abs_diff_eq(&other.y, epsilon)
+    }
+}
+
+impl RelativeEq for Vec2 {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Vec2;
+    /// # fn main() {
+    /// assert_relative_eq!(Vec2::new(0.1 + 0.2, 1.0e10), Vec2::new(0.3, 1.0e10 + 1.0e-6));
+    /// assert_relative_ne!(Vec2::new(1.0, 2.0), Vec2::new(1.0, 2.1));
+    /// # }
+    /// ```
+    fn relative_eq(&self, other: &Vec2, epsilon: f64, max_relative: f64) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative) && self.y.relative_eq(&other.y, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Vec2 {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Vec2;
+    /// # fn main() {
+    /// let next = f64::from_bits(2.0f64.to_bits() + 1);
+    /// assert_ulps_eq!(Vec2::new(1.0, 2.0), Vec2::new(1.0, next));
+    /// # }
+    /// ```
+    fn ulps_eq(&self, other: &Vec2, epsilon: f64, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps) && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+    }
+}
+
+impl AbsDiffEq for Vec3 {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Vec3;
+    /// # fn main() {
+    /// assert_abs_diff_eq!(Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.05, 1.95, 3.0), epsilon = 0.1);
+    /// # }
+    /// ```
+    fn abs_diff_eq(&self, other: &Vec3, epsilon: f64) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon) && self.y.abs_diff_eq(&other.y, epsilon) &&
+            self.z.abs_diff_eq(&other.z, epsilon)
+    }
+}
+
+impl RelativeEq for Vec3 {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Vec3, epsilon: f64, max_relative: f64) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative) &&
+            self.y.relative_eq(&other.y, epsilon, max_relative) &&
+            self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Vec3 {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Vec3, epsilon: f64, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps) && self.y.ulps_eq(&other.y, epsilon, max_ulps) &&
+            self.z.ulps_eq(&other.z, epsilon, max_ulps)
+    }
+}
+
+impl AbsDiffEq for Complex {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Complex;
+    /// # fn main() {
+    /// assert_relative_eq!(Complex::new(0.0, 1.0) * Complex::new(0.0, 1.0), Complex::new(-1.0, 0.0));
+    /// assert_abs_diff_ne!(Complex::new(1.0, 2.0), Complex::new(1.0, 2.2), epsilon = 0.1);
+    /// # }
+    /// ```
+    fn abs_diff_eq(&self, other: &Complex, epsilon: f64) -> bool {
+        self.re.abs_diff_eq(&other.re, epsilon) && self.im.abs_diff_eq(&other.im, epsilon)
+    }
+}
+
+impl RelativeEq for Complex {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Complex, epsilon: f64, max_relative: f64) -> bool {
+        self.re.relative_eq(&other.re, epsilon, max_relative) &&
+            self.im.relative_eq(&other.im, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Complex {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Complex, epsilon: f64, max_ulps: u32) -> bool {
+        self.re.ulps_eq(&other.re, epsilon, max_ulps) &&
+            self.im.ulps_eq(&other.im, epsilon, max_ulps)
+    }
+}
+
+impl AbsDiffEq for Mat2 {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Mat2;
+    /// # fn main() {
+    /// let m = Mat2::new(2.0, 1.0, 1.0, 1.0);
+    /// assert_relative_eq!(m * m.inverse().unwrap(), Mat2::IDENTITY);
+    /// # }
+    /// ```
+    fn abs_diff_eq(&self, other: &Mat2, epsilon: f64) -> bool {
+        self.m00.abs_diff_eq(&other.m00, epsilon) && self.m01.abs_diff_eq(&other.m01, epsilon) &&
+            self.m10.abs_diff_eq(&other.m10, epsilon) && self.m11.abs_diff_eq(&other.m11, epsilon)
+    }
+}
+
+impl RelativeEq for Mat2 {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Mat2, epsilon: f64, max_relative: f64) -> bool {
+        self.m00.relative_eq(&other.m00, epsilon, max_relative) &&
+            self.m01.relative_eq(&other.m01, epsilon, max_relative) &&
+            self.m10.relative_eq(&other.m10, epsilon, max_relative) &&
+            self.m11.relative_eq(&other.m11, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Mat2 {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Mat2, epsilon: f64, max_ulps: u32) -> bool {
+        self.m00.ulps_eq(&other.m00, epsilon, max_ulps) &&
+            self.m01.ulps_eq(&other.m01, epsilon, max_ulps) &&
+            self.m10.ulps_eq(&other.m10, epsilon, max_ulps) &&
+            self.m11.ulps_eq(&other.m11, epsilon, max_ulps)
+    }
+}
+
+impl AbsDiffEq for Mat3 {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    /// # Examples
+    /// ```
+    /// #[macro_use] extern crate approx;
+    /// extern crate candle;
+    /// use candle::math::Mat3;
+    /// # fn main() {
+    /// let m = Mat3::new([[1.0, 1.0, 1.0], [0.0, 2.0, 5.0], [2.0, 5.0, -1.0]]);
+    /// assert_relative_eq!(m * m.inverse().unwrap(), Mat3::IDENTITY, epsilon = 1e-12);
+    /// # }
+    /// ```
+    fn abs_diff_eq(&self, other: &Mat3, epsilon: f64) -> bool {
+        self.m00.abs_diff_eq(&other.m00, epsilon) && self.m01.abs_diff_eq(&other.m01, epsilon) &&
+            self.m02.abs_diff_eq(&other.m02, epsilon) && self.m10.abs_diff_eq(&other.m10, epsilon) &&
+            self.m11.abs_diff_eq(&other.m11, epsilon) && self.m12.abs_diff_eq(&other.m12, epsilon) &&
+            self.m20.abs_diff_eq(&other.m20, epsilon) && self.m21.abs_diff_eq(&other.m21, epsilon) &&
+            self.m22.abs_diff_eq(&other.m22, epsilon)
+    }
+}
+
+impl RelativeEq for Mat3 {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Mat3, epsilon: f64, max_relative: f64) -> bool {
+        self.m00.relative_eq(&other.m00, epsilon, max_relative) &&
+            self.m01.relative_eq(&other.m01, epsilon, max_relative) &&
+            self.m02.relative_eq(&other.m02, epsilon, max_relative) &&
+            self.m10.relative_eq(&other.m10, epsilon, max_relative) &&
+            self.m11.relative_eq(&other.m11, epsilon, max_relative) &&
+            self.m12.relative_eq(&other.m12, epsilon, max_relative) &&
+            self.m20.relative_eq(&other.m20, epsilon, max_relative) &&
+            self.m21.relative_eq(&other.m21, epsilon, max_relative) &&
+            self.m22.relative_eq(&other.m22, epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Mat3 {
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Mat3, epsilon: f64, max_ulps: u32) -> bool {
+        self.m00.ulps_eq(&other.m00, epsilon, max_ulps) &&
+            self.m01.ulps_eq(&other.m01, epsilon, max_ulps) &&
+            self.m02.ulps_eq(&other.m02, epsilon, max_ulps) &&
+            self.m10.ulps_eq(&other.m10, epsilon, max_ulps) &&
+            self.m11.ulps_eq(&other.m11, epsilon, max_ulps) &&
+            self.m12.ulps_eq(&other.m12, epsilon, max_ulps) &&
+            self.m20.ulps_eq(&other.m20, epsilon, max_ulps) &&
+            self.m21.ulps_eq(&other.m21, epsilon, max_ulps) &&
+            self.m22.ulps_eq(&other.m22, epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,107 @@
+use math::vec2::Vec2;
+
+/// Barycentric coordinates of a point with respect to a triangle `(a, b, c)`. The weights
+/// `u`, `v` and `w` belong to the vertices `a`, `b` and `c` respectively and always add up
+/// to one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Barycentric {
+    pub u: f64,
+    pub v: f64,
+    pub w: f64,
+}
+
+impl Barycentric {
+
+    /// Allocates new barycentric coordinates with the given weights.
+    pub fn new(u: f64, v: f64, w: f64) -> Barycentric {
+        Barycentric { u, v, w }
+    }
+
+    /// Computes the barycentric coordinates of `point` with respect to the triangle
+    /// `(a, b, c)`. Returns `None` if the triangle is degenerate (zero area).
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, Barycentric, ApproxEq};
+    /// let a = Vec2::new(0.0, 0.0);
+    /// let b = Vec2::new(4.0, 0.0);
+    /// let c = Vec2::new(0.0, 4.0);
+    /// let bary = Barycentric::from_point(&Vec2::new(1.0, 2.0), &a, &b, &c).unwrap();
+    /// assert!(bary.u.approx_eq(0.25));
+    /// assert!(bary.v.approx_eq(0.25));
+    /// assert!(bary.w.approx_eq(0.5));
+    ///
+    /// let flat = Vec2::new(8.0, 0.0);
+    /// assert!(Barycentric::from_point(&Vec2::new(1.0, 2.0), &a, &b, &flat).is_none());
+    /// ```
+    pub fn from_point(point: &Vec2, a: &Vec2, b: &Vec2, c: &Vec2) -> Option<Barycentric> {
+        let ab = b - a;
+        let ac = c - a;
+        let ap = point - a;
+        let denom = ab.x * ac.y - ab.y * ac.x;
+        if denom == 0.0 {
+            return None;
+        }
+        let v = (ap.x * ac.y - ap.y * ac.x) / denom;
+        let w = (ab.x * ap.y - ab.y * ap.x) / denom;
+        Some(Barycentric::new(1.0 - v - w, v, w))
+    }
+
+    /// Returns true if the coordinates describe a point inside the triangle or on its edges.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Barycentric;
+    /// assert!(Barycentric::new(0.2, 0.3, 0.5).is_inside());
+    /// assert!(Barycentric::new(0.0, 0.5, 0.5).is_inside());
+    /// assert!(!Barycentric::new(-0.1, 0.6, 0.5).is_inside());
+    /// ```
+    pub fn is_inside(&self) -> bool {
+        self.u >= 0.0 && self.v >= 0.0 && self.w >= 0.0
+    }
+
+    /// Interpolates a scalar attribute given its values at the vertices `a`, `b` and `c`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Barycentric;
+    /// let bary = Barycentric::new(0.5, 0.25, 0.25);
+    /// assert_eq!(bary.interpolate(2.0, 4.0, 8.0), 4.0);
+    /// ```
+    pub fn interpolate(&self, a: f64, b: f64, c: f64) -> f64 {
+        self.u * a + self.v * b + self.w * c
+    }
+
+    /// Interpolates a vector attribute given its values at the vertices `a`, `b` and `c`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, Barycentric};
+    /// let bary = Barycentric::new(0.5, 0.5, 0.0);
+    /// let uv = bary.interpolate_vec2(&Vec2::new(0.0, 0.0), &Vec2::new(1.0, 1.0), &Vec2::new(0.0, 1.0));
+    /// assert_eq!(uv.x, 0.5);
+    /// assert_eq!(uv.y, 0.5);
+    /// ```
+    pub fn interpolate_vec2(&self, a: &Vec2, b: &Vec2, c: &Vec2) -> Vec2 {
+        Vec2::new(self.interpolate(a.x, b.x, c.x), self.interpolate(a.y, b.y, c.y))
+    }
+}
+
+/// Returns true if `point` lies inside the triangle `(a, b, c)` or on one of its edges.
+/// Degenerate triangles contain no points.
+///
+/// # Examples
+/// ```
+/// use candle::math::{self, Vec2};
+/// let a = Vec2::new(0.0, 0.0);
+/// let b = Vec2::new(4.0, 0.0);
+/// let c = Vec2::new(0.0, 4.0);
+/// assert!(math::point_in_triangle(&Vec2::new(1.0, 1.0), &a, &b, &c));
+/// assert!(!math::point_in_triangle(&Vec2::new(3.0, 3.0), &a, &b, &c));
+/// ```
+pub fn point_in_triangle(point: &Vec2, a: &Vec2, b: &Vec2, c: &Vec2) -> bool {
+    match Barycentric::from_point(point, a, b, c) {
+        Some(bary) => bary.is_inside(),
+        None => false,
+    }
+}
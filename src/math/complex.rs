@@ -0,0 +1,244 @@
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use math::vec2::Vec2;
+use math::angle::Angle;
+use math::approx_eq::ApproxEq;
+
+/// A complex number `re + im * i`. Multiplying by a unit complex number rotates by its angle,
+/// which makes unit complex numbers a compact 2D rotation that composes by multiplication.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+    pub const ONE: Complex = Complex { re: 1.0, im: 0.0 };
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    /// Allocates a new Complex with the given real and imaginary parts.
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// Allocates the unit complex number that rotates by `angle`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, Angle, Vec2, ApproxEq};
+    /// let rotation = Complex::from_angle(Angle::from_degrees(90.0));
+    /// assert!(rotation.rotate(&Vec2::new(2.0, 0.0)).approx_eq(Vec2::new(0.0, 2.0)));
+    /// ```
+    pub fn from_angle(angle: Angle) -> Complex {
+        Complex::from_polar(1.0, angle.radians())
+    }
+
+    /// Allocates a new Complex from its magnitude and its argument in radians.
+    pub fn from_polar(norm: f64, arg: f64) -> Complex {
+        let (sin, cos) = arg.sin_cos();
+        Complex::new(norm * cos, norm * sin)
+    }
+
+    /// Allocates a new Complex with the vector `x` as real part and `y` as imaginary part.
+    pub fn from_vec2(vec: &Vec2) -> Complex {
+        Complex::new(vec.x, vec.y)
+    }
+
+    /// Returns the vector with the real part as `x` and the imaginary part as `y`.
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.re, self.im)
+    }
+
+    /// Returns the argument of the number as an Angle, which is the rotation it applies.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, ApproxEq};
+    /// assert!(Complex::new(0.0, 3.0).to_angle().to_degrees().approx_eq(90.0));
+    /// ```
+    pub fn to_angle(&self) -> Angle {
+        Angle::from_radians(self.arg())
+    }
+
+    /// Returns the argument of the number in radians, in the `(-π, π]` range.
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the magnitude of the number.
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the squared magnitude of the number, which avoids the square root.
+    pub fn norm_squared(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Returns the complex conjugate, which for unit numbers is the inverse rotation.
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// Returns the number scaled to unit magnitude, so it only represents a rotation.
+    pub fn normalized(&self) -> Complex {
+        let norm = self.norm();
+        Complex::new(self.re / norm, self.im / norm)
+    }
+
+    /// Rotates (and scales, unless the number is unit) the vector by the number.
+    pub fn rotate(&self, vec: &Vec2) -> Vec2 {
+        (*self * Complex::from_vec2(vec)).to_vec2()
+    }
+
+    /// Returns `e` raised to the number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, ApproxEq};
+    /// use std::f64::consts::PI;
+    /// // Euler's identity
+    /// assert!(Complex::new(0.0, PI).exp().approx_eq(Complex::new(-1.0, 0.0)));
+    /// ```
+    pub fn exp(&self) -> Complex {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Returns the principal natural logarithm of the number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, ApproxEq};
+    /// let value = Complex::new(1.5, -2.0);
+    /// assert!(value.ln().exp().approx_eq(value));
+    /// ```
+    pub fn ln(&self) -> Complex {
+        Complex::new(self.norm().ln(), self.arg())
+    }
+
+    /// Raises the number to a real power, using the principal branch. As with `f64::powf`, any
+    /// number to the power of zero is one, and zero to a negative power is infinite.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, Angle, ApproxEq};
+    /// let rotation = Complex::from_angle(Angle::from_degrees(60.0));
+    /// let half = rotation.powf(0.5);
+    /// assert!(half.to_angle().to_degrees().approx_eq_eps(30.0, 1e-9));
+    /// assert!((half * half).approx_eq(rotation));
+    ///
+    /// assert_eq!(Complex::ZERO.powf(0.0), Complex::ONE);
+    /// assert_eq!(Complex::ZERO.powf(2.0), Complex::ZERO);
+    /// assert_eq!(Complex::ZERO.powf(-1.0), Complex::new(f64::INFINITY, 0.0));
+    /// ```
+    pub fn powf(&self, exponent: f64) -> Complex {
+        if exponent == 0.0 {
+            return Complex::ONE;
+        }
+        if self.re == 0.0 && self.im == 0.0 {
+            // The argument of zero is undefined; only the magnitude matters.
+            if exponent > 0.0 {
+                return Complex::ZERO;
+            }
+            if exponent < 0.0 {
+                return Complex::new(f64::INFINITY, 0.0);
+            }
+        }
+        Complex::from_polar(self.norm().powf(exponent), self.arg() * exponent)
+    }
+}
+
+impl Add<Complex> for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub<Complex> for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul<Complex> for Complex {
+    type Output = Complex;
+
+    /// Multiplies the two numbers. For unit numbers this composes their rotations.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, Angle, ApproxEq};
+    /// let a = Complex::from_angle(Angle::from_degrees(30.0));
+    /// let b = Complex::from_angle(Angle::from_degrees(60.0));
+    /// assert!((a * b).approx_eq_eps(Complex::I, Complex::new(1e-9, 1e-9)));
+    /// ```
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(self.re * other.re - self.im * other.im, self.re * other.im + self.im * other.re)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+
+    fn mul(self, value: f64) -> Complex {
+        Complex::new(self.re * value, self.im * value)
+    }
+}
+
+impl Div<Complex> for Complex {
+    type Output = Complex;
+
+    /// Divides the two numbers. For unit numbers this undoes the rotation of `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Complex, ApproxEq};
+    /// let value = Complex::new(3.0, 4.0) / Complex::new(1.0, 2.0);
+    /// assert!(value.approx_eq(Complex::new(2.2, -0.4)));
+    /// ```
+    fn div(self, other: Complex) -> Complex {
+        let denom = other.norm_squared();
+        let numer = self * other.conj();
+        Complex::new(numer.re / denom, numer.im / denom)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Complex;
+
+    fn div(self, value: f64) -> Complex {
+        Complex::new(self.re / value, self.im / value)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl ApproxEq<Complex> for Complex {
+    fn approx_eq_eps(self, other: Complex, eps: Complex) -> bool {
+        (self.re - other.re).abs() < eps.re && (self.im - other.im).abs() < eps.im
+    }
+
+    /// Returns true if both parts are approximately equal, with the combined absolute and
+    /// relative EPSILON tolerance.
+    fn approx_eq(self, other: Complex) -> bool {
+        self.re.approx_eq(other.re) && self.im.approx_eq(other.im)
+    }
+
+    fn approx_eq_rel(self, other: Complex, max_rel: f64) -> bool {
+        self.re.approx_eq_rel(other.re, max_rel) && self.im.approx_eq_rel(other.im, max_rel)
+    }
+
+    fn approx_eq_ulps(self, other: Complex, max_ulps: u64) -> bool {
+        self.re.approx_eq_ulps(other.re, max_ulps) && self.im.approx_eq_ulps(other.im, max_ulps)
+    }
+}
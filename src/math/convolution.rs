@@ -0,0 +1,161 @@
+use math::vec2::Vec2;
+use math::grid::Grid;
+
+/// How cells outside of the grid are read when a kernel overlaps its border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderMode {
+    /// Repeat the nearest edge cell, suited to heightmaps and images.
+    Clamp,
+    /// Read from the opposite edge, suited to tiling textures and periodic fields.
+    Wrap,
+}
+
+/// A convolution kernel: a `width` by `height` matrix of weights, both odd, centered on the
+/// cell being filtered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kernel {
+    width: usize,
+    height: usize,
+    weights: Vec<f64>,
+}
+
+impl Kernel {
+
+    /// Allocates a new Kernel from its weights, row by row. Returns `None` if a dimension is even
+    /// or the number of weights is not `width * height`.
+    pub fn new(width: usize, height: usize, weights: Vec<f64>) -> Option<Kernel> {
+        if width.is_multiple_of(2) || height.is_multiple_of(2) || weights.len() != width * height {
+            return None;
+        }
+        Some(Kernel { width, height, weights })
+    }
+
+    /// Returns the kernel averaging the square of `2 * radius + 1` cells on a side.
+    pub fn box_blur(radius: usize) -> Kernel {
+        let size = 2 * radius + 1;
+        Kernel { width: size, height: size, weights: vec![1.0 / (size * size) as f64; size * size] }
+    }
+
+    /// Returns the normalized Gaussian kernel with standard deviation `sigma`, in cells,
+    /// truncated at three standard deviations.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::ApproxEq;
+    /// use candle::math::convolution::Kernel;
+    /// let kernel = Kernel::gaussian(1.0);
+    /// assert_eq!(kernel.width(), 7);
+    /// assert!(kernel.weights().iter().sum::<f64>().approx_eq_eps(1.0, 1e-12));
+    /// assert!(kernel.weight(0, 0) > kernel.weight(1, 0));
+    /// ```
+    pub fn gaussian(sigma: f64) -> Kernel {
+        let radius = (3.0 * sigma).ceil().max(0.0) as usize;
+        let size = 2 * radius + 1;
+        let mut weights = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let (dx, dy) = (x as f64 - radius as f64, y as f64 - radius as f64);
+                weights.push(if sigma > 0.0 { (-(dx * dx + dy * dy) / (2.0 * sigma * sigma)).exp() } else { 1.0 });
+            }
+        }
+        let total: f64 = weights.iter().sum();
+        for weight in weights.iter_mut() {
+            *weight /= total;
+        }
+        Kernel { width: size, height: size, weights }
+    }
+
+    /// Returns the Sobel kernel estimating the derivative along x, scaled so the response to a
+    /// slope of one per cell is one.
+    pub fn sobel_x() -> Kernel {
+        Kernel { width: 3, height: 3, weights: vec![-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0].into_iter().map(|w| w / 8.0).collect() }
+    }
+
+    /// Returns the Sobel kernel estimating the derivative along y, scaled like `sobel_x`.
+    pub fn sobel_y() -> Kernel {
+        Kernel { width: 3, height: 3, weights: vec![-1.0, -2.0, -1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 1.0].into_iter().map(|w| w / 8.0).collect() }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the weights row by row.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the weight at the offset `(dx, dy)` from the center.
+    pub fn weight(&self, dx: isize, dy: isize) -> f64 {
+        let x = (dx + (self.width / 2) as isize) as usize;
+        let y = (dy + (self.height / 2) as isize) as usize;
+        self.weights[y * self.width + x]
+    }
+}
+
+/// Filters the grid with the kernel, returning a grid of the same size. Each output cell is the
+/// sum of the input cells around it weighted by the kernel laid over them, without flipping the
+/// kernel (strictly, a correlation), so `Kernel::sobel_x` responds positively to values that
+/// increase with x.
+///
+/// # Examples
+/// ```
+/// use candle::math::Grid;
+/// use candle::math::convolution::{self, BorderMode, Kernel};
+/// let mut grid = Grid::new(5, 5, 0.0);
+/// grid[(2, 2)] = 9.0;
+/// let blurred = convolution::convolve(&grid, &Kernel::box_blur(1), BorderMode::Clamp);
+/// assert_eq!(blurred[(1, 1)], 1.0);
+/// assert_eq!(blurred[(0, 0)], 0.0);
+///
+/// let ramp = Grid::from_fn(4, 4, |x, _| x as f64 * 2.0);
+/// let slope = convolution::convolve(&ramp, &Kernel::sobel_x(), BorderMode::Clamp);
+/// assert_eq!(slope[(1, 1)], 2.0);
+/// // Clamping flattens the ramp past its edges.
+/// assert_eq!(slope[(0, 1)], 1.0);
+/// ```
+pub fn convolve(grid: &Grid<f64>, kernel: &Kernel, border: BorderMode) -> Grid<f64> {
+    let (width, height) = (grid.width(), grid.height());
+    let (rx, ry) = ((kernel.width / 2) as isize, (kernel.height / 2) as isize);
+    Grid::from_fn(width, height, |x, y| {
+        let mut sum = 0.0;
+        for dy in -ry..=ry {
+            let sy = border_index(y as isize + dy, height, border);
+            for dx in -rx..=rx {
+                let sx = border_index(x as isize + dx, width, border);
+                sum += kernel.weight(dx, dy) * grid[(sx, sy)];
+            }
+        }
+        sum
+    })
+}
+
+/// Returns the gradient of the grid at every cell, estimated with the Sobel kernels, in units
+/// per cell. Useful to extract slopes and normals from heightmaps.
+///
+/// # Examples
+/// ```
+/// use candle::math::{Grid, Vec2};
+/// use candle::math::convolution::{self, BorderMode};
+/// let plane = Grid::from_fn(4, 4, |x, y| x as f64 - 3.0 * y as f64);
+/// let gradient = convolution::gradient(&plane, BorderMode::Clamp);
+/// assert_eq!(gradient[(1, 2)], Vec2::new(1.0, -3.0));
+/// ```
+pub fn gradient(grid: &Grid<f64>, border: BorderMode) -> Grid<Vec2> {
+    let dx = convolve(grid, &Kernel::sobel_x(), border);
+    let dy = convolve(grid, &Kernel::sobel_y(), border);
+    Grid::from_fn(grid.width(), grid.height(), |x, y| Vec2::new(dx[(x, y)], dy[(x, y)]))
+}
+
+fn border_index(index: isize, len: usize, border: BorderMode) -> usize {
+    match border {
+        BorderMode::Clamp => index.clamp(0, len as isize - 1) as usize,
+        BorderMode::Wrap => index.rem_euclid(len as isize) as usize,
+    }
+}
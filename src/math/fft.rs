@@ -0,0 +1,134 @@
+use std::f64::consts::PI;
+use math::complex::Complex;
+
+/// Computes the discrete Fourier transform of `buffer` in place with the iterative radix-2
+/// Cooley-Tukey algorithm. Bin `k` of the result holds the amplitude and phase of the frequency
+/// of `k` cycles over the whole buffer. The transform is unnormalized: `inverse` divides by the
+/// length, so a forward and inverse round trip returns the input.
+///
+/// # Panics
+/// Panics if the length of the buffer is not a power of two.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fft, Complex, ApproxEq};
+/// // One cycle of a cosine over four samples.
+/// let mut buffer = [Complex::new(1.0, 0.0), Complex::ZERO, Complex::new(-1.0, 0.0), Complex::ZERO];
+/// fft::forward(&mut buffer);
+/// let eps = Complex::new(1e-12, 1e-12);
+/// assert!(buffer[1].approx_eq_eps(Complex::new(2.0, 0.0), eps));
+/// assert!(buffer[3].approx_eq_eps(Complex::new(2.0, 0.0), eps));
+/// assert!(buffer[0].approx_eq_eps(Complex::ZERO, eps));
+/// ```
+pub fn forward(buffer: &mut [Complex]) {
+    transform(buffer, -1.0);
+}
+
+/// Computes the inverse discrete Fourier transform of `buffer` in place, including the division
+/// by the length.
+///
+/// # Panics
+/// Panics if the length of the buffer is not a power of two.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fft, Complex, ApproxEq};
+/// let signal = [Complex::new(1.0, 2.0), Complex::new(-3.0, 0.5), Complex::new(0.0, 1.0), Complex::new(4.0, -1.0)];
+/// let mut buffer = signal;
+/// fft::forward(&mut buffer);
+/// fft::inverse(&mut buffer);
+/// for (a, b) in buffer.iter().zip(signal.iter()) {
+///     assert!(a.approx_eq_eps(*b, Complex::new(1e-12, 1e-12)));
+/// }
+/// ```
+pub fn inverse(buffer: &mut [Complex]) {
+    transform(buffer, 1.0);
+    let scale = 1.0 / buffer.len() as f64;
+    for value in buffer.iter_mut() {
+        *value = *value * scale;
+    }
+}
+
+/// Computes the spectrum of a real signal, returning the `len / 2 + 1` bins from zero up to the
+/// Nyquist frequency. The remaining bins of a real signal are the conjugates of these and are
+/// left out.
+///
+/// # Panics
+/// Panics if the length of the signal is not a power of two.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fft, ApproxEq};
+/// use std::f64::consts::PI;
+/// // A sine of 3 cycles over 16 samples.
+/// let signal: Vec<f64> = (0..16).map(|i| (2.0 * PI * 3.0 * i as f64 / 16.0).sin()).collect();
+/// let spectrum = fft::forward_real(&signal);
+/// assert_eq!(spectrum.len(), 9);
+/// let magnitudes: Vec<f64> = spectrum.iter().map(|bin| bin.norm()).collect();
+/// assert!(magnitudes[3].approx_eq_eps(8.0, 1e-9));
+/// assert!(magnitudes.iter().enumerate().all(|(k, m)| k == 3 || *m < 1e-9));
+/// ```
+pub fn forward_real(signal: &[f64]) -> Vec<Complex> {
+    let mut buffer: Vec<Complex> = signal.iter().map(|&value| Complex::new(value, 0.0)).collect();
+    forward(&mut buffer);
+    buffer.truncate(signal.len() / 2 + 1);
+    buffer
+}
+
+/// Reconstructs a real signal of length `len` from the `len / 2 + 1` bins returned by
+/// `forward_real`.
+///
+/// # Panics
+/// Panics if `len` is not a power of two or the spectrum doesn't have `len / 2 + 1` bins.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fft, ApproxEq};
+/// let signal = [0.5, 1.0, -2.0, 3.0, 0.0, -1.0, 4.0, 2.5];
+/// let restored = fft::inverse_real(&fft::forward_real(&signal), signal.len());
+/// assert!(restored[..].approx_eq_eps(&signal[..], &[1e-12; 8][..]));
+/// ```
+pub fn inverse_real(spectrum: &[Complex], len: usize) -> Vec<f64> {
+    assert_eq!(spectrum.len(), len / 2 + 1, "the spectrum of a real signal of length {} has {} bins", len, len / 2 + 1);
+    let mut buffer = vec![Complex::ZERO; len];
+    for (k, value) in buffer.iter_mut().enumerate() {
+        *value = if k < spectrum.len() { spectrum[k] } else { spectrum[len - k].conj() };
+    }
+    inverse(&mut buffer);
+    buffer.iter().map(|value| value.re).collect()
+}
+
+/// The shared radix-2 transform; `sign` is the sign of the twiddle factor exponent, negative
+/// for the forward transform.
+fn transform(buffer: &mut [Complex], sign: f64) {
+    let len = buffer.len();
+    assert!(len.is_power_of_two(), "FFT length must be a power of two, got {}", len);
+    if len == 1 {
+        return;
+    }
+
+    // Reorder the samples by bit-reversed index, so the butterflies can run in place.
+    let bits = len.trailing_zeros();
+    for i in 0..len {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        // Twiddle factors are computed directly rather than by repeated multiplication, which
+        // would accumulate rounding errors on long buffers.
+        let angle = sign * 2.0 * PI / size as f64;
+        for chunk in buffer.chunks_mut(size) {
+            let (even, odd) = chunk.split_at_mut(size / 2);
+            for (k, (a, b)) in even.iter_mut().zip(odd.iter_mut()).enumerate() {
+                let t = Complex::from_polar(1.0, angle * k as f64) * *b;
+                *b = *a - t;
+                *a = *a + t;
+            }
+        }
+        size *= 2;
+    }
+}
@@ -0,0 +1,198 @@
+use math::vec2::Vec2;
+use math::mat2::Mat2;
+
+/// Variance given to the initial velocity and acceleration, which are unknown until a few
+/// measurements arrive.
+const UNKNOWN_VARIANCE: f64 = 1e6;
+
+/// The motion a `Kalman2D` expects between measurements. Deviations from it are absorbed by the
+/// process noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionModel {
+    /// The tracked point keeps its velocity; changes of velocity are noise. Suited to cursors
+    /// and touches.
+    ConstantVelocity,
+    /// The tracked point keeps its acceleration; changes of acceleration are noise. Follows
+    /// curving and braking motion more closely, at the cost of overshooting sudden stops.
+    ConstantAcceleration,
+}
+
+/// A Kalman filter tracking the position, velocity and, depending on the model, acceleration of
+/// a point from noisy position measurements, such as cursor or touch samples.
+///
+/// The noise is assumed to be the same along both axes and independent between them, which
+/// splits the filter into one small filter per axis. `process_noise` is the spectral density of
+/// the unmodeled changes (acceleration for the constant velocity model, jerk for the constant
+/// acceleration model): raising it follows the measurements more closely. `measurement_noise`
+/// is the variance of the measurements: raising it smooths more.
+///
+/// # Examples
+/// ```
+/// use candle::math::Vec2;
+/// use candle::math::filter::{Kalman2D, MotionModel};
+/// let mut filter = Kalman2D::new(MotionModel::ConstantVelocity, Vec2::new(0.0, 0.0), 1.0, 0.25);
+/// // A cursor moving right at 10 units per second, with jittery samples.
+/// for i in 1..=60 {
+///     let jitter = if i % 2 == 0 { 0.3 } else { -0.3 };
+///     filter.predict(1.0 / 60.0);
+///     filter.update(Vec2::new(i as f64 / 6.0, jitter));
+/// }
+/// assert!((filter.position() - Vec2::new(10.0, 0.0)).length() < 0.3);
+/// assert!((filter.velocity() - Vec2::new(10.0, 0.0)).length() < 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kalman2D {
+    model: MotionModel,
+    x: AxisFilter,
+    y: AxisFilter,
+    pub process_noise: f64,
+    pub measurement_noise: f64,
+}
+
+impl Kalman2D {
+
+    /// Allocates a new Kalman2D at `position`, with unknown velocity and acceleration.
+    pub fn new(model: MotionModel, position: Vec2, process_noise: f64, measurement_noise: f64) -> Kalman2D {
+        Kalman2D {
+            model,
+            x: AxisFilter::new(model, position.x, measurement_noise),
+            y: AxisFilter::new(model, position.y, measurement_noise),
+            process_noise,
+            measurement_noise,
+        }
+    }
+
+    /// Returns the motion model of the filter.
+    pub fn model(&self) -> MotionModel {
+        self.model
+    }
+
+    /// Advances the estimate by `dt` seconds following the motion model, growing its
+    /// uncertainty.
+    pub fn predict(&mut self, dt: f64) {
+        self.x.predict(self.model, dt, self.process_noise);
+        self.y.predict(self.model, dt, self.process_noise);
+    }
+
+    /// Corrects the estimate with a position measurement.
+    pub fn update(&mut self, measurement: Vec2) {
+        self.x.update(measurement.x, self.measurement_noise);
+        self.y.update(measurement.y, self.measurement_noise);
+    }
+
+    /// Returns the estimated position.
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x.state[0], self.y.state[0])
+    }
+
+    /// Returns the estimated velocity.
+    pub fn velocity(&self) -> Vec2 {
+        Vec2::new(self.x.state[1], self.y.state[1])
+    }
+
+    /// Returns the estimated acceleration, always zero with the constant velocity model.
+    pub fn acceleration(&self) -> Vec2 {
+        Vec2::new(self.x.state[2], self.y.state[2])
+    }
+
+    /// Returns the covariance of the estimated position. The axes are independent, so it is
+    /// diagonal.
+    pub fn position_covariance(&self) -> Mat2 {
+        Mat2::new(self.x.covariance[0][0], 0.0, 0.0, self.y.covariance[0][0])
+    }
+
+    /// Returns the covariance of the estimated velocity.
+    pub fn velocity_covariance(&self) -> Mat2 {
+        Mat2::new(self.x.covariance[1][1], 0.0, 0.0, self.y.covariance[1][1])
+    }
+
+    /// Returns the full covariance of the state along the x axis and along the y axis, ordered
+    /// position, velocity, acceleration.
+    pub fn axis_covariances(&self) -> ([[f64; 3]; 3], [[f64; 3]; 3]) {
+        (self.x.covariance, self.y.covariance)
+    }
+}
+
+/// The filter of one axis. The constant velocity model uses the same three element state with
+/// the acceleration pinned to zero.
+#[derive(Debug, Clone, PartialEq)]
+struct AxisFilter {
+    state: [f64; 3],
+    covariance: [[f64; 3]; 3],
+}
+
+impl AxisFilter {
+    fn new(model: MotionModel, position: f64, measurement_noise: f64) -> AxisFilter {
+        let acceleration_variance = match model {
+            MotionModel::ConstantVelocity => 0.0,
+            MotionModel::ConstantAcceleration => UNKNOWN_VARIANCE,
+        };
+        AxisFilter {
+            state: [position, 0.0, 0.0],
+            covariance: [
+                [measurement_noise, 0.0, 0.0],
+                [0.0, UNKNOWN_VARIANCE, 0.0],
+                [0.0, 0.0, acceleration_variance],
+            ],
+        }
+    }
+
+    fn predict(&mut self, model: MotionModel, dt: f64, process_noise: f64) {
+        let (dt2, dt3) = (dt * dt, dt * dt * dt);
+        let (transition, noise) = match model {
+            MotionModel::ConstantVelocity => (
+                [[1.0, dt, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]],
+                [[dt3 / 3.0, dt2 / 2.0, 0.0], [dt2 / 2.0, dt, 0.0], [0.0, 0.0, 0.0]],
+            ),
+            MotionModel::ConstantAcceleration => {
+                let (dt4, dt5) = (dt3 * dt, dt3 * dt2);
+                (
+                    [[1.0, dt, dt2 / 2.0], [0.0, 1.0, dt], [0.0, 0.0, 1.0]],
+                    [[dt5 / 20.0, dt4 / 8.0, dt3 / 6.0], [dt4 / 8.0, dt3 / 3.0, dt2 / 2.0], [dt3 / 6.0, dt2 / 2.0, dt]],
+                )
+            }
+        };
+
+        let mut state = [0.0; 3];
+        for (i, value) in state.iter_mut().enumerate() {
+            *value = (0..3).map(|k| transition[i][k] * self.state[k]).sum();
+        }
+        // P = F P Fᵀ + Q
+        let mut covariance = [[0.0; 3]; 3];
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let mut sum = 0.0;
+                for k in 0..3 {
+                    for l in 0..3 {
+                        sum += transition[i][k] * self.covariance[k][l] * transition[j][l];
+                    }
+                }
+                *value = sum + noise[i][j] * process_noise;
+            }
+        }
+        self.state = state;
+        self.covariance = covariance;
+    }
+
+    fn update(&mut self, measurement: f64, measurement_noise: f64) {
+        // The measurement observes the position only, so the gain is the first column of the
+        // covariance over the innovation variance.
+        let innovation = measurement - self.state[0];
+        let variance = self.covariance[0][0] + measurement_noise;
+        if variance <= 0.0 {
+            return;
+        }
+        let gain = [
+            self.covariance[0][0] / variance,
+            self.covariance[1][0] / variance,
+            self.covariance[2][0] / variance,
+        ];
+        let first_row = self.covariance[0];
+        for (i, gain) in gain.iter().enumerate() {
+            self.state[i] += gain * innovation;
+            for (value, first) in self.covariance[i].iter_mut().zip(first_row.iter()) {
+                *value -= gain * first;
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use math::vec2::Vec2;
+use math::mat2::{Mat2, solve2};
+use math::stats;
+
+/// A line fitted to a set of points, passing through `origin` along the unit `direction`.
+/// `rms` is the root mean square of the perpendicular distances from the points to the line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineFit {
+    pub origin: Vec2,
+    pub direction: Vec2,
+    pub rms: f64,
+}
+
+impl LineFit {
+
+    /// Returns the signed perpendicular distance from `point` to the line, positive on the left
+    /// of `direction`. This is the residual of the point.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        let offset = point - self.origin;
+        self.direction.x * offset.y - self.direction.y * offset.x
+    }
+}
+
+/// A circle fitted to a set of points. `rms` is the root mean square of the distances from the
+/// points to the circle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircleFit {
+    pub center: Vec2,
+    pub radius: f64,
+    pub rms: f64,
+}
+
+impl CircleFit {
+
+    /// Returns the signed distance from `point` to the circle, positive outside of it. This is
+    /// the residual of the point.
+    pub fn distance(&self, point: &Vec2) -> f64 {
+        (point - self.center).length() - self.radius
+    }
+
+    fn with_rms(center: Vec2, radius: f64, points: &[Vec2]) -> CircleFit {
+        let mut fit = CircleFit { center, radius, rms: 0.0 };
+        fit.rms = rms(points.iter().map(|point| fit.distance(point)));
+        fit
+    }
+}
+
+/// Fits a line to the points with total least squares, minimizing the perpendicular distances
+/// (unlike ordinary regression, which minimizes vertical distances and fails on steep lines).
+/// The line passes through the centroid along the principal axis of the points. Returns `None`
+/// for fewer than two distinct points.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2, ApproxEq};
+/// let points = [Vec2::new(1.0, 0.0), Vec2::new(1.1, 1.0), Vec2::new(0.9, 2.0), Vec2::new(1.0, 3.0)];
+/// let line = fit::line(&points).unwrap();
+/// assert!(line.origin.approx_eq(Vec2::new(1.0, 1.5)));
+/// assert!(line.direction.y.abs().approx_eq_eps(1.0, 1e-2));
+/// assert!(line.rms < 0.1);
+/// ```
+pub fn line(points: &[Vec2]) -> Option<LineFit> {
+    if points.len() < 2 {
+        return None;
+    }
+    let pca = stats::pca(points)?;
+    if pca.variances.0 == 0.0 {
+        return None;
+    }
+    let mut fit = LineFit { origin: pca.mean, direction: pca.axes.0, rms: 0.0 };
+    fit.rms = rms(points.iter().map(|point| fit.distance(point)));
+    Some(fit)
+}
+
+/// Fits a circle to the points by algebraic least squares (the Kåsa fit), which minimizes the
+/// error of the circle equation rather than the distances to the circle. It is fast and has a
+/// closed form, but is biased towards smaller circles when the points only cover a short arc.
+/// Returns `None` for fewer than three points or collinear points.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2, ApproxEq};
+/// let points = [Vec2::new(3.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(-1.0, 0.0), Vec2::new(1.0, -2.0)];
+/// let circle = fit::circle_algebraic(&points).unwrap();
+/// assert!(circle.center.approx_eq(Vec2::new(1.0, 0.0)));
+/// assert!(circle.radius.approx_eq(2.0));
+/// assert!(circle.rms.approx_eq(0.0));
+/// ```
+pub fn circle_algebraic(points: &[Vec2]) -> Option<CircleFit> {
+    if points.len() < 3 {
+        return None;
+    }
+    // Working relative to the centroid turns the normal equations into a 2x2 system.
+    let centroid = stats::mean(points)?;
+    let (mut suu, mut suv, mut svv) = (0.0, 0.0, 0.0);
+    let (mut suuu, mut svvv, mut suvv, mut svuu) = (0.0, 0.0, 0.0, 0.0);
+    for point in points {
+        let (u, v) = (point.x - centroid.x, point.y - centroid.y);
+        suu += u * u;
+        suv += u * v;
+        svv += v * v;
+        suuu += u * u * u;
+        svvv += v * v * v;
+        suvv += u * v * v;
+        svuu += v * u * u;
+    }
+    let system = Mat2::new(suu, suv, suv, svv);
+    let offset = solve2(&system, &Vec2::new((suuu + suvv) / 2.0, (svvv + svuu) / 2.0))?;
+    let radius = (offset.dot(&offset) + (suu + svv) / points.len() as f64).sqrt();
+    Some(CircleFit::with_rms(centroid + offset, radius, points))
+}
+
+/// Fits a circle to the points by geometric least squares, minimizing the distances from the
+/// points to the circle. Starts from the algebraic fit and refines it iteratively, so it stays
+/// accurate on short arcs where the algebraic fit is biased. Returns `None` for fewer than three
+/// points or collinear points.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2, ApproxEq};
+/// // Noisy points on a quarter of the circle of radius 10 around the origin.
+/// let points: Vec<Vec2> = (0..10).map(|i| {
+///     let angle = i as f64 * 0.17;
+///     let radius = if i % 2 == 0 { 10.05 } else { 9.95 };
+///     Vec2::new(angle.cos() * radius, angle.sin() * radius)
+/// }).collect();
+/// let geometric = fit::circle_geometric(&points).unwrap();
+/// let algebraic = fit::circle_algebraic(&points).unwrap();
+/// assert!(geometric.radius.approx_eq_eps(10.0, 0.5));
+/// assert!(geometric.rms <= algebraic.rms);
+/// ```
+pub fn circle_geometric(points: &[Vec2]) -> Option<CircleFit> {
+    let initial = circle_algebraic(points)?;
+    let mut center = initial.center;
+    let mut radius = initial.radius;
+    let count = points.len() as f64;
+    let centroid = stats::mean(points)?;
+    // Fixed point iteration: the best radius for a center is the mean distance to it, and the
+    // best center for a radius pulls the centroid back along the mean direction to the points.
+    for _ in 0..200 {
+        let mut total_distance = 0.0;
+        let mut total_direction = Vec2::new(0.0, 0.0);
+        for point in points {
+            let offset = center - point;
+            let distance = offset.length();
+            if distance == 0.0 {
+                continue;
+            }
+            total_distance += distance;
+            total_direction = total_direction + offset / distance;
+        }
+        radius = total_distance / count;
+        let next = centroid + total_direction / count * radius;
+        let step = (next - center).length();
+        center = next;
+        if step <= 1e-12 * radius.max(1.0) {
+            break;
+        }
+    }
+    let fit = CircleFit::with_rms(center, radius, points);
+    if fit.rms.is_nan() || fit.rms > initial.rms {
+        return Some(initial);
+    }
+    Some(fit)
+}
+
+fn rms<I: Iterator<Item = f64>>(residuals: I) -> f64 {
+    let (sum, count) = residuals.fold((0.0, 0), |(sum, count), r| (sum + r * r, count + 1));
+    if count == 0 {
+        return 0.0;
+    }
+    (sum / count as f64).sqrt()
+}
+
+/// The result of `ransac`: the model refitted to its consensus set and the indices of the
+/// points in that set, in ascending order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RansacFit<M> {
+    pub model: M,
+    pub inliers: Vec<usize>,
+}
+
+/// Fits a model to points contaminated by outliers with RANSAC (random sample consensus). Each
+/// of the `iterations` fits a candidate model with `fit` to `sample_size` randomly chosen
+/// points and counts the points that `is_inlier` accepts for it. The candidate with the most
+/// inliers is then refitted to all of them.
+///
+/// `fit` returns `None` for degenerate samples, such as coincident points, which are skipped.
+/// The samples are drawn from a generator seeded with `seed`, so the result is reproducible.
+/// Returns `None` if there are fewer points than `sample_size` or no sample could be fitted.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2};
+/// // Points on the parabola y = x² with two outliers.
+/// let mut points: Vec<Vec2> = (1..=10).map(|x| Vec2::new(x as f64, (x * x) as f64)).collect();
+/// points.push(Vec2::new(2.0, 20.0));
+/// points.push(Vec2::new(3.0, -4.0));
+/// let parabola = |sample: &[Vec2]| -> Option<f64> {
+///     let curvature = sample.iter().map(|p| p.y / (p.x * p.x)).sum::<f64>() / sample.len() as f64;
+///     if curvature.is_finite() { Some(curvature) } else { None }
+/// };
+/// let result = fit::ransac(&points, 1, 20, 7, parabola, |a, p| (a * p.x * p.x - p.y).abs() < 0.1).unwrap();
+/// assert_eq!(result.model, 1.0);
+/// assert_eq!(result.inliers.len(), 10);
+/// ```
+pub fn ransac<T, M, F, I>(points: &[T], sample_size: usize, iterations: usize, seed: u64, fit: F, is_inlier: I) -> Option<RansacFit<M>>
+    where T: Clone, F: Fn(&[T]) -> Option<M>, I: Fn(&M, &T) -> bool
+{
+    if sample_size == 0 || points.len() < sample_size {
+        return None;
+    }
+    let mut random = SplitMix64(seed);
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    let mut sample = Vec::with_capacity(sample_size);
+    let mut best: Option<Vec<usize>> = None;
+    for _ in 0..iterations {
+        // Partial Fisher-Yates shuffle: the first `sample_size` indices become the sample.
+        sample.clear();
+        for i in 0..sample_size {
+            let j = i + (random.next() % (points.len() - i) as u64) as usize;
+            indices.swap(i, j);
+            sample.push(points[indices[i]].clone());
+        }
+        let model = match fit(&sample) {
+            Some(model) => model,
+            None => continue,
+        };
+        let inliers: Vec<usize> = (0..points.len()).filter(|&i| is_inlier(&model, &points[i])).collect();
+        if best.as_ref().is_none_or(|best| inliers.len() > best.len()) {
+            best = Some(inliers);
+        }
+    }
+
+    let inliers = best?;
+    let consensus: Vec<T> = inliers.iter().map(|&i| points[i].clone()).collect();
+    let model = fit(&consensus)?;
+    Some(RansacFit { model, inliers })
+}
+
+/// Fits a line to the points with `ransac`, counting as inliers the points within `threshold`
+/// of the line. Each candidate is built from two points and the result is refitted with `line`.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2, ApproxEq};
+/// let mut points: Vec<Vec2> = (0..20).map(|i| Vec2::new(i as f64, 2.0 * i as f64 + 1.0)).collect();
+/// points.extend(vec![Vec2::new(3.0, 30.0), Vec2::new(15.0, -2.0), Vec2::new(8.0, 8.0)]);
+/// let result = fit::ransac_line(&points, 0.1, 50, 1).unwrap();
+/// assert_eq!(result.inliers, (0..20).collect::<Vec<usize>>());
+/// assert!(result.model.rms.approx_eq_eps(0.0, 1e-9));
+/// ```
+pub fn ransac_line(points: &[Vec2], threshold: f64, iterations: usize, seed: u64) -> Option<RansacFit<LineFit>> {
+    ransac(points, 2, iterations, seed, line, |model, point| model.distance(point).abs() <= threshold)
+}
+
+/// Fits a circle to the points with `ransac`, counting as inliers the points within
+/// `threshold` of the circle. Each candidate is the circle through three points and the result
+/// is refitted with `circle_geometric`.
+///
+/// # Examples
+/// ```
+/// use candle::math::{fit, Vec2, ApproxEq};
+/// let mut points: Vec<Vec2> = (0..16).map(|i| {
+///     let angle = i as f64 * 0.4;
+///     Vec2::new(5.0 + angle.cos() * 3.0, -2.0 + angle.sin() * 3.0)
+/// }).collect();
+/// points.extend(vec![Vec2::new(0.0, 0.0), Vec2::new(5.0, -2.0), Vec2::new(9.0, 9.0)]);
+/// let result = fit::ransac_circle(&points, 0.05, 100, 3).unwrap();
+/// assert_eq!(result.inliers.len(), 16);
+/// assert!(result.model.center.approx_eq_eps(Vec2::new(5.0, -2.0), Vec2::new(1e-6, 1e-6)));
+/// assert!(result.model.radius.approx_eq_eps(3.0, 1e-6));
+/// ```
+pub fn ransac_circle(points: &[Vec2], threshold: f64, iterations: usize, seed: u64) -> Option<RansacFit<CircleFit>> {
+    ransac(points, 3, iterations, seed, circle_geometric, |model, point| model.distance(point).abs() <= threshold)
+}
+
+/// The SplitMix64 generator, small and good enough to draw RANSAC samples.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,334 @@
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, SubAssign, MulAssign, DivAssign};
+use math::vec2::Vec2;
+
+const FRACTIONAL_BITS: u32 = 32;
+
+/// A signed Q32.32 fixed-point number: 32 bits of integer part and 32 bits of fraction stored in
+/// an `i64`.
+///
+/// All operations are plain integer arithmetic, so results are bit-for-bit identical on every
+/// platform, which floats can't guarantee. This is what lockstep simulations need. Addition and
+/// subtraction wrap on overflow in both debug and release builds, and multiplication and division
+/// truncate towards negative infinity, so no build profile can diverge from another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed {
+    raw: i64,
+}
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed { raw: 0 };
+    pub const ONE: Fixed = Fixed { raw: 1 << FRACTIONAL_BITS };
+
+    /// Allocates a new Fixed from its raw Q32.32 representation.
+    pub fn from_raw(raw: i64) -> Fixed {
+        Fixed { raw }
+    }
+
+    /// Returns the raw Q32.32 representation, which is what should be sent over the network or
+    /// hashed to detect desyncs.
+    pub fn raw(&self) -> i64 {
+        self.raw
+    }
+
+    /// Allocates a new Fixed with the given integer value.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// assert_eq!(Fixed::from_int(3).to_f64(), 3.0);
+    /// assert_eq!(Fixed::from_int(-2).to_f64(), -2.0);
+    /// ```
+    pub fn from_int(value: i32) -> Fixed {
+        Fixed { raw: (value as i64) << FRACTIONAL_BITS }
+    }
+
+    /// Converts the float to the nearest Fixed. Only conversions of the inputs should go through
+    /// floats; the simulation itself must stay in fixed point to remain deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// assert_eq!(Fixed::from_f64(1.5).to_f64(), 1.5);
+    /// assert_eq!(Fixed::from_f64(-0.25), -Fixed::ONE / Fixed::from_int(4));
+    /// ```
+    pub fn from_f64(value: f64) -> Fixed {
+        Fixed { raw: (value * (1u64 << FRACTIONAL_BITS) as f64).round() as i64 }
+    }
+
+    /// Converts the value to a float, for rendering or debugging.
+    pub fn to_f64(&self) -> f64 {
+        self.raw as f64 / (1u64 << FRACTIONAL_BITS) as f64
+    }
+
+    /// Returns the largest integer value lower than or equal to the number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// assert_eq!(Fixed::from_f64(2.75).floor(), Fixed::from_int(2));
+    /// assert_eq!(Fixed::from_f64(-2.25).floor(), Fixed::from_int(-3));
+    /// ```
+    pub fn floor(&self) -> Fixed {
+        Fixed { raw: self.raw & !((1 << FRACTIONAL_BITS) - 1) }
+    }
+
+    /// Returns the absolute value of the number.
+    pub fn abs(&self) -> Fixed {
+        Fixed { raw: self.raw.wrapping_abs() }
+    }
+
+    /// Returns the square root of the number, computed with integer arithmetic only. Negative
+    /// numbers return zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// assert_eq!(Fixed::from_int(9).sqrt(), Fixed::from_int(3));
+    /// assert_eq!(Fixed::from_f64(2.25).sqrt(), Fixed::from_f64(1.5));
+    /// assert_eq!(Fixed::from_int(-4).sqrt(), Fixed::ZERO);
+    /// ```
+    pub fn sqrt(&self) -> Fixed {
+        if self.raw <= 0 {
+            return Fixed::ZERO;
+        }
+        // sqrt(raw / 2^32) * 2^32 == sqrt(raw * 2^32)
+        Fixed { raw: isqrt((self.raw as u128) << FRACTIONAL_BITS) as i64 }
+    }
+}
+
+/// Integer square root rounded down, by Newton's method.
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+impl Add<Fixed> for Fixed {
+    type Output = Fixed;
+
+    /// Adds the two numbers, wrapping on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// let sum = Fixed::from_f64(1.25) + Fixed::from_f64(2.5);
+    /// assert_eq!(sum.to_f64(), 3.75);
+    /// ```
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed { raw: self.raw.wrapping_add(other.raw) }
+    }
+}
+
+impl Sub<Fixed> for Fixed {
+    type Output = Fixed;
+
+    /// Substracts the two numbers, wrapping on overflow.
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed { raw: self.raw.wrapping_sub(other.raw) }
+    }
+}
+
+impl Mul<Fixed> for Fixed {
+    type Output = Fixed;
+
+    /// Multiplies the two numbers, truncating the result to 32 fractional bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// let product = Fixed::from_f64(1.5) * Fixed::from_f64(-2.5);
+    /// assert_eq!(product.to_f64(), -3.75);
+    /// ```
+    fn mul(self, other: Fixed) -> Fixed {
+        Fixed { raw: ((self.raw as i128 * other.raw as i128) >> FRACTIONAL_BITS) as i64 }
+    }
+}
+
+impl Div<Fixed> for Fixed {
+    type Output = Fixed;
+
+    /// Divides the two numbers, truncating the result to 32 fractional bits. Panics if `other`
+    /// is zero, like integer division.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Fixed;
+    /// let quotient = Fixed::from_int(3) / Fixed::from_int(4);
+    /// assert_eq!(quotient.to_f64(), 0.75);
+    /// ```
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Fixed) -> Fixed {
+        let numerator = (self.raw as i128) << FRACTIONAL_BITS;
+        let denominator = other.raw as i128;
+        let mut quotient = numerator / denominator;
+        if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+            quotient -= 1;
+        }
+        Fixed { raw: quotient as i64 }
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed { raw: self.raw.wrapping_neg() }
+    }
+}
+
+impl AddAssign<Fixed> for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign<Fixed> for Fixed {
+    fn sub_assign(&mut self, other: Fixed) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<Fixed> for Fixed {
+    fn mul_assign(&mut self, other: Fixed) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<Fixed> for Fixed {
+    fn div_assign(&mut self, other: Fixed) {
+        *self = *self / other;
+    }
+}
+
+/// A Vec2 variant with Fixed coordinates, for deterministic positions and velocities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+
+    /// Allocates a new FixedVec2 with the given `x` and `y`.
+    pub fn new(x: Fixed, y: Fixed) -> FixedVec2 {
+        FixedVec2 { x, y }
+    }
+
+    /// Converts the Vec2 coordinates to the nearest fixed-point values.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, FixedVec2};
+    /// let vec = FixedVec2::from_vec2(&Vec2::new(1.5, -2.0));
+    /// assert_eq!(vec.to_vec2(), Vec2::new(1.5, -2.0));
+    /// ```
+    pub fn from_vec2(vec: &Vec2) -> FixedVec2 {
+        FixedVec2::new(Fixed::from_f64(vec.x), Fixed::from_f64(vec.y))
+    }
+
+    /// Converts the coordinates to a Vec2, for rendering or debugging.
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.x.to_f64(), self.y.to_f64())
+    }
+
+    /// Returns the dot product between two vectors.
+    pub fn dot(&self, other: &FixedVec2) -> Fixed {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Calculates the squared length of the vector, which avoids the square root.
+    pub fn length_squared(&self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// Calculates the length of the vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Fixed, FixedVec2};
+    /// let vec = FixedVec2::new(Fixed::from_int(3), Fixed::from_int(4));
+    /// assert_eq!(vec.length(), Fixed::from_int(5));
+    /// ```
+    pub fn length(&self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+}
+
+impl Add<FixedVec2> for FixedVec2 {
+    type Output = FixedVec2;
+
+    /// Adds the coordinates of two vectors, returning a new result vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Fixed, FixedVec2};
+    /// let position = FixedVec2::new(Fixed::from_int(1), Fixed::from_int(2));
+    /// let velocity = FixedVec2::new(Fixed::from_f64(0.5), Fixed::from_f64(-0.25));
+    /// let moved = position + velocity;
+    /// assert_eq!(moved.x.to_f64(), 1.5);
+    /// assert_eq!(moved.y.to_f64(), 1.75);
+    /// ```
+    fn add(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub<FixedVec2> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn sub(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    /// Multiplies each coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Fixed, FixedVec2};
+    /// let velocity = FixedVec2::new(Fixed::from_int(2), Fixed::from_int(-4));
+    /// let step = velocity * Fixed::from_f64(0.5);
+    /// assert_eq!(step, FixedVec2::new(Fixed::from_int(1), Fixed::from_int(-2)));
+    /// ```
+    fn mul(self, value: Fixed) -> FixedVec2 {
+        FixedVec2::new(self.x * value, self.y * value)
+    }
+}
+
+impl Div<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn div(self, value: Fixed) -> FixedVec2 {
+        FixedVec2::new(self.x / value, self.y / value)
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn neg(self) -> FixedVec2 {
+        FixedVec2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign<FixedVec2> for FixedVec2 {
+    fn add_assign(&mut self, other: FixedVec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign<FixedVec2> for FixedVec2 {
+    fn sub_assign(&mut self, other: FixedVec2) {
+        *self = *self - other;
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::{Index, IndexMut};
+
+/// A dense 2D array of values stored row by row, such as a heightmap or a scalar field sampled
+/// on a regular lattice. Cells are addressed by `(x, y)`, with `x` the column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+
+    /// Allocates a new Grid of `width` by `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Allocates a new Grid by evaluating `f(x, y)` for every cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Grid;
+    /// let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+    /// assert_eq!(grid[(2, 1)], 12);
+    /// ```
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+        where F: FnMut(usize, usize) -> T
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Grid { width, height, cells }
+    }
+
+    /// Allocates a new Grid from cells stored row by row. Returns `None` if the number of cells
+    /// is not `width * height`.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if cells.len() != width * height {
+            return None;
+        }
+        Some(Grid { width, height, cells })
+    }
+}
+
+impl<T> Grid<T> {
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is out of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get(y * self.width + x)
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or `None` if it is out of the grid.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get_mut(y * self.width + x)
+    }
+
+    /// Returns the cells row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Returns the cells row by row, mutably.
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Returns a new grid of the same size with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+        where F: FnMut(&T) -> U
+    {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Returns the cell at `(x, y)`, panicking if it is out of the grid.
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "cell ({}, {}) is out of a {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    /// Returns the cell at `(x, y)` mutably, panicking if it is out of the grid.
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "cell ({}, {}) is out of a {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
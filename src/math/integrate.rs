@@ -0,0 +1,71 @@
+use std::ops::{Add, Mul};
+
+/// Advances `state` from time `t` by `dt` with one explicit (forward) Euler step, where
+/// `derivative(t, state)` returns the rate of change of the state. Cheap but only first order
+/// accurate, and it gains energy on oscillating systems such as springs and orbits.
+///
+/// # Examples
+/// ```
+/// use candle::math::integrate;
+/// // dy/dt = -y, y(0) = 1
+/// let y = integrate::euler(1.0, 0.0, 0.1, |_, y: f64| -y);
+/// assert_eq!(y, 0.9);
+/// ```
+pub fn euler<S, F>(state: S, t: f64, dt: f64, derivative: F) -> S
+    where S: Copy + Add<Output = S> + Mul<f64, Output = S>, F: Fn(f64, S) -> S
+{
+    state + derivative(t, state) * dt
+}
+
+/// Advances a second order system, such as a particle, from time `t` by `dt` with one
+/// semi-implicit (symplectic) Euler step: the velocity is updated first from
+/// `acceleration(t, position, velocity)`, then the position from the new velocity. It costs the
+/// same as explicit Euler but keeps oscillations stable, which makes it the usual choice for
+/// game physics. Returns the new position and velocity.
+///
+/// # Examples
+/// ```
+/// use candle::math::{integrate, Vec2};
+/// let gravity = Vec2::new(0.0, -10.0);
+/// let mut position = Vec2::new(0.0, 0.0);
+/// let mut velocity = Vec2::new(1.0, 10.0);
+/// for _ in 0..10 {
+///     let (p, v) = integrate::semi_implicit_euler(position, velocity, 0.0, 0.1, |_, _, _| gravity);
+///     position = p;
+///     velocity = v;
+/// }
+/// assert!(velocity.y.abs() < 1e-12);
+/// assert!(position.y > 4.0 && position.y < 5.0);
+/// ```
+pub fn semi_implicit_euler<S, F>(position: S, velocity: S, t: f64, dt: f64, acceleration: F) -> (S, S)
+    where S: Copy + Add<Output = S> + Mul<f64, Output = S>, F: Fn(f64, S, S) -> S
+{
+    let velocity = velocity + acceleration(t, position, velocity) * dt;
+    (position + velocity * dt, velocity)
+}
+
+/// Advances `state` from time `t` by `dt` with one step of the classic fourth order Runge-Kutta
+/// method, where `derivative(t, state)` returns the rate of change of the state. It evaluates
+/// the derivative four times per step but is accurate enough for large steps, which suits
+/// projectile prediction and offline simulation.
+///
+/// # Examples
+/// ```
+/// use candle::math::{integrate, ApproxEq};
+/// // dy/dt = -y, y(0) = 1, so y(1) = e⁻¹
+/// let mut y = 1.0;
+/// for step in 0..10 {
+///     y = integrate::rk4(y, step as f64 * 0.1, 0.1, |_, y: f64| -y);
+/// }
+/// assert!(y.approx_eq_eps((-1.0f64).exp(), 1e-6));
+/// ```
+pub fn rk4<S, F>(state: S, t: f64, dt: f64, derivative: F) -> S
+    where S: Copy + Add<Output = S> + Mul<f64, Output = S>, F: Fn(f64, S) -> S
+{
+    let half = dt / 2.0;
+    let k1 = derivative(t, state);
+    let k2 = derivative(t + half, state + k1 * half);
+    let k3 = derivative(t + half, state + k2 * half);
+    let k4 = derivative(t + dt, state + k3 * dt);
+    state + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (dt / 6.0)
+}
@@ -0,0 +1,226 @@
+use std::ops::{Add, Sub, Mul};
+use math::vec2::Vec2;
+use math::approx_eq::ApproxEq;
+
+/// A 2x2 matrix of f64, with fields named by row and column, so `m01` is the first row, second
+/// column. Matrices multiply column vectors: `m * v`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat2 {
+    pub m00: f64,
+    pub m01: f64,
+    pub m10: f64,
+    pub m11: f64,
+}
+
+impl Mat2 {
+    pub const ZERO: Mat2 = Mat2 { m00: 0.0, m01: 0.0, m10: 0.0, m11: 0.0 };
+    pub const IDENTITY: Mat2 = Mat2 { m00: 1.0, m01: 0.0, m10: 0.0, m11: 1.0 };
+
+    /// Allocates a new Mat2 from its elements in row-major order.
+    pub fn new(m00: f64, m01: f64, m10: f64, m11: f64) -> Mat2 {
+        Mat2 { m00, m01, m10, m11 }
+    }
+
+    /// Allocates a new Mat2 with the given rows.
+    pub fn from_rows(row0: &Vec2, row1: &Vec2) -> Mat2 {
+        Mat2::new(row0.x, row0.y, row1.x, row1.y)
+    }
+
+    /// Allocates a new Mat2 with the given columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat2, Vec2};
+    /// let m = Mat2::from_cols(&Vec2::new(1.0, 2.0), &Vec2::new(3.0, 4.0));
+    /// assert_eq!(m, Mat2::new(1.0, 3.0, 2.0, 4.0));
+    /// ```
+    pub fn from_cols(col0: &Vec2, col1: &Vec2) -> Mat2 {
+        Mat2::new(col0.x, col1.x, col0.y, col1.y)
+    }
+
+    /// Returns the row at `index`, 0 or 1.
+    pub fn row(&self, index: usize) -> Vec2 {
+        match index {
+            0 => Vec2::new(self.m00, self.m01),
+            1 => Vec2::new(self.m10, self.m11),
+            _ => panic!("Mat2 row index out of bounds: {}", index),
+        }
+    }
+
+    /// Returns the column at `index`, 0 or 1.
+    pub fn col(&self, index: usize) -> Vec2 {
+        match index {
+            0 => Vec2::new(self.m00, self.m10),
+            1 => Vec2::new(self.m01, self.m11),
+            _ => panic!("Mat2 column index out of bounds: {}", index),
+        }
+    }
+
+    /// Returns the determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        self.m00 * self.m11 - self.m01 * self.m10
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transpose(&self) -> Mat2 {
+        Mat2::new(self.m00, self.m10, self.m01, self.m11)
+    }
+
+    /// Returns the inverse of the matrix, or `None` if it is singular, as decided by
+    /// `is_singular`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Mat2;
+    /// let m = Mat2::new(2.0, 1.0, 1.0, 1.0);
+    /// assert_eq!(m.inverse(), Some(Mat2::new(1.0, -1.0, -1.0, 2.0)));
+    /// assert_eq!(m * m.inverse().unwrap(), Mat2::IDENTITY);
+    /// assert_eq!(Mat2::new(1.0, 2.0, 2.0, 4.0).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Mat2> {
+        if self.is_singular() {
+            return None;
+        }
+        let det = self.determinant();
+        Some(Mat2::new(self.m11 / det, -self.m01 / det, -self.m10 / det, self.m00 / det))
+    }
+
+    /// Returns true if the matrix has no inverse, or is so close to singular that its inverse
+    /// would be dominated by rounding errors: the determinant is negligible next to the product
+    /// of the row lengths.
+    pub fn is_singular(&self) -> bool {
+        let det = self.determinant();
+        let scale = self.row(0).length() * self.row(1).length();
+        det == 0.0 || !det.is_finite() || det.abs() <= scale * f64::EPSILON * 4.0
+    }
+
+    /// Returns the eigenvalues of a symmetric matrix, largest first, and their unit
+    /// eigenvectors. The eigenvectors are perpendicular, the second one being the first rotated
+    /// counterclockwise by 90 degrees. For a covariance matrix, they are the principal axes of
+    /// the point cloud, and the eigenvalues the variance along each axis.
+    ///
+    /// Only the diagonal and the average of the two off-diagonal elements are read, so the
+    /// result is that of the nearest symmetric matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat2, Vec2, ApproxEq};
+    /// let m = Mat2::new(2.0, 1.0, 1.0, 2.0);
+    /// let ((major, minor), (axis, normal)) = m.symmetric_eigen();
+    /// assert!(major.approx_eq(3.0));
+    /// assert!(minor.approx_eq(1.0));
+    /// let diagonal = Vec2::new(1.0, 1.0).normalized();
+    /// assert!(axis.approx_eq(diagonal));
+    /// assert!((m * normal).approx_eq(normal * minor));
+    /// ```
+    pub fn symmetric_eigen(&self) -> ((f64, f64), (Vec2, Vec2)) {
+        let off_diagonal = (self.m01 + self.m10) / 2.0;
+        let mean = (self.m00 + self.m11) / 2.0;
+        let half_difference = (self.m00 - self.m11) / 2.0;
+        let radius = half_difference.hypot(off_diagonal);
+        // The major axis is at half the angle of (m00 - m11, 2 m01), which stays accurate when
+        // the eigenvalues are nearly equal.
+        let (sin, cos) = (0.5 * off_diagonal.atan2(half_difference)).sin_cos();
+        ((mean + radius, mean - radius), (Vec2::new(cos, sin), Vec2::new(-sin, cos)))
+    }
+}
+
+/// Solves the linear system `m * x = b` for `x`, with Cramer's rule. Returns `None` if the
+/// matrix is singular, as decided by `Mat2::is_singular`, which happens when the two equations
+/// are parallel lines.
+///
+/// # Examples
+/// ```
+/// use candle::math::{self, Mat2, Vec2, ApproxEq};
+/// // 2x + y = 5, x - y = 1
+/// let m = Mat2::new(2.0, 1.0, 1.0, -1.0);
+/// let x = math::solve2(&m, &Vec2::new(5.0, 1.0)).unwrap();
+/// assert!(x.approx_eq(Vec2::new(2.0, 1.0)));
+///
+/// assert_eq!(math::solve2(&Mat2::new(1.0, 1.0, 2.0, 2.0), &Vec2::new(1.0, 2.0)), None);
+/// ```
+pub fn solve2(m: &Mat2, b: &Vec2) -> Option<Vec2> {
+    if m.is_singular() {
+        return None;
+    }
+    let det = m.determinant();
+    Some(Vec2::new((b.x * m.m11 - m.m01 * b.y) / det, (m.m00 * b.y - b.x * m.m10) / det))
+}
+
+impl Add<Mat2> for Mat2 {
+    type Output = Mat2;
+
+    fn add(self, other: Mat2) -> Mat2 {
+        Mat2::new(self.m00 + other.m00, self.m01 + other.m01, self.m10 + other.m10, self.m11 + other.m11)
+    }
+}
+
+impl Sub<Mat2> for Mat2 {
+    type Output = Mat2;
+
+    fn sub(self, other: Mat2) -> Mat2 {
+        Mat2::new(self.m00 - other.m00, self.m01 - other.m01, self.m10 - other.m10, self.m11 - other.m11)
+    }
+}
+
+impl Mul<Mat2> for Mat2 {
+    type Output = Mat2;
+
+    /// Multiplies the two matrices. The result applies `other` first, then `self`.
+    fn mul(self, other: Mat2) -> Mat2 {
+        Mat2::new(
+            self.m00 * other.m00 + self.m01 * other.m10,
+            self.m00 * other.m01 + self.m01 * other.m11,
+            self.m10 * other.m00 + self.m11 * other.m10,
+            self.m10 * other.m01 + self.m11 * other.m11,
+        )
+    }
+}
+
+impl Mul<Vec2> for Mat2 {
+    type Output = Vec2;
+
+    /// Transforms the column vector by the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat2, Vec2};
+    /// let m = Mat2::new(1.0, 2.0, 3.0, 4.0);
+    /// assert_eq!(m * Vec2::new(1.0, 1.0), Vec2::new(3.0, 7.0));
+    /// ```
+    fn mul(self, vec: Vec2) -> Vec2 {
+        Vec2::new(self.m00 * vec.x + self.m01 * vec.y, self.m10 * vec.x + self.m11 * vec.y)
+    }
+}
+
+impl Mul<f64> for Mat2 {
+    type Output = Mat2;
+
+    fn mul(self, value: f64) -> Mat2 {
+        Mat2::new(self.m00 * value, self.m01 * value, self.m10 * value, self.m11 * value)
+    }
+}
+
+impl ApproxEq<Mat2> for Mat2 {
+    fn approx_eq_eps(self, other: Mat2, eps: Mat2) -> bool {
+        self.m00.approx_eq_eps(other.m00, eps.m00) && self.m01.approx_eq_eps(other.m01, eps.m01) &&
+            self.m10.approx_eq_eps(other.m10, eps.m10) && self.m11.approx_eq_eps(other.m11, eps.m11)
+    }
+
+    /// Returns true if every element is approximately equal, with the combined absolute and
+    /// relative EPSILON tolerance.
+    fn approx_eq(self, other: Mat2) -> bool {
+        self.m00.approx_eq(other.m00) && self.m01.approx_eq(other.m01) &&
+            self.m10.approx_eq(other.m10) && self.m11.approx_eq(other.m11)
+    }
+
+    fn approx_eq_rel(self, other: Mat2, max_rel: f64) -> bool {
+        self.m00.approx_eq_rel(other.m00, max_rel) && self.m01.approx_eq_rel(other.m01, max_rel) &&
+            self.m10.approx_eq_rel(other.m10, max_rel) && self.m11.approx_eq_rel(other.m11, max_rel)
+    }
+
+    fn approx_eq_ulps(self, other: Mat2, max_ulps: u64) -> bool {
+        self.m00.approx_eq_ulps(other.m00, max_ulps) && self.m01.approx_eq_ulps(other.m01, max_ulps) &&
+            self.m10.approx_eq_ulps(other.m10, max_ulps) && self.m11.approx_eq_ulps(other.m11, max_ulps)
+    }
+}
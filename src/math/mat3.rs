@@ -0,0 +1,206 @@
+use std::ops::{Add, Sub, Mul};
+use math::vec3::Vec3;
+use math::approx_eq::ApproxEq;
+
+/// A 3x3 matrix of f64, the counterpart of `Mat2` for `Vec3`, with fields named by row and
+/// column, so `m12` is the second row, third column. Matrices multiply column vectors: `m * v`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3 {
+    pub m00: f64,
+    pub m01: f64,
+    pub m02: f64,
+    pub m10: f64,
+    pub m11: f64,
+    pub m12: f64,
+    pub m20: f64,
+    pub m21: f64,
+    pub m22: f64,
+}
+
+impl Mat3 {
+    pub const ZERO: Mat3 = Mat3 { m00: 0.0, m01: 0.0, m02: 0.0, m10: 0.0, m11: 0.0, m12: 0.0, m20: 0.0, m21: 0.0, m22: 0.0 };
+    pub const IDENTITY: Mat3 = Mat3 { m00: 1.0, m01: 0.0, m02: 0.0, m10: 0.0, m11: 1.0, m12: 0.0, m20: 0.0, m21: 0.0, m22: 1.0 };
+
+    /// Allocates a new Mat3 from its elements, row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat3, Vec3};
+    /// let m = Mat3::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+    /// assert_eq!(m.m12, 6.0);
+    /// assert_eq!(m.col(0), Vec3::new(1.0, 4.0, 7.0));
+    /// ```
+    pub fn new(rows: [[f64; 3]; 3]) -> Mat3 {
+        let [[m00, m01, m02], [m10, m11, m12], [m20, m21, m22]] = rows;
+        Mat3 { m00, m01, m02, m10, m11, m12, m20, m21, m22 }
+    }
+
+    /// Allocates a new Mat3 with the given rows.
+    pub fn from_rows(row0: &Vec3, row1: &Vec3, row2: &Vec3) -> Mat3 {
+        Mat3::new([[row0.x, row0.y, row0.z], [row1.x, row1.y, row1.z], [row2.x, row2.y, row2.z]])
+    }
+
+    /// Allocates a new Mat3 with the given columns.
+    pub fn from_cols(col0: &Vec3, col1: &Vec3, col2: &Vec3) -> Mat3 {
+        Mat3::from_rows(col0, col1, col2).transpose()
+    }
+
+    /// Returns the row at `index`, from 0 to 2.
+    pub fn row(&self, index: usize) -> Vec3 {
+        match index {
+            0 => Vec3::new(self.m00, self.m01, self.m02),
+            1 => Vec3::new(self.m10, self.m11, self.m12),
+            2 => Vec3::new(self.m20, self.m21, self.m22),
+            _ => panic!("Mat3 row index out of bounds: {}", index),
+        }
+    }
+
+    /// Returns the column at `index`, from 0 to 2.
+    pub fn col(&self, index: usize) -> Vec3 {
+        match index {
+            0 => Vec3::new(self.m00, self.m10, self.m20),
+            1 => Vec3::new(self.m01, self.m11, self.m21),
+            2 => Vec3::new(self.m02, self.m12, self.m22),
+            _ => panic!("Mat3 column index out of bounds: {}", index),
+        }
+    }
+
+    /// Returns the determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        self.row(0).dot(&self.row(1).cross(&self.row(2)))
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transpose(&self) -> Mat3 {
+        Mat3::new([[self.m00, self.m10, self.m20], [self.m01, self.m11, self.m21], [self.m02, self.m12, self.m22]])
+    }
+
+    /// Returns the inverse of the matrix, or `None` if it is singular, as decided by
+    /// `is_singular`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat3, ApproxEq};
+    /// let m = Mat3::new([[2.0, 0.0, 0.0], [0.0, 1.0, 1.0], [0.0, 0.0, 4.0]]);
+    /// let inverse = m.inverse().unwrap();
+    /// assert_eq!(inverse, Mat3::new([[0.5, 0.0, 0.0], [0.0, 1.0, -0.25], [0.0, 0.0, 0.25]]));
+    /// assert!((m * inverse).approx_eq(Mat3::IDENTITY));
+    /// assert_eq!(Mat3::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Mat3> {
+        if self.is_singular() {
+            return None;
+        }
+        let det = self.determinant();
+        let (row0, row1, row2) = (self.row(0), self.row(1), self.row(2));
+        // The columns of the inverse are perpendicular to two rows each, and scaled so their
+        // dot product with the third is one.
+        Some(Mat3::from_cols(&(row1.cross(&row2) / det), &(row2.cross(&row0) / det), &(row0.cross(&row1) / det)))
+    }
+
+    /// Returns true if the matrix has no inverse, or is so close to singular that its inverse
+    /// would be dominated by rounding errors: the determinant is negligible next to the product
+    /// of the row lengths.
+    pub fn is_singular(&self) -> bool {
+        let det = self.determinant();
+        let scale = self.row(0).length() * self.row(1).length() * self.row(2).length();
+        det == 0.0 || !det.is_finite() || det.abs() <= scale * f64::EPSILON * 8.0
+    }
+}
+
+/// Solves the linear system `m * x = b` for `x`, with Cramer's rule. Returns `None` if the
+/// matrix is singular, as decided by `Mat3::is_singular`, which happens when the three
+/// equations are planes that don't meet at a single point.
+///
+/// # Examples
+/// ```
+/// use candle::math::{self, Mat3, Vec3, ApproxEq};
+/// // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+/// let m = Mat3::new([[1.0, 1.0, 1.0], [0.0, 2.0, 5.0], [2.0, 5.0, -1.0]]);
+/// let x = math::solve3(&m, &Vec3::new(6.0, -4.0, 27.0)).unwrap();
+/// assert!(x.approx_eq(Vec3::new(5.0, 3.0, -2.0)));
+///
+/// let parallel = Mat3::new([[1.0, 1.0, 1.0], [2.0, 2.0, 2.0], [0.0, 1.0, 0.0]]);
+/// assert_eq!(math::solve3(&parallel, &Vec3::new(1.0, 2.0, 3.0)), None);
+/// ```
+pub fn solve3(m: &Mat3, b: &Vec3) -> Option<Vec3> {
+    if m.is_singular() {
+        return None;
+    }
+    let det = m.determinant();
+    let (col0, col1, col2) = (m.col(0), m.col(1), m.col(2));
+    Some(Vec3::new(
+        b.dot(&col1.cross(&col2)) / det,
+        col0.dot(&b.cross(&col2)) / det,
+        col0.dot(&col1.cross(b)) / det,
+    ))
+}
+
+impl Add<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn add(self, other: Mat3) -> Mat3 {
+        Mat3::from_rows(&(self.row(0) + other.row(0)), &(self.row(1) + other.row(1)), &(self.row(2) + other.row(2)))
+    }
+}
+
+impl Sub<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn sub(self, other: Mat3) -> Mat3 {
+        Mat3::from_rows(&(self.row(0) - other.row(0)), &(self.row(1) - other.row(1)), &(self.row(2) - other.row(2)))
+    }
+}
+
+impl Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    /// Multiplies the two matrices. The result applies `other` first, then `self`.
+    fn mul(self, other: Mat3) -> Mat3 {
+        Mat3::from_cols(&(self * other.col(0)), &(self * other.col(1)), &(self * other.col(2)))
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    /// Transforms the column vector by the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat3, Vec3};
+    /// let m = Mat3::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+    /// assert_eq!(m * Vec3::new(1.0, 1.0, 1.0), Vec3::new(6.0, 15.0, 24.0));
+    /// ```
+    fn mul(self, vec: Vec3) -> Vec3 {
+        Vec3::new(self.row(0).dot(&vec), self.row(1).dot(&vec), self.row(2).dot(&vec))
+    }
+}
+
+impl Mul<f64> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, value: f64) -> Mat3 {
+        Mat3::from_rows(&(self.row(0) * value), &(self.row(1) * value), &(self.row(2) * value))
+    }
+}
+
+impl ApproxEq<Mat3> for Mat3 {
+    fn approx_eq_eps(self, other: Mat3, eps: Mat3) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq_eps(other.row(i), eps.row(i)))
+    }
+
+    /// Returns true if every element is approximately equal, with the combined absolute and
+    /// relative EPSILON tolerance.
+    fn approx_eq(self, other: Mat3) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq(other.row(i)))
+    }
+
+    fn approx_eq_rel(self, other: Mat3, max_rel: f64) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq_rel(other.row(i), max_rel))
+    }
+
+    fn approx_eq_ulps(self, other: Mat3, max_ulps: u64) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq_ulps(other.row(i), max_ulps))
+    }
+}
@@ -0,0 +1,294 @@
+use std::ops::{Index, IndexMut, Mul};
+
+/// A heap-allocated matrix of f64 with dimensions chosen at runtime, stored row by row. Meant
+/// for modest sizes, such as constraint systems of a few dozen unknowns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatN {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl MatN {
+
+    /// Allocates a new MatN of `rows` by `cols` zeros.
+    pub fn zeros(rows: usize, cols: usize) -> MatN {
+        MatN { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    /// Allocates the `size` by `size` identity matrix.
+    pub fn identity(size: usize) -> MatN {
+        let mut m = MatN::zeros(size, size);
+        for i in 0..size {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    /// Allocates a new MatN from its elements row by row. Returns `None` if the number of
+    /// elements is not `rows * cols`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::MatN;
+    /// let m = MatN::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    /// assert_eq!(m[(1, 0)], 4.0);
+    /// assert_eq!(MatN::from_vec(2, 2, vec![1.0]), None);
+    /// ```
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Option<MatN> {
+        if data.len() != rows * cols {
+            return None;
+        }
+        Some(MatN { rows, cols, data })
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns true if the matrix has as many rows as columns.
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Returns the elements row by row.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Returns the row at `index` as a slice.
+    pub fn row(&self, index: usize) -> &[f64] {
+        &self.data[index * self.cols..(index + 1) * self.cols]
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transpose(&self) -> MatN {
+        let mut t = MatN::zeros(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t[(c, r)] = self[(r, c)];
+            }
+        }
+        t
+    }
+
+    /// Returns the product of the two matrices, or `None` if the columns of `self` don't match
+    /// the rows of `other`.
+    pub fn checked_mul(&self, other: &MatN) -> Option<MatN> {
+        if self.cols != other.rows {
+            return None;
+        }
+        let mut product = MatN::zeros(self.rows, other.cols);
+        for r in 0..self.rows {
+            for (k, &value) in self.row(r).iter().enumerate() {
+                if value == 0.0 {
+                    continue;
+                }
+                for (c, &factor) in other.row(k).iter().enumerate() {
+                    product.data[r * other.cols + c] += value * factor;
+                }
+            }
+        }
+        Some(product)
+    }
+
+    /// Multiplies the matrix by the column vector `vector`, or returns `None` if its length
+    /// doesn't match the columns.
+    pub fn mul_vec(&self, vector: &[f64]) -> Option<Vec<f64>> {
+        if vector.len() != self.cols {
+            return None;
+        }
+        Some((0..self.rows).map(|r| self.row(r).iter().zip(vector).map(|(a, b)| a * b).sum()).collect())
+    }
+
+    /// Computes the LU factorization of a square matrix with partial pivoting. Returns `None` if
+    /// the matrix isn't square or is singular: a pivot is within rounding error of zero compared
+    /// to the largest element of its own row, so rows of very different scales still factor.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::MatN;
+    /// let stiff = MatN::from_vec(2, 2, vec![1e6, 0.0, 0.0, 1e-10]).unwrap();
+    /// assert!(stiff.lu().is_some());
+    /// let singular = MatN::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+    /// assert!(singular.lu().is_none());
+    /// ```
+    pub fn lu(&self) -> Option<Lu> {
+        self.factor(self.rows as f64 * f64::EPSILON)
+    }
+
+    /// Computes the LU factorization, failing on a pivot no larger than `tolerance` times the
+    /// largest element of its row.
+    fn factor(&self, tolerance: f64) -> Option<Lu> {
+        if !self.is_square() {
+            return None;
+        }
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        let scales: Vec<f64> = (0..n).map(|r| self.row(r).iter().fold(0.0, |max: f64, value| max.max(value.abs()))).collect();
+
+        for k in 0..n {
+            // Pivot on the largest remaining element of the column to bound the growth of
+            // rounding errors.
+            let pivot = (k..n).max_by(|&a, &b| lu[(a, k)].abs().total_cmp(&lu[(b, k)].abs()))?;
+            if lu[(pivot, k)].abs() <= tolerance * scales[permutation[pivot]] {
+                return None;
+            }
+            if pivot != k {
+                for c in 0..n {
+                    lu.data.swap(k * n + c, pivot * n + c);
+                }
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            for r in k + 1..n {
+                let factor = lu[(r, k)] / lu[(k, k)];
+                lu[(r, k)] = factor;
+                for c in k + 1..n {
+                    let value = lu[(k, c)];
+                    lu[(r, c)] -= factor * value;
+                }
+            }
+        }
+        Some(Lu { lu, permutation, sign })
+    }
+
+    /// Solves `self * x = b` for `x`. Returns `None` if the matrix isn't square, is singular or
+    /// doesn't match the length of `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{MatN, ApproxEq};
+    /// let m = MatN::from_vec(3, 3, vec![
+    ///     2.0, 1.0, -1.0,
+    ///     -3.0, -1.0, 2.0,
+    ///     -2.0, 1.0, 2.0,
+    /// ]).unwrap();
+    /// let x = m.solve(&[8.0, -11.0, -3.0]).unwrap();
+    /// assert!(x[..].approx_eq_eps(&[2.0, 3.0, -1.0][..], &[1e-12; 3][..]));
+    ///
+    /// let stiff = MatN::from_vec(2, 2, vec![1e6, 0.0, 0.0, 1e-10]).unwrap();
+    /// assert_eq!(stiff.solve(&[1e6, 1e-10]), Some(vec![1.0, 1.0]));
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        self.lu()?.solve(b)
+    }
+
+    /// Returns the determinant of a square matrix, zero if it is singular, or `None` if it
+    /// isn't square. It is the product of the pivots however small they get, so only an exactly
+    /// singular matrix gives zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{MatN, ApproxEq};
+    /// let m = MatN::from_vec(2, 2, vec![3.0, 8.0, 4.0, 6.0]).unwrap();
+    /// assert!(m.determinant().unwrap().approx_eq(-14.0));
+    /// let stiff = MatN::from_vec(2, 2, vec![1e17, 0.0, 0.0, 1.0]).unwrap();
+    /// assert_eq!(stiff.determinant(), Some(1e17));
+    /// ```
+    pub fn determinant(&self) -> Option<f64> {
+        if !self.is_square() {
+            return None;
+        }
+        Some(self.factor(0.0).map_or(0.0, |lu| lu.determinant()))
+    }
+
+    /// Returns the inverse of a square matrix, or `None` if it isn't square or is singular.
+    pub fn inverse(&self) -> Option<MatN> {
+        let lu = self.lu()?;
+        let n = self.rows;
+        let mut inverse = MatN::zeros(n, n);
+        let mut unit = vec![0.0; n];
+        for c in 0..n {
+            unit[c] = 1.0;
+            for (r, value) in lu.solve(&unit)?.into_iter().enumerate() {
+                inverse[(r, c)] = value;
+            }
+            unit[c] = 0.0;
+        }
+        Some(inverse)
+    }
+}
+
+/// The LU factorization of a square matrix with partial pivoting, `P * A = L * U`, which
+/// solves systems with the same matrix and different right hand sides in O(n²) each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu {
+    /// L below the diagonal (its unit diagonal implied) and U on and above it.
+    lu: MatN,
+    permutation: Vec<usize>,
+    sign: f64,
+}
+
+impl Lu {
+
+    /// Solves `A * x = b` for `x`, or returns `None` if `b` doesn't match the size of `A`.
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.lu.rows;
+        if b.len() != n {
+            return None;
+        }
+        // Forward substitution with L, then back substitution with U.
+        let mut x: Vec<f64> = self.permutation.iter().map(|&i| b[i]).collect();
+        for r in 0..n {
+            let sum: f64 = (0..r).map(|c| self.lu[(r, c)] * x[c]).sum();
+            x[r] -= sum;
+        }
+        for r in (0..n).rev() {
+            let sum: f64 = (r + 1..n).map(|c| self.lu[(r, c)] * x[c]).sum();
+            x[r] = (x[r] - sum) / self.lu[(r, r)];
+        }
+        Some(x)
+    }
+
+    /// Returns the determinant of `A`.
+    pub fn determinant(&self) -> f64 {
+        (0..self.lu.rows).fold(self.sign, |product, i| product * self.lu[(i, i)])
+    }
+}
+
+impl Index<(usize, usize)> for MatN {
+    type Output = f64;
+
+    /// Returns the element at `(row, column)`, panicking if it is out of the matrix.
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(row < self.rows && col < self.cols, "element ({}, {}) is out of a {}x{} matrix", row, col, self.rows, self.cols);
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for MatN {
+    /// Returns the element at `(row, column)` mutably, panicking if it is out of the matrix.
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        assert!(row < self.rows && col < self.cols, "element ({}, {}) is out of a {}x{} matrix", row, col, self.rows, self.cols);
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl Mul<&MatN> for &MatN {
+    type Output = MatN;
+
+    /// Multiplies the two matrices, panicking if their dimensions don't match. See
+    /// `checked_mul` for the non-panicking variant.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::MatN;
+    /// let a = MatN::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    /// let product = &a * &a.transpose();
+    /// assert_eq!(product, MatN::from_vec(2, 2, vec![14.0, 32.0, 32.0, 77.0]).unwrap());
+    /// ```
+    fn mul(self, other: &MatN) -> MatN {
+        self.checked_mul(other).unwrap_or_else(|| {
+            panic!("cannot multiply a {}x{} matrix by a {}x{} matrix", self.rows, self.cols, other.rows, other.cols)
+        })
+    }
+}
@@ -1,5 +1,43 @@
 mod vec2;
+mod vec3;
+mod vec2_key;
 mod approx_eq;
+mod barycentric;
+mod angle;
+mod fixed;
+mod rational;
+mod complex;
+mod polynomial;
+mod mat2;
+mod mat3;
+mod grid;
+mod mat_n;
+#[cfg(feature = "approx")]
+mod approx_impls;
+
+pub mod scalar;
+pub mod predicates;
+pub mod roots;
+pub mod integrate;
+pub mod fit;
+pub mod stats;
+pub mod filter;
+pub mod fft;
+pub mod convolution;
+pub mod morph;
+pub mod distance_transform;
 
 pub use self::vec2::Vec2;
+pub use self::vec3::Vec3;
+pub use self::vec2_key::Vec2Key;
 pub use self::approx_eq::ApproxEq;
+pub use self::barycentric::{Barycentric, point_in_triangle};
+pub use self::angle::Angle;
+pub use self::fixed::{Fixed, FixedVec2};
+pub use self::rational::Rational;
+pub use self::complex::Complex;
+pub use self::polynomial::Polynomial;
+pub use self::mat2::{Mat2, solve2};
+pub use self::mat3::{Mat3, solve3};
+pub use self::grid::Grid;
+pub use self::mat_n::{MatN, Lu};
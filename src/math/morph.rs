@@ -0,0 +1,60 @@
+use std::ops::{Add, Sub, Mul};
+
+/// Blends point sets of the same length by a weighted average, point by point. The weights are
+/// normalized to sum to one, so they only express the relative influence of each set. Returns
+/// `None` if the numbers of sets and weights differ, the sets have different lengths, there are
+/// no sets or the weights sum to zero.
+///
+/// Works with any point type supporting addition and scaling, such as `Vec2` or `f64`.
+///
+/// # Examples
+/// ```
+/// use candle::math::{morph, Vec2};
+/// let square = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0)];
+/// let diamond = [Vec2::new(1.0, -1.0), Vec2::new(3.0, 1.0), Vec2::new(1.0, 3.0), Vec2::new(-1.0, 1.0)];
+/// let halfway = morph::blend(&[&square[..], &diamond[..]], &[1.0, 1.0]).unwrap();
+/// assert_eq!(halfway[0], Vec2::new(0.5, -0.5));
+/// assert_eq!(morph::blend(&[&square[..], &diamond[..3]], &[1.0, 1.0]), None);
+/// ```
+pub fn blend<T>(sets: &[&[T]], weights: &[f64]) -> Option<Vec<T>>
+    where T: Copy + Add<Output = T> + Mul<f64, Output = T>
+{
+    let total: f64 = weights.iter().sum();
+    if sets.is_empty() || sets.len() != weights.len() || total == 0.0 {
+        return None;
+    }
+    let len = sets[0].len();
+    if sets.iter().any(|set| set.len() != len) {
+        return None;
+    }
+    Some((0..len).map(|i| {
+        sets[1..].iter().zip(&weights[1..])
+            .fold(sets[0][i] * (weights[0] / total), |sum, (set, weight)| sum + set[i] * (weight / total))
+    }).collect())
+}
+
+/// Blends shape keys on top of a `base` point set: each key moves the points by its offset from
+/// the base, scaled by its weight, and the offsets add up. Unlike `blend`, weights are not
+/// normalized, so keys combine independently, overshoot above one and reverse below zero, as
+/// blend shapes do. Returns `None` if the numbers of keys and weights differ or a key has a
+/// different length than the base.
+///
+/// # Examples
+/// ```
+/// use candle::math::{morph, Vec2};
+/// let base = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)];
+/// let stretch = [Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0)];
+/// let lift = [Vec2::new(0.0, 1.0), Vec2::new(1.0, 1.0)];
+/// let shape = morph::blend_deltas(&base, &[&stretch[..], &lift[..]], &[0.5, 1.0]).unwrap();
+/// assert_eq!(shape, vec![Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0)]);
+/// ```
+pub fn blend_deltas<T>(base: &[T], keys: &[&[T]], weights: &[f64]) -> Option<Vec<T>>
+    where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>
+{
+    if keys.len() != weights.len() || keys.iter().any(|key| key.len() != base.len()) {
+        return None;
+    }
+    Some(base.iter().enumerate().map(|(i, &point)| {
+        keys.iter().zip(weights).fold(point, |sum, (key, &weight)| sum + (key[i] - point) * weight)
+    }).collect())
+}
@@ -0,0 +1,359 @@
+use std::ops::{Add, Sub, Mul};
+use std::f64::consts::PI;
+use math::roots::{self, Tolerance};
+
+/// Relative tolerance under which two roots are considered the same.
+const ROOT_EPSILON: f64 = 1e-12;
+
+/// A polynomial with real coefficients, stored from the constant term up, so
+/// `Polynomial::new(vec![1.0, 2.0, 3.0])` is `1 + 2x + 3x²`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<f64>,
+}
+
+impl Polynomial {
+
+    /// Allocates a new Polynomial from its coefficients, constant term first. Zero coefficients
+    /// of the highest powers are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Polynomial;
+    /// let poly = Polynomial::new(vec![1.0, 2.0, 3.0, 0.0]);
+    /// assert_eq!(poly.degree(), 2);
+    /// assert_eq!(poly.coefficients(), &[1.0, 2.0, 3.0]);
+    /// ```
+    pub fn new(mut coefficients: Vec<f64>) -> Polynomial {
+        while coefficients.len() > 1 && coefficients[coefficients.len() - 1] == 0.0 {
+            coefficients.pop();
+        }
+        if coefficients.is_empty() {
+            coefficients.push(0.0);
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Returns the coefficients, constant term first.
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Returns the highest power with a non-zero coefficient. Constant polynomials, including
+    /// zero, have degree zero.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// Evaluates the polynomial at `x` with Horner's method.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Polynomial;
+    /// let poly = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// assert_eq!(poly.eval(2.0), 17.0);
+    /// ```
+    pub fn eval(&self, x: f64) -> f64 {
+        self.coefficients.iter().rev().fold(0.0, |acc, coefficient| acc * x + coefficient)
+    }
+
+    /// Returns the derivative of the polynomial.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Polynomial;
+    /// let poly = Polynomial::new(vec![1.0, 2.0, 3.0]);
+    /// assert_eq!(poly.derivative(), Polynomial::new(vec![2.0, 6.0]));
+    /// ```
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::new(self.coefficients.iter().enumerate().skip(1)
+            .map(|(power, coefficient)| coefficient * power as f64)
+            .collect())
+    }
+
+    /// Returns the distinct real roots of the polynomial in ascending order. Polynomials up to
+    /// the fourth degree are solved in closed form and the roots refined with Newton steps;
+    /// higher degrees isolate each root between the extrema of the polynomial and refine it with
+    /// `roots::brent`, as do cubics and quartics whose discriminant is within its rounding error,
+    /// where roots too close together can't be told apart in closed form. Constant polynomials
+    /// have no roots, even zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Polynomial, ApproxEq};
+    /// // (x - 1)(x + 2) = x² + x - 2
+    /// let quadratic = Polynomial::new(vec![-2.0, 1.0, 1.0]);
+    /// assert_eq!(quadratic.roots(), vec![-2.0, 1.0]);
+    ///
+    /// // (x - 1)(x - 2)(x - 3)(x + 4) = x⁴ - 2x³ - 13x² + 38x - 24
+    /// let quartic = Polynomial::new(vec![-24.0, 38.0, -13.0, -2.0, 1.0]);
+    /// let roots = quartic.roots();
+    /// assert!(roots[..].approx_eq_eps(&[-4.0, 1.0, 2.0, 3.0][..], &[1e-9; 4][..]));
+    ///
+    /// // x⁵ - x = x(x - 1)(x + 1)(x² + 1)
+    /// let quintic = Polynomial::new(vec![0.0, -1.0, 0.0, 0.0, 0.0, 1.0]);
+    /// let roots = quintic.roots();
+    /// assert!(roots[..].approx_eq_eps(&[-1.0, 0.0, 1.0][..], &[1e-9; 3][..]));
+    ///
+    /// assert!(Polynomial::new(vec![1.0, 0.0, 1.0]).roots().is_empty());
+    ///
+    /// // Roots close together stay apart.
+    /// let close = Polynomial::new(vec![-1.000001, 1.0]) * Polynomial::new(vec![-1.0, 1.0]);
+    /// let roots = close.roots();
+    /// assert!(roots[..].approx_eq_eps(&[1.0, 1.000001][..], &[1e-9; 2][..]));
+    /// let close = close * Polynomial::new(vec![-5.0, 1.0]);
+    /// let roots = close.roots();
+    /// assert!(roots[..].approx_eq_eps(&[1.0, 1.000001, 5.0][..], &[1e-9; 3][..]));
+    /// let close = close * Polynomial::new(vec![3.0, 1.0]);
+    /// let roots = close.roots();
+    /// assert!(roots[..].approx_eq_eps(&[-3.0, 1.0, 1.000001, 5.0][..], &[1e-9; 4][..]));
+    /// // (x - 1e8 + 1)(x - 1e8 - 1) would round its constant term to 1e16, a double root.
+    /// let far = Polynomial::new(vec![-1e8 + 2.0, 1.0]) * Polynomial::new(vec![-1e8 - 2.0, 1.0]);
+    /// assert_eq!(far.roots(), vec![1e8 - 2.0, 1e8 + 2.0]);
+    /// ```
+    pub fn roots(&self) -> Vec<f64> {
+        let c = &self.coefficients;
+        let closed = match self.degree() {
+            0 => Some(Vec::new()),
+            1 => Some(vec![-c[0] / c[1]]),
+            2 => Some(solve_quadratic(c[2], c[1], c[0])),
+            3 => solve_cubic(c[2] / c[3], c[1] / c[3], c[0] / c[3]),
+            4 => solve_quartic(c[3] / c[4], c[2] / c[4], c[1] / c[4], c[0] / c[4]),
+            _ => None,
+        };
+        let mut roots = match closed {
+            Some(roots) => roots,
+            None => return self.isolated_roots(),
+        };
+        if self.degree() > 2 {
+            let derivative = self.derivative();
+            for root in roots.iter_mut() {
+                *root = newton_polish(self, &derivative, *root);
+            }
+        }
+        sort_and_dedup(roots)
+    }
+
+    /// Finds the roots of polynomials of any degree by recursively finding the extrema (the roots
+    /// of the derivative) and refining the root of each monotonic interval with a sign change.
+    fn isolated_roots(&self) -> Vec<f64> {
+        let c = &self.coefficients;
+        let leading = c[c.len() - 1];
+        // Cauchy's bound: every root lies within this distance from zero.
+        let bound = 1.0 + c[..c.len() - 1].iter().map(|coefficient| (coefficient / leading).abs()).fold(0.0, f64::max);
+
+        let mut points = vec![-bound];
+        points.extend(self.derivative().roots().into_iter().filter(|x| x.abs() < bound));
+        points.push(bound);
+
+        let mut roots = Vec::new();
+        for window in points.windows(2) {
+            let (a, b) = (window[0], window[1]);
+            let (fa, fb) = (self.eval(a), self.eval(b));
+            if fa.abs() <= self.eval_error(a) {
+                roots.push(a);
+            } else if fa.signum() != fb.signum() {
+                let tolerance = Tolerance::new(0.0, 0.0).with_max_iterations(200);
+                roots.push(roots::brent(|x| self.eval(x), a, b, &tolerance).unwrap_or((a + b) / 2.0));
+            }
+        }
+        if self.eval(bound) == 0.0 {
+            roots.push(bound);
+        }
+        sort_and_dedup(roots)
+    }
+
+    /// Bounds the rounding error of `eval(x)`, below which the value can't be told from zero.
+    fn eval_error(&self, x: f64) -> f64 {
+        let magnitude = self.coefficients.iter().rev().fold(0.0, |acc, coefficient| acc * x.abs() + coefficient.abs());
+        2.0 * self.coefficients.len() as f64 * f64::EPSILON * magnitude
+    }
+}
+
+/// Solves `a x² + b x + c = 0` for real roots, avoiding cancellation between `b` and the
+/// square root of the discriminant.
+fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let discriminant = b * b - 4.0 * a * c;
+    // The rounding error of the subtraction, within which the discriminant can't be told from
+    // zero and the two roots from a double one.
+    let error = f64::EPSILON * (b * b).max((4.0 * a * c).abs());
+    if discriminant < -error {
+        return Vec::new();
+    }
+    if discriminant <= error {
+        return vec![-b / (2.0 * a)];
+    }
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    if q == 0.0 {
+        return vec![0.0];
+    }
+    vec![q / a, c / q]
+}
+
+/// Solves the monic cubic `x³ + a x² + b x + c = 0` for real roots, or returns `None` if the
+/// discriminant is within its rounding error, when roots too close together can't be told
+/// apart in closed form.
+fn solve_cubic(a: f64, b: f64, c: f64) -> Option<Vec<f64>> {
+    // Substitute x = t - a/3 to get the depressed cubic t³ + p t + q = 0.
+    let shift = a / 3.0;
+    let p = b - a * a / 3.0;
+    let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+    let discriminant = (q / 2.0) * (q / 2.0) + (p / 3.0) * (p / 3.0) * (p / 3.0);
+    let magnitude = |v: &[f64]| (v[1] / 2.0).powi(2) + (v[0] / 3.0).abs().powi(3);
+    let error = discriminant_error(magnitude, &[
+        (p, b.abs() + a * a / 3.0),
+        (q, (2.0 * a * a * a / 27.0).abs() + (a * b / 3.0).abs() + c.abs()),
+    ]);
+    if discriminant.abs() <= error {
+        return None;
+    }
+
+    let roots = if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+        vec![(-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt()]
+    } else {
+        let radius = 2.0 * (-p / 3.0).sqrt();
+        let phi = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3).map(|k| radius * (phi - 2.0 * PI * k as f64 / 3.0).cos()).collect()
+    };
+    Some(roots.into_iter().map(|t| t - shift).collect())
+}
+
+/// Solves the monic quartic `x⁴ + a x³ + b x² + c x + d = 0` for real roots with Ferrari's
+/// method, or returns `None` if the discriminant is within its rounding error, as for cubics.
+fn solve_quartic(a: f64, b: f64, c: f64, d: f64) -> Option<Vec<f64>> {
+    // Substitute x = y - a/4 to get the depressed quartic y⁴ + p y² + q y + r = 0.
+    let shift = a / 4.0;
+    let a2 = a * a;
+    let p = b - 3.0 * a2 / 8.0;
+    let q = c - a * b / 2.0 + a2 * a / 8.0;
+    let r = d - a * c / 4.0 + a2 * b / 16.0 - 3.0 * a2 * a2 / 256.0;
+
+    let discriminant = 256.0 * r * r * r - 128.0 * p * p * r * r + 144.0 * p * q * q * r
+        - 27.0 * q * q * q * q + 16.0 * p * p * p * p * r - 4.0 * p * p * p * q * q;
+    let magnitude = |v: &[f64]| {
+        let (p, q, r) = (v[0].abs(), v[1].abs(), v[2].abs());
+        256.0 * r * r * r + 128.0 * p * p * r * r + 144.0 * p * q * q * r
+            + 27.0 * q * q * q * q + 16.0 * p * p * p * p * r + 4.0 * p * p * p * q * q
+    };
+    let error = discriminant_error(magnitude, &[
+        (p, b.abs() + 3.0 * a2 / 8.0),
+        (q, c.abs() + (a * b / 2.0).abs() + (a2 * a / 8.0).abs()),
+        (r, d.abs() + (a * c / 4.0).abs() + (a2 * b / 16.0).abs() + 3.0 * a2 * a2 / 256.0),
+    ]);
+    if discriminant.abs() <= error {
+        return None;
+    }
+
+    let mut roots = Vec::new();
+    if q.abs() <= ROOT_EPSILON {
+        // Biquadratic: solve for z = y².
+        for z in solve_quadratic(1.0, p, r) {
+            if z > 0.0 {
+                roots.push(z.sqrt());
+                roots.push(-z.sqrt());
+            } else if z.abs() <= ROOT_EPSILON {
+                roots.push(0.0);
+            }
+        }
+    } else {
+        // Any positive root m of the resolvent cubic turns both sides of
+        // (y² + p/2 + m)² = 2m y² - q y + m² + m p + p²/4 - r into perfect squares.
+        let (b, c, d) = (p, p * p / 4.0 - r, -q * q / 8.0);
+        let resolvent = solve_cubic(b, c, d).unwrap_or_else(|| Polynomial::new(vec![d, c, b, 1.0]).isolated_roots());
+        let m = resolvent.into_iter().fold(0.0, f64::max);
+        if m <= 0.0 {
+            return Some(Vec::new());
+        }
+        let s = (2.0 * m).sqrt();
+        roots.extend(solve_quadratic(1.0, -s, p / 2.0 + m + q / (2.0 * s)));
+        roots.extend(solve_quadratic(1.0, s, p / 2.0 + m - q / (2.0 * s)));
+    }
+    Some(roots.into_iter().map(|y| y - shift).collect())
+}
+
+/// Bounds the rounding error of a discriminant computed from some values, each paired with the
+/// sum of the magnitudes of the terms it was computed from. `magnitude` sums the magnitudes of
+/// the discriminant's terms, so its growth when the values are off by their own rounding errors
+/// bounds how far those errors carry through.
+fn discriminant_error<F>(magnitude: F, values: &[(f64, f64)]) -> f64
+    where F: Fn(&[f64]) -> f64
+{
+    let exact: Vec<f64> = values.iter().map(|&(value, _)| value.abs()).collect();
+    let perturbed: Vec<f64> = values.iter().map(|&(value, terms)| value.abs() + 4.0 * f64::EPSILON * terms).collect();
+    8.0 * f64::EPSILON * magnitude(&exact) + magnitude(&perturbed) - magnitude(&exact)
+}
+
+/// Refines a root with Newton steps, keeping only those that bring the polynomial closer to
+/// zero, so a step from where the slope nearly vanishes can't throw the root away.
+fn newton_polish(poly: &Polynomial, derivative: &Polynomial, mut x: f64) -> f64 {
+    let mut residual = poly.eval(x).abs();
+    for _ in 0..8 {
+        let slope = derivative.eval(x);
+        if residual == 0.0 || slope == 0.0 {
+            break;
+        }
+        let next = x - poly.eval(x) / slope;
+        let next_residual = poly.eval(next).abs();
+        if next_residual.is_nan() || next_residual >= residual {
+            break;
+        }
+        x = next;
+        residual = next_residual;
+    }
+    x
+}
+
+fn sort_and_dedup(mut roots: Vec<f64>) -> Vec<f64> {
+    roots.retain(|root| root.is_finite());
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots.dedup_by(|a, b| (*a - *b).abs() <= ROOT_EPSILON * a.abs().max(b.abs()).max(1.0) * 1e3);
+    roots
+}
+
+impl Add<Polynomial> for Polynomial {
+    type Output = Polynomial;
+
+    /// Adds the coefficients of the two polynomials.
+    fn add(self, other: Polynomial) -> Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        Polynomial::new((0..len).map(|i| {
+            self.coefficients.get(i).unwrap_or(&0.0) + other.coefficients.get(i).unwrap_or(&0.0)
+        }).collect())
+    }
+}
+
+impl Sub<Polynomial> for Polynomial {
+    type Output = Polynomial;
+
+    /// Subtracts the coefficients of the two polynomials.
+    fn sub(self, other: Polynomial) -> Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        Polynomial::new((0..len).map(|i| {
+            self.coefficients.get(i).unwrap_or(&0.0) - other.coefficients.get(i).unwrap_or(&0.0)
+        }).collect())
+    }
+}
+
+impl Mul<Polynomial> for Polynomial {
+    type Output = Polynomial;
+
+    /// Multiplies the two polynomials.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Polynomial;
+    /// // (x - 1)(x + 1) = x² - 1
+    /// let product = Polynomial::new(vec![-1.0, 1.0]) * Polynomial::new(vec![1.0, 1.0]);
+    /// assert_eq!(product, Polynomial::new(vec![-1.0, 0.0, 1.0]));
+    /// ```
+    fn mul(self, other: Polynomial) -> Polynomial {
+        let mut coefficients = vec![0.0; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += a * b;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+use math::vec2::Vec2;
+use math::rational::Rational;
+
+/// Half the distance between 1.0 and the next float, the unit roundoff of f64 arithmetic.
+const ROUNDOFF: f64 = f64::EPSILON / 2.0;
+const ORIENT_ERROR_BOUND: f64 = (3.0 + 16.0 * ROUNDOFF) * ROUNDOFF;
+const INCIRCLE_ERROR_BOUND: f64 = (10.0 + 96.0 * ROUNDOFF) * ROUNDOFF;
+
+/// Returns the orientation of the triangle `(a, b, c)`: `Greater` if the points turn
+/// counterclockwise, `Less` if they turn clockwise and `Equal` if they are collinear.
+///
+/// The floating point result is used when its error bound proves the sign right; otherwise the
+/// determinant is recomputed exactly with `Rational`. If even that overflows, which needs
+/// coordinates with very different magnitudes, the floating point sign is returned.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use candle::math::{predicates, Vec2};
+/// let a = Vec2::new(0.0, 0.0);
+/// let b = Vec2::new(1.0, 0.0);
+/// assert_eq!(predicates::orient2d(&a, &b, &Vec2::new(0.5, 1.0)), Ordering::Greater);
+/// assert_eq!(predicates::orient2d(&a, &b, &Vec2::new(0.5, -1.0)), Ordering::Less);
+///
+/// // Nearly collinear points where the naive determinant rounds to zero.
+/// let a = Vec2::new(0.5, 0.5);
+/// let b = Vec2::new(12.0, 12.0);
+/// let c = Vec2::new(24.0, 24.0);
+/// let p = Vec2::new(0.5 + f64::EPSILON, 0.5);
+/// assert_eq!(predicates::orient2d(&a, &b, &c), Ordering::Equal);
+/// assert_eq!(predicates::orient2d(&p, &b, &c), Ordering::Less);
+/// ```
+pub fn orient2d(a: &Vec2, b: &Vec2, c: &Vec2) -> Ordering {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+    if det.abs() > ORIENT_ERROR_BOUND * (left.abs() + right.abs()) {
+        return sign(det);
+    }
+    orient2d_exact(a, b, c).unwrap_or_else(|| sign(det))
+}
+
+/// Computes the orientation of the triangle `(a, b, c)` with exact rational arithmetic, as
+/// described in `orient2d`. Returns `None` if a coordinate isn't finite or an intermediate
+/// value doesn't fit in `Rational`.
+pub fn orient2d_exact(a: &Vec2, b: &Vec2, c: &Vec2) -> Option<Ordering> {
+    let (ax, ay) = to_rational(a)?;
+    let (bx, by) = to_rational(b)?;
+    let (cx, cy) = to_rational(c)?;
+    let left = ax.checked_sub(&cx)?.checked_mul(&by.checked_sub(&cy)?)?;
+    let right = ay.checked_sub(&cy)?.checked_mul(&bx.checked_sub(&cx)?)?;
+    Some(left.cmp(&right))
+}
+
+/// Returns `Greater` if `d` lies inside the circle through the counterclockwise triangle
+/// `(a, b, c)`, `Less` if it lies outside and `Equal` if it lies on the circle. The result is
+/// reversed for clockwise triangles.
+///
+/// Like `orient2d`, ambiguous floating point results are recomputed exactly, falling back to
+/// the floating point sign if the exact computation overflows. Being a degree four polynomial
+/// of the coordinates, this overflows much sooner than `orient2d`: it is exact for coordinates
+/// with a few fractional bits, such as integers, halves or quarters.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use candle::math::{predicates, Vec2};
+/// let a = Vec2::new(0.0, 0.0);
+/// let b = Vec2::new(2.0, 0.0);
+/// let c = Vec2::new(2.0, 2.0);
+/// assert_eq!(predicates::incircle(&a, &b, &c, &Vec2::new(1.0, 1.0)), Ordering::Greater);
+/// assert_eq!(predicates::incircle(&a, &b, &c, &Vec2::new(0.0, 2.0)), Ordering::Equal);
+/// assert_eq!(predicates::incircle(&a, &b, &c, &Vec2::new(3.0, 3.0)), Ordering::Less);
+/// ```
+pub fn incircle(a: &Vec2, b: &Vec2, c: &Vec2, d: &Vec2) -> Ordering {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift +
+                    (cdxady.abs() + adxcdy.abs()) * blift +
+                    (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > INCIRCLE_ERROR_BOUND * permanent {
+        return sign(det);
+    }
+    incircle_exact(a, b, c, d).unwrap_or_else(|| sign(det))
+}
+
+/// Computes the incircle test with exact rational arithmetic, as described in `incircle`.
+/// Returns `None` if a coordinate isn't finite or an intermediate value doesn't fit in
+/// `Rational`.
+pub fn incircle_exact(a: &Vec2, b: &Vec2, c: &Vec2, d: &Vec2) -> Option<Ordering> {
+    let (dx, dy) = to_rational(d)?;
+    let lifted = |point: &Vec2| -> Option<(Rational, Rational, Rational)> {
+        let (x, y) = to_rational(point)?;
+        let (x, y) = (x.checked_sub(&dx)?, y.checked_sub(&dy)?);
+        let lift = x.checked_mul(&x)?.checked_add(&y.checked_mul(&y)?)?;
+        Some((x, y, lift))
+    };
+    let (adx, ady, alift) = lifted(a)?;
+    let (bdx, bdy, blift) = lifted(b)?;
+    let (cdx, cdy, clift) = lifted(c)?;
+    let cross = |ux: &Rational, uy: &Rational, vx: &Rational, vy: &Rational| -> Option<Rational> {
+        ux.checked_mul(vy)?.checked_sub(&vx.checked_mul(uy)?)
+    };
+    let det = alift.checked_mul(&cross(&bdx, &bdy, &cdx, &cdy)?)?
+        .checked_add(&blift.checked_mul(&cross(&cdx, &cdy, &adx, &ady)?)?)?
+        .checked_add(&clift.checked_mul(&cross(&adx, &ady, &bdx, &bdy)?)?)?;
+    Some(det.cmp(&Rational::ZERO))
+}
+
+fn to_rational(point: &Vec2) -> Option<(Rational, Rational)> {
+    Some((Rational::from_f64(point.x)?, Rational::from_f64(point.y)?))
+}
+
+fn sign(value: f64) -> Ordering {
+    value.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+}
@@ -0,0 +1,275 @@
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::cmp::Ordering;
+
+/// An exact fraction of two `i128`, always kept in lowest terms with a positive denominator.
+///
+/// Every finite f64 with a reasonable exponent converts to a Rational exactly, which makes it
+/// suitable for geometric predicates that must not suffer from rounding. The range is bounded by
+/// `i128`: the `checked_*` methods return `None` when a result can't be represented, while the
+/// operators panic in that case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    /// Allocates a new Rational equal to `numer / denom`, reduced to lowest terms. Panics if
+    /// `denom` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// let half = Rational::new(-3, -6);
+    /// assert_eq!(half.numer(), 1);
+    /// assert_eq!(half.denom(), 2);
+    /// ```
+    pub fn new(numer: i128, denom: i128) -> Rational {
+        Rational::checked_new(numer, denom).expect("invalid rational: zero or unrepresentable denominator")
+    }
+
+    /// Allocates a new Rational with the given integer value.
+    pub fn from_int(value: i128) -> Rational {
+        Rational { numer: value, denom: 1 }
+    }
+
+    /// Converts the float to the exactly equal Rational. Returns `None` for infinities, NaN and
+    /// values whose exact fraction doesn't fit in `i128`.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// assert_eq!(Rational::from_f64(0.75), Some(Rational::new(3, 4)));
+    /// assert_eq!(Rational::from_f64(-6.0), Some(Rational::from_int(-6)));
+    /// // 0.1 isn't 1/10 in binary, but its exact value is still representable.
+    /// assert!(Rational::from_f64(0.1).unwrap() != Rational::new(1, 10));
+    /// assert_eq!(Rational::from_f64(f64::NAN), None);
+    /// assert_eq!(Rational::from_f64(1.0e300), None);
+    /// ```
+    pub fn from_f64(value: f64) -> Option<Rational> {
+        if !value.is_finite() {
+            return None;
+        }
+        if value == 0.0 {
+            return Some(Rational::ZERO);
+        }
+        let bits = value.to_bits();
+        let sign = if bits >> 63 == 0 { 1 } else { -1 };
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & 0xf_ffff_ffff_ffff) as i128;
+        // value == sign * mantissa * 2^exponent
+        let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+        let zeros = mantissa.trailing_zeros() as i32;
+        mantissa >>= zeros;
+        exponent += zeros;
+        if exponent >= 0 {
+            if exponent >= 126 || mantissa.leading_zeros() as i32 <= exponent + 1 {
+                return None;
+            }
+            Some(Rational { numer: sign * (mantissa << exponent), denom: 1 })
+        } else {
+            if -exponent >= 127 {
+                return None;
+            }
+            Some(Rational { numer: sign * mantissa, denom: 1 << -exponent })
+        }
+    }
+
+    /// Returns the numerator of the reduced fraction.
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    /// Returns the denominator of the reduced fraction, which is always positive.
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    /// Returns the nearest float to the fraction, approximately.
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+
+    /// Returns `-1`, `0` or `1` depending on the sign of the number.
+    pub fn signum(&self) -> i128 {
+        self.numer.signum()
+    }
+
+    /// Returns true if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+
+    /// Same as `new`, returning `None` instead of panicking.
+    pub fn checked_new(numer: i128, denom: i128) -> Option<Rational> {
+        if denom == 0 {
+            return None;
+        }
+        let divisor = gcd(numer, denom) as i128;
+        let (numer, denom) = (numer / divisor, denom / divisor);
+        if denom < 0 {
+            Some(Rational { numer: numer.checked_neg()?, denom: denom.checked_neg()? })
+        } else {
+            Some(Rational { numer, denom })
+        }
+    }
+
+    /// Adds the two numbers, returning `None` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// let sum = Rational::new(1, 6).checked_add(&Rational::new(1, 3));
+    /// assert_eq!(sum, Some(Rational::new(1, 2)));
+    /// assert_eq!(Rational::from_int(i128::MAX).checked_add(&Rational::ONE), None);
+    /// ```
+    pub fn checked_add(&self, other: &Rational) -> Option<Rational> {
+        let divisor = gcd(self.denom, other.denom) as i128;
+        let left = self.numer.checked_mul(other.denom / divisor)?;
+        let right = other.numer.checked_mul(self.denom / divisor)?;
+        let denom = (self.denom / divisor).checked_mul(other.denom)?;
+        Rational::checked_new(left.checked_add(right)?, denom)
+    }
+
+    /// Substracts the two numbers, returning `None` on overflow.
+    pub fn checked_sub(&self, other: &Rational) -> Option<Rational> {
+        self.checked_add(&Rational { numer: other.numer.checked_neg()?, denom: other.denom })
+    }
+
+    /// Multiplies the two numbers, returning `None` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// let product = Rational::new(2, 3).checked_mul(&Rational::new(9, 4));
+    /// assert_eq!(product, Some(Rational::new(3, 2)));
+    /// ```
+    pub fn checked_mul(&self, other: &Rational) -> Option<Rational> {
+        let first = gcd(self.numer, other.denom) as i128;
+        let second = gcd(other.numer, self.denom) as i128;
+        let numer = (self.numer / first).checked_mul(other.numer / second)?;
+        let denom = (self.denom / second).checked_mul(other.denom / first)?;
+        Rational::checked_new(numer, denom)
+    }
+
+    /// Divides the two numbers, returning `None` on overflow or division by zero.
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        if other.numer == 0 {
+            return None;
+        }
+        self.checked_mul(&Rational::checked_new(other.denom, other.numer)?)
+    }
+}
+
+/// Greatest common divisor of the absolute values, never zero so it is always safe to divide by.
+fn gcd(a: i128, b: i128) -> u128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+    if a == 0 { 1 } else { a }
+}
+
+impl Ord for Rational {
+    /// Compares the fractions exactly, without any risk of overflow, by comparing the terms of
+    /// their continued fraction expansions.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// assert!(Rational::new(1, 3) < Rational::new(1, 2));
+    /// assert!(Rational::new(-1, 2) < Rational::new(-1, 3));
+    /// let big = i128::MAX;
+    /// assert!(Rational::new(big - 2, big - 1) < Rational::new(big - 1, big));
+    /// ```
+    fn cmp(&self, other: &Rational) -> Ordering {
+        let (mut a, mut b, mut c, mut d) = (self.numer, self.denom, other.numer, other.denom);
+        let mut reversed = false;
+        loop {
+            let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+            let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+            let ordering = if q1 != q2 {
+                q1.cmp(&q2)
+            } else if r1 == 0 || r2 == 0 {
+                // At most one of them is non-zero, and that one is the larger fraction.
+                r1.cmp(&r2)
+            } else {
+                // Same integer part: r1/b vs r2/d is the reverse of b/r1 vs d/r2.
+                a = b;
+                b = r1;
+                c = d;
+                d = r2;
+                reversed = !reversed;
+                continue;
+            };
+            return if reversed { ordering.reverse() } else { ordering };
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<Rational> for Rational {
+    type Output = Rational;
+
+    /// Adds the two numbers. Panics on overflow.
+    fn add(self, other: Rational) -> Rational {
+        self.checked_add(&other).expect("rational addition overflowed")
+    }
+}
+
+impl Sub<Rational> for Rational {
+    type Output = Rational;
+
+    /// Substracts the two numbers. Panics on overflow.
+    fn sub(self, other: Rational) -> Rational {
+        self.checked_sub(&other).expect("rational substraction overflowed")
+    }
+}
+
+impl Mul<Rational> for Rational {
+    type Output = Rational;
+
+    /// Multiplies the two numbers. Panics on overflow.
+    fn mul(self, other: Rational) -> Rational {
+        self.checked_mul(&other).expect("rational multiplication overflowed")
+    }
+}
+
+impl Div<Rational> for Rational {
+    type Output = Rational;
+
+    /// Divides the two numbers. Panics on overflow or division by zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Rational;
+    /// let value = (Rational::new(1, 2) + Rational::new(1, 4)) / Rational::new(3, 1);
+    /// assert_eq!(value, Rational::new(1, 4));
+    /// ```
+    fn div(self, other: Rational) -> Rational {
+        self.checked_div(&other).expect("rational division overflowed or divided by zero")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    /// Negates the number. Panics on overflow, which only `i128::MIN` as a numerator can cause.
+    fn neg(self) -> Rational {
+        Rational { numer: self.numer.checked_neg().expect("rational negation overflowed"), denom: self.denom }
+    }
+}
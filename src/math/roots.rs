@@ -0,0 +1,202 @@
+use math::approx_eq::ApproxEq;
+
+/// Stopping criteria shared by the root finders. A root is accepted once the bracket around it
+/// (or the Newton step) is narrower than `x`, or once the function value at it is
+/// approximately zero within `f`, as decided by `ApproxEq::approx_eq_eps`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub x: f64,
+    pub f: f64,
+    pub max_iterations: usize,
+}
+
+impl Tolerance {
+
+    /// Allocates a new Tolerance on the root position and on the function value, with the
+    /// default iteration limit.
+    pub fn new(x: f64, f: f64) -> Tolerance {
+        Tolerance { x, f, max_iterations: 100 }
+    }
+
+    /// Returns the tolerance with a different iteration limit.
+    pub fn with_max_iterations(self, max_iterations: usize) -> Tolerance {
+        Tolerance { max_iterations, ..self }
+    }
+}
+
+impl Default for Tolerance {
+    /// Tolerances of `1e-12` on both the position and the value, within 100 iterations.
+    fn default() -> Tolerance {
+        Tolerance::new(1e-12, 1e-12)
+    }
+}
+
+/// Finds a root of `f` in `[a, b]` by bisection. The function must have opposite signs at the
+/// two ends; returns `None` otherwise. Bisection is slow but never fails on a valid bracket,
+/// so it returns the midpoint of the last bracket even if it runs out of iterations.
+///
+/// # Examples
+/// ```
+/// use candle::math::ApproxEq;
+/// use candle::math::roots::{self, Tolerance};
+/// let root = roots::bisection(|x| x * x - 2.0, 0.0, 2.0, &Tolerance::default()).unwrap();
+/// assert!(root.approx_eq_eps(2f64.sqrt(), 1e-12));
+/// assert_eq!(roots::bisection(|x| x * x + 1.0, -1.0, 1.0, &Tolerance::default()), None);
+/// ```
+pub fn bisection<F>(f: F, a: f64, b: f64, tolerance: &Tolerance) -> Option<f64>
+    where F: Fn(f64) -> f64
+{
+    let (mut a, mut b) = (a, b);
+    let (fa, fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+    for _ in 0..tolerance.max_iterations {
+        let mid = (a + b) / 2.0;
+        let value = f(mid);
+        if value == 0.0 || value.approx_eq_eps(0.0, tolerance.f) || (b - a).abs() / 2.0 < tolerance.x {
+            return Some(mid);
+        }
+        if value.signum() == fa.signum() {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+    Some((a + b) / 2.0)
+}
+
+/// Finds a root of `f` with Newton's method, starting at `x` and using the derivative `df`.
+/// Converges quickly near simple roots, but may diverge from a poor starting point; returns
+/// `None` if the derivative vanishes, the iterate stops being finite or the iterations run out.
+///
+/// # Examples
+/// ```
+/// use candle::math::ApproxEq;
+/// use candle::math::roots::{self, Tolerance};
+/// let root = roots::newton(|x| x.cos() - x, |x| -x.sin() - 1.0, 1.0, &Tolerance::default());
+/// assert!(root.unwrap().approx_eq_eps(0.739085133215161, 1e-12));
+/// assert_eq!(roots::newton(|x| x * x + 1.0, |x| 2.0 * x, 0.0, &Tolerance::default()), None);
+/// ```
+pub fn newton<F, D>(f: F, df: D, x: f64, tolerance: &Tolerance) -> Option<f64>
+    where F: Fn(f64) -> f64, D: Fn(f64) -> f64
+{
+    let mut x = x;
+    for _ in 0..tolerance.max_iterations {
+        let value = f(x);
+        if value.approx_eq_eps(0.0, tolerance.f) {
+            return Some(x);
+        }
+        let slope = df(x);
+        if slope == 0.0 {
+            return None;
+        }
+        let next = x - value / slope;
+        if !next.is_finite() {
+            return None;
+        }
+        if next.approx_eq_eps(x, tolerance.x) {
+            return Some(next);
+        }
+        x = next;
+    }
+    None
+}
+
+/// Finds a root of `f` in `[a, b]` with Brent's method, which combines inverse quadratic
+/// interpolation and secant steps with bisection: it converges nearly as fast as Newton's
+/// method without a derivative, and is as safe as bisection. The function must have opposite
+/// signs at the two ends; returns `None` otherwise or if the iterations run out.
+///
+/// # Examples
+/// ```
+/// use candle::math::ApproxEq;
+/// use candle::math::roots::{self, Tolerance};
+/// // The time at which a body falling from 10 units reaches the ground.
+/// let height = |t: f64| 10.0 - 4.9 * t * t;
+/// let time = roots::brent(height, 0.0, 5.0, &Tolerance::default()).unwrap();
+/// assert!(time.approx_eq_eps((10.0f64 / 4.9).sqrt(), 1e-12));
+/// ```
+pub fn brent<F>(f: F, a: f64, b: f64, tolerance: &Tolerance) -> Option<f64>
+    where F: Fn(f64) -> f64
+{
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+
+    // `b` is the best estimate, `c` the other end of the bracket, `a` the previous estimate.
+    let (mut c, mut fc) = (b, fb);
+    let mut step = b - a;
+    let mut previous_step = step;
+    for _ in 0..tolerance.max_iterations {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            step = b - a;
+            previous_step = step;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let min_step = 2.0 * f64::EPSILON * b.abs() + tolerance.x / 2.0;
+        let half = (c - b) / 2.0;
+        if fb == 0.0 || half.abs() <= min_step || fb.approx_eq_eps(0.0, tolerance.f) {
+            return Some(b);
+        }
+
+        if previous_step.abs() >= min_step && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant step.
+                (2.0 * half * s, 1.0 - s)
+            } else {
+                // Inverse quadratic interpolation.
+                let q = fa / fc;
+                let r = fb / fc;
+                (s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            // Accept the interpolation only if it falls inside the bracket and shrinks fast
+            // enough; otherwise bisect.
+            if 2.0 * p < (3.0 * half * q - (min_step * q).abs()).min((previous_step * q).abs()) {
+                previous_step = step;
+                step = p / q;
+            } else {
+                step = half;
+                previous_step = step;
+            }
+        } else {
+            step = half;
+            previous_step = step;
+        }
+
+        a = b;
+        fa = fb;
+        b += if step.abs() > min_step { step } else { min_step.copysign(half) };
+        fb = f(b);
+    }
+    None
+}
@@ -0,0 +1,110 @@
+/// Linearly interpolates between `a` and `b`, where `t` of `0.0` returns `a` and `1.0` returns
+/// `b`. `t` is not clamped.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::lerp(2.0, 4.0, 0.5), 3.0);
+/// assert_eq!(scalar::lerp(2.0, 4.0, 2.0), 6.0);
+/// ```
+pub fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Returns the `t` for which `lerp(a, b, t)` equals `value`. Returns `0.0` when `a` and `b`
+/// are equal.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::inverse_lerp(2.0, 4.0, 3.0), 0.5);
+/// assert_eq!(scalar::inverse_lerp(2.0, 4.0, 6.0), 2.0);
+/// ```
+pub fn inverse_lerp(a: f64, b: f64, value: f64) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+    (value - a) / (b - a)
+}
+
+/// Maps `value` from the `in_range` to the `out_range`, both given as `(start, end)`. The value
+/// is not clamped to the ranges.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::remap(5.0, (0.0, 10.0), (100.0, 200.0)), 150.0);
+/// assert_eq!(scalar::remap(0.25, (0.0, 1.0), (1.0, -1.0)), 0.5);
+/// ```
+pub fn remap(value: f64, in_range: (f64, f64), out_range: (f64, f64)) -> f64 {
+    lerp(out_range.0, out_range.1, inverse_lerp(in_range.0, in_range.1, value))
+}
+
+/// Hermite interpolation from `0.0` to `1.0` as `x` goes from `edge0` to `edge1`, with zero
+/// slope at both edges. Values outside the edges are clamped.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::smoothstep(0.0, 1.0, -1.0), 0.0);
+/// assert_eq!(scalar::smoothstep(0.0, 1.0, 0.5), 0.5);
+/// assert_eq!(scalar::smoothstep(0.0, 1.0, 0.25), 0.15625);
+/// assert_eq!(scalar::smoothstep(0.0, 1.0, 2.0), 1.0);
+/// ```
+pub fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = inverse_lerp(edge0, edge1, x).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Ken Perlin's variant of `smoothstep`, which also has zero second derivative at both edges.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::smootherstep(0.0, 1.0, 0.5), 0.5);
+/// assert_eq!(scalar::smootherstep(0.0, 1.0, 0.25), 0.103515625);
+/// assert_eq!(scalar::smootherstep(10.0, 20.0, 30.0), 1.0);
+/// ```
+pub fn smootherstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = inverse_lerp(edge0, edge1, x).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Wraps `value` into the `[min, max)` range, as needed for looping coordinates and timers. The
+/// bounds may be given in either order.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::wrap(370.0, 0.0, 360.0), 10.0);
+/// assert_eq!(scalar::wrap(-1.0, 0.0, 360.0), 359.0);
+/// assert_eq!(scalar::wrap(5.0, 2.0, 4.0), 3.0);
+/// assert_eq!(scalar::wrap(1.5, 1.0, 0.0), 0.5);
+/// ```
+pub fn wrap(value: f64, min: f64, max: f64) -> f64 {
+    let (low, range) = (min.min(max), (max - min).abs());
+    if range == 0.0 {
+        return min;
+    }
+    low + (value - low).rem_euclid(range)
+}
+
+/// Bounces `t` back and forth between `0.0` and `length`, which may be negative.
+///
+/// # Examples
+/// ```
+/// use candle::math::scalar;
+/// assert_eq!(scalar::ping_pong(1.5, 2.0), 1.5);
+/// assert_eq!(scalar::ping_pong(2.5, 2.0), 1.5);
+/// assert_eq!(scalar::ping_pong(4.5, 2.0), 0.5);
+/// assert_eq!(scalar::ping_pong(-0.5, 2.0), 0.5);
+/// assert_eq!(scalar::ping_pong(2.5, -2.0), -1.5);
+/// ```
+pub fn ping_pong(t: f64, length: f64) -> f64 {
+    if length == 0.0 {
+        return 0.0;
+    }
+    let span = length.abs();
+    let t = t.rem_euclid(span * 2.0);
+    (span - (t - span).abs()) * length.signum()
+}
@@ -0,0 +1,259 @@
+use math::vec2::Vec2;
+use math::mat2::Mat2;
+use math::angle::Angle;
+use std::f64::consts::PI;
+
+/// The principal components of a 2D point set: the `mean` of the points, the perpendicular unit
+/// `axes` along which they spread the most and the least, and the `variances` of the points
+/// along each axis, in the same order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pca {
+    pub mean: Vec2,
+    pub axes: (Vec2, Vec2),
+    pub variances: (f64, f64),
+}
+
+impl Pca {
+
+    /// Expresses `point` in the frame of the principal axes, centered on the mean: `x` along the
+    /// major axis and `y` along the minor one. Dividing by the standard deviations (the square
+    /// roots of the variances) then normalizes the point set to unit spread.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{stats, Vec2, ApproxEq};
+    /// let points = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(4.0, 4.0)];
+    /// let pca = stats::pca(&points).unwrap();
+    /// let local = pca.to_local(&Vec2::new(4.0, 4.0));
+    /// assert!(local.x.abs().approx_eq_eps(8f64.sqrt(), 1e-9));
+    /// assert!(local.y.approx_eq_eps(0.0, 1e-9));
+    /// ```
+    pub fn to_local(&self, point: &Vec2) -> Vec2 {
+        let offset = point - self.mean;
+        Vec2::new(offset.dot(&self.axes.0), offset.dot(&self.axes.1))
+    }
+}
+
+/// Returns the mean of the points, or `None` if there are none.
+pub fn mean(points: &[Vec2]) -> Option<Vec2> {
+    if points.is_empty() {
+        return None;
+    }
+    Some(points.iter().sum::<Vec2>() / points.len() as f64)
+}
+
+/// Returns the covariance matrix of the points, dividing by the number of points (the
+/// population covariance), or `None` if there are none.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Mat2, Vec2};
+/// let points = [Vec2::new(-1.0, -2.0), Vec2::new(1.0, 2.0)];
+/// assert_eq!(stats::covariance(&points), Some(Mat2::new(1.0, 2.0, 2.0, 4.0)));
+/// ```
+pub fn covariance(points: &[Vec2]) -> Option<Mat2> {
+    let mean = mean(points)?;
+    let mut sum = Mat2::ZERO;
+    for point in points {
+        let d = point - mean;
+        sum = sum + Mat2::new(d.x * d.x, d.x * d.y, d.y * d.x, d.y * d.y);
+    }
+    Some(sum * (1.0 / points.len() as f64))
+}
+
+/// Computes the principal component analysis of the points from the eigen-decomposition of
+/// their covariance. Returns `None` if there are no points.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Vec2, ApproxEq};
+/// let points = [Vec2::new(-3.0, 1.0), Vec2::new(3.0, 1.0), Vec2::new(0.0, 2.0), Vec2::new(0.0, 0.0)];
+/// let pca = stats::pca(&points).unwrap();
+/// assert!(pca.mean.approx_eq(Vec2::new(0.0, 1.0)));
+/// assert!(pca.axes.0.x.abs().approx_eq(1.0));
+/// assert!(pca.variances.0.approx_eq(4.5));
+/// assert!(pca.variances.1.approx_eq(0.5));
+/// ```
+pub fn pca(points: &[Vec2]) -> Option<Pca> {
+    let mean = mean(points)?;
+    let (variances, axes) = covariance(points)?.symmetric_eigen();
+    Some(Pca { mean, axes, variances })
+}
+
+/// Summary statistics of a 2D point set, gathered in a single pass over the points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointStats {
+    pub count: usize,
+    pub centroid: Vec2,
+    /// The population covariance, as returned by `covariance`.
+    pub covariance: Mat2,
+    /// The minimum corner of the bounding box.
+    pub min: Vec2,
+    /// The maximum corner of the bounding box.
+    pub max: Vec2,
+    /// The first points with the smallest x, the largest x, the smallest y and the largest y,
+    /// in that order.
+    pub extremes: [Vec2; 4],
+}
+
+impl PointStats {
+
+    /// Computes the statistics of the points in one pass, accumulating the covariance with
+    /// Welford's algorithm, which stays accurate for points far from the origin. Returns `None`
+    /// if there are no points.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Mat2, Vec2, ApproxEq};
+    /// use candle::math::stats::PointStats;
+    /// let points = [Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0), Vec2::new(3.0, 4.0), Vec2::new(1.0, 2.0)];
+    /// let stats = PointStats::from_points(&points).unwrap();
+    /// assert_eq!(stats.count, 4);
+    /// assert!(stats.centroid.approx_eq(Vec2::new(2.0, 2.0)));
+    /// assert!(stats.covariance.approx_eq(Mat2::new(1.0, 0.5, 0.5, 1.5)));
+    /// assert_eq!((stats.min, stats.max), (Vec2::new(1.0, 1.0), Vec2::new(3.0, 4.0)));
+    /// assert_eq!(stats.extremes[3], Vec2::new(3.0, 4.0));
+    /// ```
+    pub fn from_points(points: &[Vec2]) -> Option<PointStats> {
+        let first = *points.first()?;
+        let mut mean = Vec2::new(0.0, 0.0);
+        let mut comoment = Mat2::ZERO;
+        let (mut min, mut max) = (first, first);
+        let mut extremes = [first; 4];
+        for (index, point) in points.iter().enumerate() {
+            let before = point - mean;
+            mean = mean + before / (index + 1) as f64;
+            let after = point - mean;
+            comoment = comoment + Mat2::new(before.x * after.x, before.x * after.y, before.y * after.x, before.y * after.y);
+
+            min = Vec2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Vec2::new(max.x.max(point.x), max.y.max(point.y));
+            if point.x < extremes[0].x {
+                extremes[0] = *point;
+            }
+            if point.x > extremes[1].x {
+                extremes[1] = *point;
+            }
+            if point.y < extremes[2].y {
+                extremes[2] = *point;
+            }
+            if point.y > extremes[3].y {
+                extremes[3] = *point;
+            }
+        }
+        // The two off-diagonal terms differ by rounding only; keep the matrix exactly symmetric.
+        let cross = (comoment.m01 + comoment.m10) / 2.0;
+        let count = points.len();
+        let covariance = Mat2::new(comoment.m00, cross, cross, comoment.m11) * (1.0 / count as f64);
+        Some(PointStats { count, centroid: mean, covariance, min, max, extremes })
+    }
+
+    /// Returns the principal component analysis of the points, as `pca` would.
+    pub fn pca(&self) -> Pca {
+        let (variances, axes) = self.covariance.symmetric_eigen();
+        Pca { mean: self.centroid, axes, variances }
+    }
+}
+
+/// Returns the circular mean of the angles: the direction of the sum of their unit vectors.
+/// Unlike averaging raw radians, it is unaffected by wrapping, so the mean of 170° and -170° is
+/// 180° rather than 0°. Returns `None` if there are no angles or they cancel out.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Angle, ApproxEq};
+/// let angles = [Angle::from_degrees(170.0), Angle::from_degrees(-170.0)];
+/// assert!(stats::circular_mean(&angles).unwrap().to_degrees().approx_eq_eps(180.0, 1e-9));
+/// assert_eq!(stats::circular_mean(&[Angle::from_degrees(0.0), Angle::from_degrees(180.0)]), None);
+/// ```
+pub fn circular_mean(angles: &[Angle]) -> Option<Angle> {
+    let sum = angles.iter().map(|angle| angle.to_vec2()).sum::<Vec2>();
+    if sum.length() <= angles.len() as f64 * 1e-12 {
+        return None;
+    }
+    Some(Angle::from_vec2(&sum))
+}
+
+/// Returns the circular variance of the angles, one minus the length of the mean of their unit
+/// vectors: zero when they all agree and one when they spread evenly around the circle. Returns
+/// `None` if there are no angles.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Angle, ApproxEq};
+/// let agreeing = [Angle::from_degrees(30.0); 3];
+/// assert!(stats::circular_variance(&agreeing).unwrap().approx_eq_eps(0.0, 1e-12));
+/// let opposite = [Angle::from_degrees(90.0), Angle::from_degrees(-90.0)];
+/// assert!(stats::circular_variance(&opposite).unwrap().approx_eq_eps(1.0, 1e-12));
+/// ```
+pub fn circular_variance(angles: &[Angle]) -> Option<f64> {
+    if angles.is_empty() {
+        return None;
+    }
+    let sum = angles.iter().map(|angle| angle.to_vec2()).sum::<Vec2>();
+    Some(1.0 - sum.length() / angles.len() as f64)
+}
+
+/// Builds a histogram of the headings of the velocities over `bins` equal sectors, each velocity
+/// counting for its speed so that lingering doesn't outweigh movement. Bin `i` is centered on
+/// the heading `i * 2π / bins`, so the first one straddles the positive x axis. Zero velocities
+/// are ignored. Returns an empty histogram if `bins` is zero.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Vec2};
+/// let velocities = [Vec2::new(2.0, 0.0), Vec2::new(0.0, 1.0), Vec2::new(0.0, 1.0), Vec2::new(0.0, 0.0)];
+/// assert_eq!(stats::heading_histogram(&velocities, 4), vec![2.0, 2.0, 0.0, 0.0]);
+/// ```
+pub fn heading_histogram(velocities: &[Vec2], bins: usize) -> Vec<f64> {
+    let mut histogram = vec![0.0; bins];
+    for velocity in velocities {
+        let speed = velocity.length();
+        if bins == 0 || speed == 0.0 {
+            continue;
+        }
+        histogram[heading_bin(velocity, bins)] += speed;
+    }
+    histogram
+}
+
+/// Extracts the dominant direction of movement from the velocities: the speed-weighted circular
+/// mean of the velocities in the peak sector of their `bins`-sector heading histogram and its
+/// two neighbours. Focusing on the peak keeps back-and-forth or multi-modal movement from
+/// averaging out to a direction nobody took. Returns `None` if `bins` is zero or all the
+/// velocities are zero.
+///
+/// # Examples
+/// ```
+/// use candle::math::{stats, Vec2, ApproxEq};
+/// let velocities = [
+///     Vec2::new(1.0, 0.1), Vec2::new(1.0, -0.1), Vec2::new(1.0, 0.0),
+///     Vec2::new(-1.0, 0.0), Vec2::new(0.0, 1.0),
+/// ];
+/// let dominant = stats::dominant_direction(&velocities, 8).unwrap();
+/// assert!(dominant.to_degrees().approx_eq_eps(0.0, 1e-9));
+/// ```
+pub fn dominant_direction(velocities: &[Vec2], bins: usize) -> Option<Angle> {
+    let histogram = heading_histogram(velocities, bins);
+    let (peak, &weight) = histogram.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))?;
+    if weight == 0.0 {
+        return None;
+    }
+    let sum = velocities.iter()
+        .filter(|velocity| velocity.length() > 0.0)
+        .filter(|velocity| {
+            let distance = (heading_bin(velocity, bins) + bins - peak) % bins;
+            distance <= 1 || distance == bins - 1
+        })
+        .sum::<Vec2>();
+    if sum.length() == 0.0 {
+        return None;
+    }
+    Some(Angle::from_vec2(&sum))
+}
+
+fn heading_bin(velocity: &Vec2, bins: usize) -> usize {
+    let width = 2.0 * PI / bins as f64;
+    let turns = (velocity.y.atan2(velocity.x) + width / 2.0).rem_euclid(2.0 * PI);
+    ((turns / width) as usize).min(bins - 1)
+}
@@ -1,15 +1,15 @@
-use std::ops::{Add, Sub};
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::iter::{FromIterator, Sum};
+use std::array;
 use std::f64;
 use math::approx_eq::ApproxEq;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vec2 {
     pub x: f64,
     pub y: f64,
 }
 
-const VEC2_EPSILON : Vec2  = Vec2{ x: f64::EPSILON, y: f64::EPSILON };
-const VEC2_IDENTITY : Vec2 = Vec2{ x: 1.0, y: 1.0 };
-
 impl Vec2 {
 
     /// Allocates a new Vec2 with the given `x` and `y`.
@@ -25,7 +25,7 @@ impl Vec2 {
     /// let vec2 = math::Vec2{ x: 1.0, y: 2.0 };
     /// ```
     pub fn new(x: f64, y: f64) -> Vec2 {
-        Vec2 { x: x, y: y }
+        Vec2 { x, y }
     }
 
     /// Returns the dot product between two vectors.
@@ -69,6 +69,37 @@ impl Vec2 {
         let length = self.length();
         Vec2::new(self.x / length, self.y / length)
     }
+
+    /// Rotates the vector counterclockwise by `angle` radians around the `pivot` point,
+    /// returning a new vector with the rotated coordinates.
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// use std::f64::consts::PI;
+    /// let vec = Vec2::new(3.0, 2.0);
+    /// let pivot = Vec2::new(2.0, 2.0);
+    /// let rotated = vec.rotate_around(&pivot, PI / 2.0);
+    /// assert!(rotated.approx_eq(Vec2::new(2.0, 3.0)));
+    /// ```
+    pub fn rotate_around(&self, pivot: &Vec2, angle: f64) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        let dx = self.x - pivot.x;
+        let dy = self.y - pivot.y;
+        Vec2::new(pivot.x + dx * cos - dy * sin, pivot.y + dx * sin + dy * cos)
+    }
+
+    /// Returns an iterator over the vector coordinates, `x` first and then `y`.
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec = Vec2::new(3.0, 4.0);
+    /// let coords: Vec<f64> = vec.iter().collect();
+    /// assert_eq!(coords, vec![3.0, 4.0]);
+    /// assert_eq!(vec.iter().fold(0.0, f64::max), 4.0);
+    /// ```
+    pub fn iter(&self) -> array::IntoIter<f64, 2> {
+        IntoIterator::into_iter([self.x, self.y])
+    }
 }
 
 impl Add<Vec2> for Vec2 {
@@ -90,7 +121,7 @@ impl Add<Vec2> for Vec2 {
     }
 }
 
-impl<'a> Add<&'a Vec2> for Vec2 {
+impl Add<&Vec2> for Vec2 {
     type Output = Vec2;
 
     /// Adds the coordinates of two vetors, returning a new result vector.
@@ -104,12 +135,12 @@ impl<'a> Add<&'a Vec2> for Vec2 {
     /// assert_eq!(sum.x, 3.0);
     /// assert_eq!(sum.y, 6.0);
     /// ```
-    fn add(self, other: &'a Vec2) -> Vec2 {
+    fn add(self, other: &Vec2) -> Vec2 {
         Vec2 { x: self.x + other.x, y: self.y + other.y }
     }
 }
 
-impl<'a, 'b> Add<&'b Vec2> for &'a Vec2 {
+impl Add<&Vec2> for &Vec2 {
     type Output = Vec2;
 
     /// Adds the coordinates of two vetors, returning a new result vector.
@@ -123,12 +154,12 @@ impl<'a, 'b> Add<&'b Vec2> for &'a Vec2 {
     /// assert_eq!(sum.x, 3.0);
     /// assert_eq!(sum.y, 6.0);
     /// ```
-    fn add(self, other: &'b Vec2) -> Vec2 {
+    fn add(self, other: &Vec2) -> Vec2 {
         Vec2 { x: self.x + other.x, y: self.y + other.y }
     }
 }
 
-impl<'b> Add<Vec2> for &'b Vec2 {
+impl Add<Vec2> for &Vec2 {
     type Output = Vec2;
 
     /// Adds the coordinates of two vetors, returning a new result vector.
@@ -165,7 +196,7 @@ impl Add<f64> for Vec2 {
     }
 }
 
-impl<'b> Add<f64> for &'b Vec2 {
+impl Add<f64> for &Vec2 {
     type Output = Vec2;
 
     /// Adds the given number to each vector coordinate.
@@ -201,7 +232,7 @@ impl Sub<Vec2> for Vec2 {
     }
 }
 
-impl<'a> Sub<&'a Vec2> for Vec2 {
+impl Sub<&Vec2> for Vec2 {
     type Output = Vec2;
 
     /// Substracts the coordinates from the two vectors returning a new result vector.
@@ -214,12 +245,12 @@ impl<'a> Sub<&'a Vec2> for Vec2 {
     /// assert_eq!(diff.x, 2.0);
     /// assert_eq!(diff.y, -1.0);
     /// ```
-    fn sub(self, other: &'a Vec2) -> Vec2 {
+    fn sub(self, other: &Vec2) -> Vec2 {
         Vec2::new(self.x - other.x, self.y - other.y)
     }
 }
 
-impl<'a, 'b> Sub<&'a Vec2> for &'b Vec2 {
+impl Sub<&Vec2> for &Vec2 {
     type Output = Vec2;
 
     /// Substracts the coordinates from the two vectors returning a new result vector.
@@ -232,12 +263,12 @@ impl<'a, 'b> Sub<&'a Vec2> for &'b Vec2 {
     /// assert_eq!(diff.x, 2.0);
     /// assert_eq!(diff.y, -1.0);
     /// ```
-    fn sub(self, other: &'a Vec2) -> Vec2 {
+    fn sub(self, other: &Vec2) -> Vec2 {
         Vec2::new(self.x - other.x, self.y - other.y)
     }
 }
 
-impl<'b> Sub<Vec2> for &'b Vec2 {
+impl Sub<Vec2> for &Vec2 {
     type Output = Vec2;
 
     /// Substracts the coordinates from the two vectors returning a new result vector.
@@ -272,7 +303,7 @@ impl Sub<f64> for Vec2 {
     }
 }
 
-impl<'b> Sub<f64> for &'b Vec2 {
+impl Sub<f64> for &Vec2 {
     type Output = Vec2;
 
     /// Substracts the vector coordinates from the given value.
@@ -289,13 +320,158 @@ impl<'b> Sub<f64> for &'b Vec2 {
     }
 }
 
+impl Mul<f64> for Vec2 {
+    type Output = Vec2;
+
+    /// Multiplies each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec1 = Vec2::new(3.0, 4.0);
+    /// let product = vec1 * 2.0;
+    /// assert_eq!(product.x, 6.0);
+    /// assert_eq!(product.y, 8.0);
+    /// ```
+    fn mul(self, other: f64) -> Vec2 {
+        Vec2 { x: self.x * other, y: self.y * other }
+    }
+}
+
+impl Mul<f64> for &Vec2 {
+    type Output = Vec2;
+
+    /// Multiplies each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec1 = Vec2::new(3.0, 4.0);
+    /// let product = &vec1 * 2.0;
+    /// assert_eq!(product.x, 6.0);
+    /// assert_eq!(product.y, 8.0);
+    /// ```
+    fn mul(self, other: f64) -> Vec2 {
+        Vec2 { x: self.x * other, y: self.y * other }
+    }
+}
+
+impl Div<f64> for Vec2 {
+    type Output = Vec2;
+
+    /// Divides each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec1 = Vec2::new(3.0, 4.0);
+    /// let quotient = vec1 / 2.0;
+    /// assert_eq!(quotient.x, 1.5);
+    /// assert_eq!(quotient.y, 2.0);
+    /// ```
+    fn div(self, other: f64) -> Vec2 {
+        Vec2 { x: self.x / other, y: self.y / other }
+    }
+}
+
+impl Div<f64> for &Vec2 {
+    type Output = Vec2;
+
+    /// Divides each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec1 = Vec2::new(3.0, 4.0);
+    /// let quotient = &vec1 / 2.0;
+    /// assert_eq!(quotient.x, 1.5);
+    /// assert_eq!(quotient.y, 2.0);
+    /// ```
+    fn div(self, other: f64) -> Vec2 {
+        Vec2 { x: self.x / other, y: self.y / other }
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    /// Negates each vector coordinate.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec1 = -Vec2::new(3.0, -4.0);
+    /// assert_eq!(vec1.x, -3.0);
+    /// assert_eq!(vec1.y, 4.0);
+    /// ```
+    fn neg(self) -> Vec2 {
+        Vec2 { x: -self.x, y: -self.y }
+    }
+}
+
+impl Sum<Vec2> for Vec2 {
+    /// Adds up all the vectors of the iterator. An empty iterator sums to the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let points = vec![Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0), Vec2::new(5.0, 0.0)];
+    /// let sum: Vec2 = points.into_iter().sum();
+    /// assert_eq!(sum.x, 9.0);
+    /// assert_eq!(sum.y, 6.0);
+    /// ```
+    fn sum<I: Iterator<Item = Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::new(0.0, 0.0), |acc, vec| acc + vec)
+    }
+}
+
+impl<'a> Sum<&'a Vec2> for Vec2 {
+    /// Adds up all the vectors of the iterator. An empty iterator sums to the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let points = vec![Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0), Vec2::new(5.0, 0.0)];
+    /// let sum: Vec2 = points.iter().sum();
+    /// let centroid = Vec2::new(sum.x / points.len() as f64, sum.y / points.len() as f64);
+    /// assert_eq!(centroid.x, 3.0);
+    /// assert_eq!(centroid.y, 2.0);
+    /// ```
+    fn sum<I: Iterator<Item = &'a Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::new(0.0, 0.0), |acc, vec| acc + vec)
+    }
+}
+
+impl FromIterator<f64> for Vec2 {
+    /// Builds a vector from the first two values of the iterator, `x` first and then `y`.
+    /// Missing coordinates default to `0.0` and any extra values are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// let vec: Vec2 = vec![1.0, 2.0].into_iter().collect();
+    /// assert_eq!(vec.x, 1.0);
+    /// assert_eq!(vec.y, 2.0);
+    ///
+    /// let doubled: Vec2 = vec.iter().map(|c| c * 2.0).collect();
+    /// assert_eq!(doubled.x, 2.0);
+    /// assert_eq!(doubled.y, 4.0);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Vec2 {
+        let mut iter = iter.into_iter();
+        let x = iter.next().unwrap_or(0.0);
+        let y = iter.next().unwrap_or(0.0);
+        Vec2::new(x, y)
+    }
+}
+
 impl ApproxEq<Vec2> for Vec2 {
     fn approx_eq_eps(self, other: Vec2, eps: Vec2) -> bool {
         (self.x - other.x).abs() < eps.x && (self.y - other.y).abs() < eps.y
     }
 
-    /// Returns true if the vector is approximately equal the other vector, with EPSILON amount
-    /// of tolerance for both coordinates.
+    /// Returns true if the vector is approximately equal the other vector, comparing both
+    /// coordinates with the combined absolute and relative EPSILON tolerance.
     /// # Examples
     /// ```
     /// use candle::math::{Vec2, ApproxEq};
@@ -304,44 +480,95 @@ impl ApproxEq<Vec2> for Vec2 {
     /// assert!(vec1.approx_eq(vec2));
     /// ```
     fn approx_eq(self, other: Vec2) -> bool {
-        self.approx_eq_eps(other, VEC2_EPSILON)
+        self.x.approx_eq(other.x) && self.y.approx_eq(other.y)
+    }
+
+    /// Returns true if both coordinates are within `max_rel` relative tolerance.
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let vec1 = Vec2::new(1000.0, -2000.0);
+    /// let vec2 = Vec2::new(1000.5, -2001.0);
+    /// assert!(vec1.approx_eq_rel(vec2, 0.001));
+    /// ```
+    fn approx_eq_rel(self, other: Vec2, max_rel: f64) -> bool {
+        self.x.approx_eq_rel(other.x, max_rel) && self.y.approx_eq_rel(other.y, max_rel)
+    }
+
+    /// Returns true if both coordinates are at most `max_ulps` representable floats apart.
+    fn approx_eq_ulps(self, other: Vec2, max_ulps: u64) -> bool {
+        self.x.approx_eq_ulps(other.x, max_ulps) && self.y.approx_eq_ulps(other.y, max_ulps)
     }
 }
 
-impl<'a> ApproxEq<&'a Vec2> for Vec2 {
+impl ApproxEq<&Vec2> for Vec2 {
     fn approx_eq_eps(self, other: &Vec2, eps: &Vec2) -> bool {
         (self.x - other.x).abs() < eps.x && (self.y - other.y).abs() < eps.y
     }
 
-    /// Returns true if the vector is approximately equal the other vector, with EPSILON amount
-    /// of tolerance for both coordinates.
+    /// Returns true if the vector is approximately equal the other vector, comparing both
+    /// coordinates with the combined absolute and relative EPSILON tolerance.
     /// # Examples
     /// ```
     /// use candle::math::{Vec2, ApproxEq};
     /// let vec1 = Vec2::new(1.0, 1.0);
     /// let vec2 = Vec2::new(0.99999999999999999999, 0.99999999999999999999);
-    /// assert!(vec1.approx_eq(vec2));
+    /// assert!(vec1.approx_eq(&vec2));
     /// ```
     fn approx_eq(self, other: &Vec2) -> bool {
-        self.approx_eq_eps(other, &VEC2_EPSILON)
+        self.x.approx_eq(other.x) && self.y.approx_eq(other.y)
+    }
+
+    /// Returns true if both coordinates are within `max_rel` relative tolerance.
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let vec1 = Vec2::new(1000.0, -2000.0);
+    /// let vec2 = Vec2::new(1000.5, -2001.0);
+    /// assert!(vec1.approx_eq_rel(&vec2, 0.001));
+    /// ```
+    fn approx_eq_rel(self, other: &Vec2, max_rel: f64) -> bool {
+        self.x.approx_eq_rel(other.x, max_rel) && self.y.approx_eq_rel(other.y, max_rel)
+    }
+
+    /// Returns true if both coordinates are at most `max_ulps` representable floats apart.
+    fn approx_eq_ulps(self, other: &Vec2, max_ulps: u64) -> bool {
+        self.x.approx_eq_ulps(other.x, max_ulps) && self.y.approx_eq_ulps(other.y, max_ulps)
     }
 }
 
-impl<'a, 'b> ApproxEq<&'a Vec2> for &'b Vec2 {
+impl ApproxEq<&Vec2> for &Vec2 {
     fn approx_eq_eps(self, other: &Vec2, eps: &Vec2) -> bool {
         (self.x - other.x).abs() < eps.x && (self.y - other.y).abs() < eps.y
     }
 
-    /// Returns true if the vector is approximately equal the other vector, with EPSILON amount
-    /// of tolerance for both coordinates.
+    /// Returns true if the vector is approximately equal the other vector, comparing both
+    /// coordinates with the combined absolute and relative EPSILON tolerance.
     /// # Examples
     /// ```
     /// use candle::math::{Vec2, ApproxEq};
     /// let vec1 = &Vec2::new(1.0, 1.0);
     /// let vec2 = Vec2::new(0.99999999999999999999, 0.99999999999999999999);
-    /// assert!(vec1.approx_eq(vec2));
+    /// assert!(vec1.approx_eq(&vec2));
     /// ```
     fn approx_eq(self, other: &Vec2) -> bool {
-        self.approx_eq_eps(other, &VEC2_EPSILON)
+        self.x.approx_eq(other.x) && self.y.approx_eq(other.y)
+    }
+
+    /// Returns true if both coordinates are within `max_rel` relative tolerance.
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let vec1 = &Vec2::new(1000.0, -2000.0);
+    /// let vec2 = Vec2::new(1000.5, -2001.0);
+    /// assert!(vec1.approx_eq_rel(&vec2, 0.001));
+    /// ```
+    fn approx_eq_rel(self, other: &Vec2, max_rel: f64) -> bool {
+        self.x.approx_eq_rel(other.x, max_rel) && self.y.approx_eq_rel(other.y, max_rel)
+    }
+
+    /// Returns true if both coordinates are at most `max_ulps` representable floats apart.
+    fn approx_eq_ulps(self, other: &Vec2, max_ulps: u64) -> bool {
+        self.x.approx_eq_ulps(other.x, max_ulps) && self.y.approx_eq_ulps(other.y, max_ulps)
     }
 }
@@ -0,0 +1,87 @@
+use math::vec2::Vec2;
+
+/// A hashable and totally ordered representation of a Vec2, usable as a `HashMap`/`BTreeMap`
+/// key or for sorting and deduplicating vertices.
+///
+/// Keys are built from the coordinate bit patterns, so two keys are equal only when both
+/// coordinates are exactly equal. `-0.0` and `0.0` map to the same key, as do all NaNs. Use
+/// `Vec2Key::quantized` when nearby positions should collapse into the same key.
+///
+/// Keys order by `x` and then by `y`, following the numeric order of the coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2Key {
+    x: u64,
+    y: u64,
+}
+
+impl Vec2Key {
+
+    /// Builds the key from the exact coordinates of the vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use candle::math::{Vec2, Vec2Key};
+    /// let mut ids = HashMap::new();
+    /// ids.insert(Vec2Key::new(&Vec2::new(1.0, 2.0)), 0);
+    /// ids.insert(Vec2Key::new(&Vec2::new(0.0, 2.0)), 1);
+    /// assert_eq!(ids.get(&Vec2Key::new(&Vec2::new(1.0, 2.0))), Some(&0));
+    /// assert_eq!(ids.get(&Vec2Key::new(&Vec2::new(-0.0, 2.0))), Some(&1));
+    /// ```
+    pub fn new(vec: &Vec2) -> Vec2Key {
+        Vec2Key { x: ordered_bits(vec.x), y: ordered_bits(vec.y) }
+    }
+
+    /// Builds the key after snapping both coordinates to the nearest multiple of `step`, so
+    /// positions that differ by floating point noise share the same key.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, Vec2Key};
+    /// let a = Vec2Key::quantized(&Vec2::new(1.0, 2.0), 0.001);
+    /// let b = Vec2Key::quantized(&Vec2::new(1.0000001, 1.9999999), 0.001);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn quantized(vec: &Vec2, step: f64) -> Vec2Key {
+        Vec2Key::new(&Vec2::new((vec.x / step).round() * step, (vec.y / step).round() * step))
+    }
+
+    /// Returns the vector the key was built from (after quantization, if any).
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, Vec2Key};
+    /// let key = Vec2Key::new(&Vec2::new(-3.5, 4.0));
+    /// let vec = key.to_vec2();
+    /// assert_eq!(vec.x, -3.5);
+    /// assert_eq!(vec.y, 4.0);
+    ///
+    /// let mut keys = vec![Vec2Key::new(&Vec2::new(2.0, 0.0)), key];
+    /// keys.sort();
+    /// assert_eq!(keys[0], key);
+    /// ```
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(from_ordered_bits(self.x), from_ordered_bits(self.y))
+    }
+}
+
+/// Maps a float to an integer whose unsigned order matches the numeric order of the float.
+fn ordered_bits(value: f64) -> u64 {
+    let value = if value == 0.0 {
+        0.0
+    } else if value.is_nan() {
+        f64::NAN
+    } else {
+        value
+    };
+    let bits = value.to_bits();
+    if bits >> 63 == 1 { !bits } else { bits | (1 << 63) }
+}
+
+fn from_ordered_bits(bits: u64) -> f64 {
+    if bits >> 63 == 1 {
+        f64::from_bits(bits & !(1 << 63))
+    } else {
+        f64::from_bits(!bits)
+    }
+}
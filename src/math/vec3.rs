@@ -0,0 +1,255 @@
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::iter::Sum;
+use math::approx_eq::ApproxEq;
+
+/// A 3D vector or point, the counterpart of `Vec2` for the 3D geometry types.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+
+    /// The zero vector.
+    pub const ZERO: Vec3 = Vec3 { x: 0.0, y: 0.0, z: 0.0 };
+
+    /// Allocates a new Vec3 with the given `x`, `y` and `z`.
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    /// Returns the dot product between two vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(1.0, 2.0, 3.0).dot(&Vec3::new(4.0, -5.0, 6.0)), 12.0);
+    /// ```
+    pub fn dot(&self, other: &Vec3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product between two vectors, perpendicular to both and following the
+    /// right-hand rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// let x = Vec3::new(1.0, 0.0, 0.0);
+    /// let y = Vec3::new(0.0, 1.0, 0.0);
+    /// assert_eq!(x.cross(&y), Vec3::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn cross(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Calculates the length of the vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(2.0, 3.0, 6.0).length(), 7.0);
+    /// ```
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the vector scaled to unit length. The zero vector has no direction and gives NaN
+    /// coordinates.
+    pub fn normalized(&self) -> Vec3 {
+        self / self.length()
+    }
+
+    /// Returns the componentwise minimum of the two vectors.
+    pub fn min(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// Returns the componentwise maximum of the two vectors.
+    pub fn max(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+}
+
+impl Add<Vec3> for Vec3 {
+    type Output = Vec3;
+
+    /// Adds the coordinates of two vectors, returning a new result vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(1.0, 2.0, 3.0) + Vec3::new(1.0, 1.0, 1.0), Vec3::new(2.0, 3.0, 4.0));
+    /// ```
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Add<&Vec3> for Vec3 {
+    type Output = Vec3;
+
+    /// Adds the coordinates of two vectors, returning a new result vector.
+    fn add(self, other: &Vec3) -> Vec3 {
+        self + *other
+    }
+}
+
+impl Add<&Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    /// Adds the coordinates of two vectors, returning a new result vector.
+    fn add(self, other: &Vec3) -> Vec3 {
+        *self + *other
+    }
+}
+
+impl Add<Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    /// Adds the coordinates of two vectors, returning a new result vector.
+    fn add(self, other: Vec3) -> Vec3 {
+        *self + other
+    }
+}
+
+impl Sub<Vec3> for Vec3 {
+    type Output = Vec3;
+
+    /// Subtracts the coordinates of two vectors, returning a new result vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(1.0, 2.0, 3.0) - Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 2.0));
+    /// ```
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Sub<&Vec3> for Vec3 {
+    type Output = Vec3;
+
+    /// Subtracts the coordinates of two vectors, returning a new result vector.
+    fn sub(self, other: &Vec3) -> Vec3 {
+        self - *other
+    }
+}
+
+impl Sub<&Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    /// Subtracts the coordinates of two vectors, returning a new result vector.
+    fn sub(self, other: &Vec3) -> Vec3 {
+        *self - *other
+    }
+}
+
+impl Sub<Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    /// Subtracts the coordinates of two vectors, returning a new result vector.
+    fn sub(self, other: Vec3) -> Vec3 {
+        *self - other
+    }
+}
+
+impl Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    /// Multiplies each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(1.0, 2.0, 3.0) * 2.0, Vec3::new(2.0, 4.0, 6.0));
+    /// ```
+    fn mul(self, value: f64) -> Vec3 {
+        Vec3::new(self.x * value, self.y * value, self.z * value)
+    }
+}
+
+impl Mul<f64> for &Vec3 {
+    type Output = Vec3;
+
+    /// Multiplies each vector coordinate by the given number.
+    fn mul(self, value: f64) -> Vec3 {
+        *self * value
+    }
+}
+
+impl Div<f64> for Vec3 {
+    type Output = Vec3;
+
+    /// Divides each vector coordinate by the given number.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec3;
+    /// assert_eq!(Vec3::new(1.0, 2.0, 3.0) / 2.0, Vec3::new(0.5, 1.0, 1.5));
+    /// ```
+    fn div(self, value: f64) -> Vec3 {
+        Vec3::new(self.x / value, self.y / value, self.z / value)
+    }
+}
+
+impl Div<f64> for &Vec3 {
+    type Output = Vec3;
+
+    /// Divides each vector coordinate by the given number.
+    fn div(self, value: f64) -> Vec3 {
+        *self / value
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    /// Negates each vector coordinate.
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Sum<Vec3> for Vec3 {
+    /// Adds up all the vectors of the iterator. An empty iterator sums to the zero vector.
+    fn sum<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::ZERO, |acc, vec| acc + vec)
+    }
+}
+
+impl<'a> Sum<&'a Vec3> for Vec3 {
+    /// Adds up all the vectors of the iterator. An empty iterator sums to the zero vector.
+    fn sum<I: Iterator<Item = &'a Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::ZERO, |acc, vec| acc + vec)
+    }
+}
+
+impl ApproxEq<Vec3> for Vec3 {
+    fn approx_eq_eps(self, other: Vec3, eps: Vec3) -> bool {
+        self.x.approx_eq_eps(other.x, eps.x) && self.y.approx_eq_eps(other.y, eps.y) && self.z.approx_eq_eps(other.z, eps.z)
+    }
+
+    /// Returns true if every coordinate is approximately equal, with the combined absolute and
+    /// relative EPSILON tolerance.
+    fn approx_eq(self, other: Vec3) -> bool {
+        self.x.approx_eq(other.x) && self.y.approx_eq(other.y) && self.z.approx_eq(other.z)
+    }
+
+    fn approx_eq_rel(self, other: Vec3, max_rel: f64) -> bool {
+        self.x.approx_eq_rel(other.x, max_rel) && self.y.approx_eq_rel(other.y, max_rel) &&
+            self.z.approx_eq_rel(other.z, max_rel)
+    }
+
+    fn approx_eq_ulps(self, other: Vec3, max_ulps: u64) -> bool {
+        self.x.approx_eq_ulps(other.x, max_ulps) && self.y.approx_eq_ulps(other.y, max_ulps) &&
+            self.z.approx_eq_ulps(other.z, max_ulps)
+    }
+}
@@ -0,0 +1,4 @@
+mod vertex_cache;
+
+pub use self::vertex_cache::{optimize_vertex_cache, optimize_vertex_cache_with, optimize_vertex_fetch,
+                             reorder_vertices, average_cache_miss_ratio, DEFAULT_CACHE_SIZE};
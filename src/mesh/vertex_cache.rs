@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+/// Number of entries of the simulated post-transform vertex cache used by
+/// `optimize_vertex_cache`. It matches the cache of most desktop GPUs closely enough.
+pub const DEFAULT_CACHE_SIZE: usize = 16;
+
+/// Reorders the triangles of an index buffer to make better use of the post-transform vertex
+/// cache, using the Tipsify algorithm with `DEFAULT_CACHE_SIZE` entries. The triangles and their
+/// winding are preserved, only their order changes.
+///
+/// `indices` is a triangle list; a trailing incomplete triangle is dropped.
+///
+/// # Examples
+/// ```
+/// use candle::mesh;
+/// // A 8x8 grid of quads emitted column by column, which thrashes the cache.
+/// let mut indices = Vec::new();
+/// for x in 0..8u32 {
+///     for y in 0..8u32 {
+///         let v = y * 9 + x;
+///         indices.extend_from_slice(&[v, v + 1, v + 9, v + 1, v + 10, v + 9]);
+///     }
+/// }
+/// let optimized = mesh::optimize_vertex_cache(&indices);
+/// assert_eq!(optimized.len(), indices.len());
+/// assert!(mesh::average_cache_miss_ratio(&optimized, 16) < mesh::average_cache_miss_ratio(&indices, 16));
+/// ```
+pub fn optimize_vertex_cache(indices: &[u32]) -> Vec<u32> {
+    optimize_vertex_cache_with(indices, DEFAULT_CACHE_SIZE)
+}
+
+/// Same as `optimize_vertex_cache`, for a vertex cache of `cache_size` entries.
+pub fn optimize_vertex_cache_with(indices: &[u32], cache_size: usize) -> Vec<u32> {
+    let triangle_count = indices.len() / 3;
+    let vertex_count = indices[..triangle_count * 3].iter().map(|&i| i as usize + 1).max().unwrap_or(0);
+
+    // Vertex -> triangles adjacency, stored as offsets into a flat list.
+    let mut live = vec![0usize; vertex_count];
+    for &index in &indices[..triangle_count * 3] {
+        live[index as usize] += 1;
+    }
+    let mut offsets = vec![0usize; vertex_count + 1];
+    for vertex in 0..vertex_count {
+        offsets[vertex + 1] = offsets[vertex] + live[vertex];
+    }
+    let mut fill = offsets.clone();
+    let mut adjacency = vec![0usize; triangle_count * 3];
+    for triangle in 0..triangle_count {
+        for corner in 0..3 {
+            let vertex = indices[triangle * 3 + corner] as usize;
+            adjacency[fill[vertex]] = triangle;
+            fill[vertex] += 1;
+        }
+    }
+
+    let mut cache_time = vec![0usize; vertex_count];
+    let mut timestamp = cache_size + 1;
+    let mut emitted = vec![false; triangle_count];
+    let mut dead_end: Vec<usize> = Vec::new();
+    let mut cursor = 0;
+    let mut output = Vec::with_capacity(triangle_count * 3);
+
+    let mut fanning = next_live_vertex(&live, &mut cursor);
+    while let Some(vertex) = fanning {
+        let mut candidates = Vec::new();
+        for &triangle in &adjacency[offsets[vertex]..offsets[vertex + 1]] {
+            if emitted[triangle] {
+                continue;
+            }
+            for corner in 0..3 {
+                let index = indices[triangle * 3 + corner];
+                let corner_vertex = index as usize;
+                output.push(index);
+                dead_end.push(corner_vertex);
+                candidates.push(corner_vertex);
+                live[corner_vertex] -= 1;
+                if timestamp - cache_time[corner_vertex] > cache_size {
+                    cache_time[corner_vertex] = timestamp;
+                    timestamp += 1;
+                }
+            }
+            emitted[triangle] = true;
+        }
+
+        // Prefer the candidate that will still be in the cache after its remaining triangles
+        // are emitted, and among those the one that entered the cache earliest.
+        let mut best = None;
+        let mut best_priority = 0;
+        for &candidate in &candidates {
+            if live[candidate] == 0 {
+                continue;
+            }
+            let age = timestamp - cache_time[candidate];
+            let priority = if age + 2 * live[candidate] <= cache_size { age } else { 0 };
+            if best.is_none() || priority > best_priority {
+                best = Some(candidate);
+                best_priority = priority;
+            }
+        }
+
+        fanning = best.or_else(|| {
+            while let Some(candidate) = dead_end.pop() {
+                if live[candidate] > 0 {
+                    return Some(candidate);
+                }
+            }
+            next_live_vertex(&live, &mut cursor)
+        });
+    }
+
+    output
+}
+
+fn next_live_vertex(live: &[usize], cursor: &mut usize) -> Option<usize> {
+    while *cursor < live.len() {
+        if live[*cursor] > 0 {
+            return Some(*cursor);
+        }
+        *cursor += 1;
+    }
+    None
+}
+
+/// Renumbers the vertices in the order they are first referenced by the index buffer, so the
+/// vertex fetch reads memory sequentially. `indices` is rewritten in place and the returned list
+/// maps each new vertex index to the old one; vertices never referenced are moved to the end.
+/// Use `reorder_vertices` to apply the same order to the vertex attributes.
+///
+/// # Examples
+/// ```
+/// use candle::mesh;
+/// let mut indices = vec![3, 1, 2, 2, 1, 0];
+/// let order = mesh::optimize_vertex_fetch(&mut indices, 5);
+/// assert_eq!(indices, vec![0, 1, 2, 2, 1, 3]);
+/// assert_eq!(order, vec![3, 1, 2, 0, 4]);
+///
+/// let positions = vec!["a", "b", "c", "d", "e"];
+/// assert_eq!(mesh::reorder_vertices(&positions, &order), vec!["d", "b", "c", "a", "e"]);
+/// ```
+pub fn optimize_vertex_fetch(indices: &mut [u32], vertex_count: usize) -> Vec<usize> {
+    let mut remap: Vec<Option<u32>> = vec![None; vertex_count];
+    let mut order = Vec::with_capacity(vertex_count);
+    for index in indices.iter_mut() {
+        let old = *index as usize;
+        *index = match remap[old] {
+            Some(new) => new,
+            None => {
+                let new = order.len() as u32;
+                remap[old] = Some(new);
+                order.push(old);
+                new
+            }
+        };
+    }
+    for (old, new) in remap.iter().enumerate() {
+        if new.is_none() {
+            order.push(old);
+        }
+    }
+    order
+}
+
+/// Returns the vertex attributes rearranged by the order returned from `optimize_vertex_fetch`.
+pub fn reorder_vertices<T: Clone>(vertices: &[T], order: &[usize]) -> Vec<T> {
+    order.iter().map(|&old| vertices[old].clone()).collect()
+}
+
+/// Simulates a FIFO post-transform cache of `cache_size` entries and returns the average number
+/// of vertex transforms per triangle (ACMR). Lower is better; 0.5 is the best achievable on
+/// large regular meshes and 3.0 means no reuse at all.
+///
+/// # Examples
+/// ```
+/// use candle::mesh;
+/// // Two triangles sharing an edge transform four vertices.
+/// assert_eq!(mesh::average_cache_miss_ratio(&[0, 1, 2, 2, 1, 3], 16), 2.0);
+/// ```
+pub fn average_cache_miss_ratio(indices: &[u32], cache_size: usize) -> f64 {
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return 0.0;
+    }
+    let mut cache: VecDeque<u32> = VecDeque::with_capacity(cache_size);
+    let mut misses = 0;
+    for &index in &indices[..triangle_count * 3] {
+        if !cache.contains(&index) {
+            misses += 1;
+            if cache.len() == cache_size {
+                cache.pop_front();
+            }
+            cache.push_back(index);
+        }
+    }
+    misses as f64 / triangle_count as f64
+}
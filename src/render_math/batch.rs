@@ -0,0 +1,130 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// How a draw item is blended with what is already on screen. Items with different blend modes
+/// can never share a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BlendMode {
+    Opaque,
+    Alpha,
+    Additive,
+    Multiply,
+}
+
+/// The order in which items of the same layer are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortPolicy {
+    /// Farthest items first, as required for correct alpha blending. Texture and blend mode only
+    /// break ties between items at the same depth.
+    BackToFront,
+    /// Nearest items first, which lets the depth test reject hidden opaque pixels early. Texture
+    /// and blend mode only break ties between items at the same depth.
+    FrontToBack,
+    /// Groups items by blend mode and texture first, minimizing state changes for opaque items
+    /// that do not depend on draw order.
+    ByState,
+}
+
+/// The state a draw item needs from the renderer, used to order items and to decide which of
+/// them can be drawn together in one batch. Larger `z` values are farther from the camera and
+/// lower layers are always drawn before higher ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchKey {
+    pub texture: u32,
+    pub layer: i32,
+    pub blend: BlendMode,
+    pub z: f64,
+}
+
+impl BatchKey {
+
+    /// Allocates a new BatchKey with the given state.
+    pub fn new(texture: u32, layer: i32, blend: BlendMode, z: f64) -> BatchKey {
+        BatchKey { texture, layer, blend, z }
+    }
+
+    /// Compares two keys according to the sort policy. NaN depths order after every other depth,
+    /// whatever their sign and whichever way the policy sorts depths.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use candle::render_math::{BatchKey, BlendMode, SortPolicy};
+    /// let near = BatchKey::new(1, 0, BlendMode::Alpha, 1.0);
+    /// let far = BatchKey::new(2, 0, BlendMode::Alpha, 5.0);
+    /// assert_eq!(near.compare(&far, SortPolicy::BackToFront), Ordering::Greater);
+    /// assert_eq!(near.compare(&far, SortPolicy::FrontToBack), Ordering::Less);
+    ///
+    /// let overlay = BatchKey::new(0, 1, BlendMode::Alpha, 10.0);
+    /// assert_eq!(overlay.compare(&near, SortPolicy::BackToFront), Ordering::Greater);
+    ///
+    /// let broken = BatchKey::new(1, 0, BlendMode::Alpha, -f64::NAN);
+    /// assert_eq!(broken.compare(&far, SortPolicy::BackToFront), Ordering::Greater);
+    /// assert_eq!(broken.compare(&far, SortPolicy::FrontToBack), Ordering::Greater);
+    /// ```
+    pub fn compare(&self, other: &BatchKey, policy: SortPolicy) -> Ordering {
+        let state = self.blend.cmp(&other.blend).then(self.texture.cmp(&other.texture));
+        let nan = self.z.is_nan().cmp(&other.z.is_nan());
+        let depth = self.z.total_cmp(&other.z);
+        self.layer.cmp(&other.layer).then(match policy {
+            SortPolicy::BackToFront => nan.then(depth.reverse()).then(state),
+            SortPolicy::FrontToBack => nan.then(depth).then(state),
+            SortPolicy::ByState => state.then(nan).then(depth),
+        })
+    }
+
+    /// Returns true if both keys can be drawn in the same batch, which requires the same
+    /// texture, layer and blend mode. Depth doesn't matter once the items are sorted.
+    pub fn batches_with(&self, other: &BatchKey) -> bool {
+        self.texture == other.texture && self.layer == other.layer && self.blend == other.blend
+    }
+}
+
+/// Sorts the draw items by the key returned from `key` according to the sort policy. The sort
+/// is stable, so items with equal keys keep their submission order and don't flicker between
+/// frames.
+///
+/// # Examples
+/// ```
+/// use candle::render_math::{self, BatchKey, BlendMode, SortPolicy};
+/// let mut sprites = vec![
+///     ("tree", BatchKey::new(1, 0, BlendMode::Alpha, 2.0)),
+///     ("hero", BatchKey::new(2, 0, BlendMode::Alpha, 1.0)),
+///     ("rock", BatchKey::new(1, 0, BlendMode::Alpha, 2.0)),
+///     ("ground", BatchKey::new(3, -1, BlendMode::Opaque, 9.0)),
+/// ];
+/// render_math::sort_by_batch_key(&mut sprites, SortPolicy::BackToFront, |s| s.1);
+/// let names: Vec<&str> = sprites.iter().map(|s| s.0).collect();
+/// assert_eq!(names, vec!["ground", "tree", "rock", "hero"]);
+/// ```
+pub fn sort_by_batch_key<T, F>(items: &mut [T], policy: SortPolicy, key: F)
+    where F: Fn(&T) -> BatchKey
+{
+    items.sort_by(|a, b| key(a).compare(&key(b), policy));
+}
+
+/// Splits sorted keys into runs of consecutive keys that can be drawn in a single batch,
+/// returning the index range of each run.
+///
+/// # Examples
+/// ```
+/// use candle::render_math::{self, BatchKey, BlendMode};
+/// let keys = vec![
+///     BatchKey::new(1, 0, BlendMode::Alpha, 3.0),
+///     BatchKey::new(1, 0, BlendMode::Alpha, 2.0),
+///     BatchKey::new(2, 0, BlendMode::Alpha, 1.0),
+///     BatchKey::new(1, 0, BlendMode::Alpha, 0.5),
+/// ];
+/// assert_eq!(render_math::group_batches(&keys), vec![0..2, 2..3, 3..4]);
+/// ```
+pub fn group_batches(keys: &[BatchKey]) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    for index in 1..keys.len() + 1 {
+        if index == keys.len() || !keys[index].batches_with(&keys[start]) {
+            batches.push(start..index);
+            start = index;
+        }
+    }
+    batches
+}
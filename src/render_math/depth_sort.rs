@@ -0,0 +1,95 @@
+use std::cmp::Ordering;
+use math::Vec2;
+
+/// Which way the y axis points on screen, which decides whether larger or smaller y values are
+/// closer to the viewer in a y-sorted scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YAxis {
+    /// Screen coordinates: larger y is lower on screen, closer to the viewer, and drawn later.
+    Down,
+    /// World coordinates: smaller y is lower on screen, closer to the viewer, and drawn later.
+    Up,
+}
+
+/// Draw order key for y-sorted 2.5D scenes. Keys order by layer first, then by depth, and ties
+/// are broken by the entity id, so the order never depends on the sort algorithm or on the
+/// order entities were submitted in. That determinism is what keeps overlapping sprites at the
+/// same depth from flickering between frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthKey {
+    pub layer: i32,
+    pub depth: f64,
+    pub id: u64,
+}
+
+impl DepthKey {
+
+    /// Allocates a new DepthKey. Items with a lower `depth` are drawn first.
+    pub fn new(layer: i32, depth: f64, id: u64) -> DepthKey {
+        DepthKey { layer, depth, id }
+    }
+
+    /// Builds the key of an entity at `position`, sorted by its y coordinate plus `offset`. The
+    /// offset moves the sorting point of the entity, usually from its origin to its feet, so a
+    /// tall sprite sorts against others by where it touches the ground.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::Vec2;
+    /// use candle::render_math::{DepthKey, YAxis};
+    /// // A tree whose origin is at its top, 3 units above its base.
+    /// let tree = DepthKey::from_position(0, &Vec2::new(0.0, 2.0), 3.0, 1, YAxis::Down);
+    /// let hero = DepthKey::from_position(0, &Vec2::new(0.0, 4.0), 0.0, 2, YAxis::Down);
+    /// // The hero stands behind the base of the tree.
+    /// assert!(hero.compare(&tree).is_lt());
+    /// ```
+    pub fn from_position(layer: i32, position: &Vec2, offset: f64, id: u64, axis: YAxis) -> DepthKey {
+        let y = position.y + offset;
+        let depth = match axis {
+            YAxis::Down => y,
+            YAxis::Up => -y,
+        };
+        DepthKey::new(layer, depth, id)
+    }
+
+    /// Compares two keys in draw order: layer, then depth, then id. NaN depths order after every
+    /// other depth whatever their sign, keeping the comparison a total order.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use candle::render_math::DepthKey;
+    /// let a = DepthKey::new(0, 5.0, 7);
+    /// assert_eq!(a.compare(&DepthKey::new(0, 6.0, 1)), Ordering::Less);
+    /// assert_eq!(a.compare(&DepthKey::new(0, 5.0, 1)), Ordering::Greater);
+    /// assert_eq!(a.compare(&DepthKey::new(-1, 9.0, 1)), Ordering::Greater);
+    /// assert_eq!(a.compare(&DepthKey::new(0, -f64::NAN, 1)), Ordering::Less);
+    /// ```
+    pub fn compare(&self, other: &DepthKey) -> Ordering {
+        self.layer.cmp(&other.layer)
+            .then(self.depth.is_nan().cmp(&other.depth.is_nan()))
+            .then(self.depth.total_cmp(&other.depth))
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+/// Sorts the items in draw order by the DepthKey returned from `key`.
+///
+/// # Examples
+/// ```
+/// use candle::math::Vec2;
+/// use candle::render_math::{self, DepthKey, YAxis};
+/// let mut entities = vec![
+///     (3, Vec2::new(0.0, 1.0)),
+///     (1, Vec2::new(5.0, 1.0)),
+///     (2, Vec2::new(2.0, 0.0)),
+/// ];
+/// render_math::sort_by_depth(&mut entities, |e| DepthKey::from_position(0, &e.1, 0.0, e.0, YAxis::Down));
+/// let ids: Vec<u64> = entities.iter().map(|e| e.0).collect();
+/// assert_eq!(ids, vec![2, 1, 3]);
+/// ```
+pub fn sort_by_depth<T, F>(items: &mut [T], key: F)
+    where F: Fn(&T) -> DepthKey
+{
+    items.sort_by(|a, b| key(a).compare(&key(b)));
+}
@@ -0,0 +1,7 @@
+mod batch;
+mod depth_sort;
+mod shadow;
+
+pub use self::batch::{BatchKey, BlendMode, SortPolicy, sort_by_batch_key, group_batches};
+pub use self::depth_sort::{DepthKey, YAxis, sort_by_depth};
+pub use self::shadow::{PenumbraWedge, penumbra_wedges};
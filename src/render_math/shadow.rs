@@ -0,0 +1,104 @@
+use math::Vec2;
+
+/// The penumbra cast behind one endpoint of a segment occluder by a circular light: the region
+/// between the `outer` edge, where the whole light is still visible, and the `inner` edge,
+/// where the light is completely hidden. Both edges are unit directions starting at `origin`,
+/// the occluder endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenumbraWedge {
+    pub origin: Vec2,
+    pub outer: Vec2,
+    pub inner: Vec2,
+}
+
+impl PenumbraWedge {
+
+    /// Returns the angle between the two edges of the wedge in radians.
+    pub fn angle(&self) -> f64 {
+        signed_angle(&self.outer, &self.inner).abs()
+    }
+
+    /// Returns how much of the light is hidden from `point`, from `0.0` on and outside the outer
+    /// edge to `1.0` on and inside the inner edge, interpolated by angle in between. This is the
+    /// gradient renderers draw across the wedge; it approximates the hidden fraction of the
+    /// light's area, which is what a soft shadow actually shows.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::math::{Vec2, ApproxEq};
+    /// use candle::render_math;
+    /// let light = Vec2::new(0.0, 0.0);
+    /// let (left, _) = render_math::penumbra_wedges(&light, 1.0, &Vec2::new(-1.0, 5.0), &Vec2::new(1.0, 5.0)).unwrap();
+    /// let behind = |t: f64| left.origin + (left.outer * (1.0 - t) + left.inner * t) * 5.0;
+    /// assert!(left.intensity_at(&behind(0.0)).approx_eq_eps(0.0, 1e-9));
+    /// assert!(left.intensity_at(&behind(0.5)).approx_eq_eps(0.5, 1e-9));
+    /// assert_eq!(left.intensity_at(&Vec2::new(-10.0, 10.0)), 0.0);
+    /// assert_eq!(left.intensity_at(&Vec2::new(0.0, 10.0)), 1.0);
+    /// ```
+    pub fn intensity_at(&self, point: &Vec2) -> f64 {
+        let offset = point - self.origin;
+        if offset.x == 0.0 && offset.y == 0.0 {
+            return 0.0;
+        }
+        let total = signed_angle(&self.outer, &self.inner);
+        if total == 0.0 {
+            return 0.0;
+        }
+        let direction = offset.normalized();
+        // Points in front of the occluder are lit: only the half plane the wedge opens into
+        // counts.
+        if direction.dot(&(self.outer + self.inner)) <= 0.0 {
+            return 0.0;
+        }
+        (signed_angle(&self.outer, &direction) / total).clamp(0.0, 1.0)
+    }
+}
+
+/// Computes the penumbra wedges cast by the segment occluder `(a, b)` from a circular light at
+/// `light` with the given `radius`, one wedge at each endpoint in that order. Between the two
+/// inner edges lies the umbra, the fully shadowed region; when the light is wider than the
+/// occluder the inner edges cross and the umbra ends there.
+///
+/// Returns `None` if the light overlaps an endpoint, where the occluder casts no well defined
+/// penumbra.
+///
+/// # Examples
+/// ```
+/// use candle::math::{Vec2, ApproxEq};
+/// use candle::render_math;
+/// let light = Vec2::new(0.0, 0.0);
+/// let (left, right) = render_math::penumbra_wedges(&light, 1.0, &Vec2::new(-1.0, 5.0), &Vec2::new(1.0, 5.0)).unwrap();
+/// // The outer edges spread away from the occluder, the inner edges lean into it.
+/// assert!(left.outer.x < left.inner.x);
+/// assert!(right.outer.x > right.inner.x);
+/// assert!(left.angle().approx_eq(right.angle()));
+/// assert_eq!(render_math::penumbra_wedges(&light, 6.0, &Vec2::new(-1.0, 5.0), &Vec2::new(1.0, 5.0)), None);
+/// ```
+pub fn penumbra_wedges(light: &Vec2, radius: f64, a: &Vec2, b: &Vec2) -> Option<(PenumbraWedge, PenumbraWedge)> {
+    Some((endpoint_wedge(light, radius, a, b)?, endpoint_wedge(light, radius, b, a)?))
+}
+
+fn endpoint_wedge(light: &Vec2, radius: f64, endpoint: &Vec2, other: &Vec2) -> Option<PenumbraWedge> {
+    let away = endpoint - light;
+    let distance = away.length();
+    if distance <= radius {
+        return None;
+    }
+    // The edges are the lines through the endpoint tangent to the light, which deviate from
+    // the direction away from the light by the angular radius of the light.
+    let half_angle = (radius / distance).asin();
+    let direction = away / distance;
+    let along = other - endpoint;
+    let toward_other = if direction.x * along.y - direction.y * along.x >= 0.0 { 1.0 } else { -1.0 };
+    let origin = Vec2::new(0.0, 0.0);
+    Some(PenumbraWedge {
+        origin: *endpoint,
+        outer: direction.rotate_around(&origin, -half_angle * toward_other),
+        inner: direction.rotate_around(&origin, half_angle * toward_other),
+    })
+}
+
+/// Returns the counterclockwise angle from `from` to `to`, in `(-π, π]`.
+fn signed_angle(from: &Vec2, to: &Vec2) -> f64 {
+    (from.x * to.y - from.y * to.x).atan2(from.dot(to))
+}
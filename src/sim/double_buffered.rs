@@ -0,0 +1,144 @@
+/// Accumulates frame time and splits it into fixed simulation steps, so the simulation runs at
+/// the same rate whatever the frame rate. The time left over after the last whole step is
+/// carried to the next frame and exposed as `alpha`.
+///
+/// # Examples
+/// ```
+/// use candle::sim::FixedTimestep;
+/// let mut timestep = FixedTimestep::new(0.01);
+/// assert_eq!(timestep.advance(0.025), 2);
+/// assert!((timestep.alpha() - 0.5).abs() < 1e-9);
+/// assert_eq!(timestep.advance(0.005), 1);
+/// assert!(timestep.alpha() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedTimestep {
+    step: f64,
+    accumulator: f64,
+    /// Upper bound on the steps taken by a single `advance`. After a long stall the remaining
+    /// time is dropped instead of running an ever growing number of steps (the "spiral of
+    /// death").
+    pub max_steps: usize,
+}
+
+impl FixedTimestep {
+
+    /// Allocates a new FixedTimestep running steps of `step` seconds, at most 8 per frame.
+    pub fn new(step: f64) -> FixedTimestep {
+        FixedTimestep { step, accumulator: 0.0, max_steps: 8 }
+    }
+
+    /// Returns the duration of a step in seconds.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Adds `frame_time` seconds and returns the number of whole steps to simulate.
+    pub fn advance(&mut self, frame_time: f64) -> usize {
+        self.accumulator += frame_time.max(0.0);
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_steps {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        if steps == self.max_steps {
+            self.accumulator = self.accumulator.min(self.step);
+        }
+        steps
+    }
+
+    /// Returns the time accumulated towards the next step, as a fraction of a step in
+    /// `[0, 1]`. This is the blend factor between the previous and the current state for
+    /// `DoubleBuffered::interpolated`.
+    pub fn alpha(&self) -> f64 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+}
+
+/// The previous and current states of a fixed-step simulation. Rendering the current state
+/// directly stutters, because frames rarely line up with steps; rendering the interpolation
+/// between the two states at `FixedTimestep::alpha` is smooth, at the cost of displaying the
+/// simulation one step late.
+///
+/// # Examples
+/// ```
+/// use candle::sim::{DoubleBuffered, FixedTimestep};
+/// use candle::math::scalar;
+/// let mut timestep = FixedTimestep::new(0.1);
+/// // A body moving at 10 units per second.
+/// let mut position = DoubleBuffered::new(0.0);
+/// position.run(&mut timestep, 0.25, |x| x + 1.0);
+/// assert_eq!(*position.previous(), 1.0);
+/// assert_eq!(*position.current(), 2.0);
+/// let rendered = position.interpolated(timestep.alpha(), |a, b, t| scalar::lerp(*a, *b, t));
+/// assert!((rendered - 1.5).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoubleBuffered<T> {
+    previous: T,
+    current: T,
+}
+
+impl<T: Clone> DoubleBuffered<T> {
+
+    /// Allocates a new DoubleBuffered with both states set to `state`.
+    pub fn new(state: T) -> DoubleBuffered<T> {
+        DoubleBuffered { previous: state.clone(), current: state }
+    }
+
+    /// Returns the state before the latest step.
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+
+    /// Returns the state after the latest step.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Returns the current state mutably, for changes that should not be interpolated, such as
+    /// teleporting. Call `settle` afterwards to also apply them to the previous state.
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.current
+    }
+
+    /// Copies the current state over the previous one, so the next frames render it without
+    /// interpolating from the old state.
+    pub fn settle(&mut self) {
+        self.previous = self.current.clone();
+    }
+
+    /// Makes `state` the current state, moving the current one to previous.
+    pub fn push(&mut self, state: T) {
+        self.previous = ::std::mem::replace(&mut self.current, state);
+    }
+
+    /// Runs one `step` of the simulation from the current state.
+    pub fn step<F>(&mut self, step: F)
+        where F: FnOnce(&T) -> T
+    {
+        let next = step(&self.current);
+        self.push(next);
+    }
+
+    /// Advances `timestep` by `frame_time` and runs `step` once per whole simulation step.
+    /// Returns the number of steps run.
+    pub fn run<F>(&mut self, timestep: &mut FixedTimestep, frame_time: f64, mut step: F) -> usize
+        where F: FnMut(&T) -> T
+    {
+        let steps = timestep.advance(frame_time);
+        for _ in 0..steps {
+            let next = step(&self.current);
+            self.push(next);
+        }
+        steps
+    }
+
+    /// Returns the state to render, blending the previous and current states with `lerp` at
+    /// `alpha`, usually `FixedTimestep::alpha`.
+    pub fn interpolated<U, F>(&self, alpha: f64, lerp: F) -> U
+        where F: FnOnce(&T, &T, f64) -> U
+    {
+        lerp(&self.previous, &self.current, alpha)
+    }
+}
@@ -0,0 +1,212 @@
+use math::Vec2;
+use math::scalar::{lerp, smootherstep};
+
+/// A force that varies over space and time, such as wind, sampled by the simulations that move
+/// bodies through it. Fields are combined by putting them in a slice or a `Vec`, which applies
+/// the sum of their forces.
+///
+/// # Examples
+/// ```
+/// use candle::math::Vec2;
+/// use candle::sim::{ForceField, ConstantField, RadialField};
+/// let fields: Vec<Box<dyn ForceField>> = vec![
+///     Box::new(ConstantField::new(Vec2::new(0.0, -9.8))),
+///     Box::new(RadialField::new(Vec2::new(0.0, 0.0), 4.0, 10.0)),
+/// ];
+/// assert_eq!(fields.force_at(Vec2::new(5.0, 0.0), 0.0), Vec2::new(2.0, -9.8));
+/// ```
+pub trait ForceField {
+    /// Returns the force at `pos` at time `t`, in seconds.
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2;
+}
+
+impl<T: ForceField + ?Sized> ForceField for &T {
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2 {
+        (**self).force_at(pos, t)
+    }
+}
+
+impl<T: ForceField + ?Sized> ForceField for Box<T> {
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2 {
+        (**self).force_at(pos, t)
+    }
+}
+
+impl<T: ForceField> ForceField for [T] {
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2 {
+        self.iter().map(|field| field.force_at(pos, t)).sum()
+    }
+}
+
+impl<T: ForceField> ForceField for Vec<T> {
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2 {
+        self[..].force_at(pos, t)
+    }
+}
+
+/// The same force everywhere, such as gravity or a steady wind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantField {
+    pub force: Vec2,
+}
+
+impl ConstantField {
+
+    /// Allocates a new ConstantField applying `force` everywhere.
+    pub fn new(force: Vec2) -> ConstantField {
+        ConstantField { force }
+    }
+}
+
+impl ForceField for ConstantField {
+    fn force_at(&self, _pos: Vec2, _t: f64) -> Vec2 {
+        self.force
+    }
+}
+
+/// A force pointing away from `center`, or towards it for a negative `strength`, such as an
+/// explosion or an attractor. The force is `strength` at the center and falls off linearly to
+/// zero at `radius`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RadialField {
+    pub center: Vec2,
+    pub strength: f64,
+    pub radius: f64,
+}
+
+impl RadialField {
+
+    /// Allocates a new RadialField.
+    pub fn new(center: Vec2, strength: f64, radius: f64) -> RadialField {
+        RadialField { center, strength, radius }
+    }
+}
+
+impl ForceField for RadialField {
+    fn force_at(&self, pos: Vec2, _t: f64) -> Vec2 {
+        let offset = pos - self.center;
+        let distance = offset.length();
+        if distance == 0.0 || distance >= self.radius {
+            return Vec2::new(0.0, 0.0);
+        }
+        let magnitude = self.strength * (1.0 - distance / self.radius) / distance;
+        Vec2::new(offset.x * magnitude, offset.y * magnitude)
+    }
+}
+
+/// A force swirling around `center`, counterclockwise for a positive `strength` and clockwise
+/// for a negative one, such as a whirlwind or a drain. Like `RadialField`, the force falls off
+/// linearly from `strength` at the center to zero at `radius`.
+///
+/// # Examples
+/// ```
+/// use candle::math::{Vec2, ApproxEq};
+/// use candle::sim::{ForceField, VortexField};
+/// let vortex = VortexField::new(Vec2::new(0.0, 0.0), 2.0, 4.0);
+/// assert!(vortex.force_at(Vec2::new(2.0, 0.0), 0.0).approx_eq(Vec2::new(0.0, 1.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VortexField {
+    pub center: Vec2,
+    pub strength: f64,
+    pub radius: f64,
+}
+
+impl VortexField {
+
+    /// Allocates a new VortexField.
+    pub fn new(center: Vec2, strength: f64, radius: f64) -> VortexField {
+        VortexField { center, strength, radius }
+    }
+}
+
+impl ForceField for VortexField {
+    fn force_at(&self, pos: Vec2, _t: f64) -> Vec2 {
+        let offset = pos - self.center;
+        let distance = offset.length();
+        if distance == 0.0 || distance >= self.radius {
+            return Vec2::new(0.0, 0.0);
+        }
+        let magnitude = self.strength * (1.0 - distance / self.radius) / distance;
+        Vec2::new(-offset.y * magnitude, offset.x * magnitude)
+    }
+}
+
+/// Gusty wind built from curl noise: the curl of a smooth noise potential, which swirls like a
+/// fluid because it has no sources or sinks. `frequency` sets the size of the eddies (about
+/// `1 / frequency` units across), `speed` how fast the pattern changes over time and `seed`
+/// picks a different pattern.
+///
+/// # Examples
+/// ```
+/// use candle::math::Vec2;
+/// use candle::sim::{ForceField, TurbulenceField};
+/// let wind = TurbulenceField::new(3.0, 0.5, 1.0, 7);
+/// let a = wind.force_at(Vec2::new(1.0, 2.0), 0.0);
+/// assert!(a.length() > 0.0);
+/// // The field is deterministic and changes smoothly.
+/// assert_eq!(a, wind.force_at(Vec2::new(1.0, 2.0), 0.0));
+/// assert!((a - wind.force_at(Vec2::new(1.001, 2.0), 0.0)).length() < 0.1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurbulenceField {
+    pub strength: f64,
+    pub frequency: f64,
+    pub speed: f64,
+    pub seed: u32,
+}
+
+impl TurbulenceField {
+
+    /// Allocates a new TurbulenceField.
+    pub fn new(strength: f64, frequency: f64, speed: f64, seed: u32) -> TurbulenceField {
+        TurbulenceField { strength, frequency, speed, seed }
+    }
+
+    fn potential(&self, x: f64, y: f64, t: f64) -> f64 {
+        value_noise(x * self.frequency, y * self.frequency, t * self.speed, self.seed)
+    }
+}
+
+impl ForceField for TurbulenceField {
+    fn force_at(&self, pos: Vec2, t: f64) -> Vec2 {
+        if self.frequency == 0.0 {
+            return Vec2::new(0.0, 0.0);
+        }
+        // The curl of a scalar potential is its gradient rotated by 90 degrees. The gradient is
+        // taken by central differences, scaled back to world units.
+        let h = 1e-4 / self.frequency;
+        let dx = (self.potential(pos.x + h, pos.y, t) - self.potential(pos.x - h, pos.y, t)) / (2.0 * h);
+        let dy = (self.potential(pos.x, pos.y + h, t) - self.potential(pos.x, pos.y - h, t)) / (2.0 * h);
+        let scale = self.strength / self.frequency;
+        Vec2::new(dy * scale, -dx * scale)
+    }
+}
+
+/// Smooth 3D value noise in `[-1, 1]`, interpolating random values hashed from the integer
+/// lattice.
+fn value_noise(x: f64, y: f64, z: f64, seed: u32) -> f64 {
+    let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+    let (tx, ty, tz) = (smootherstep(0.0, 1.0, x - x0), smootherstep(0.0, 1.0, y - y0), smootherstep(0.0, 1.0, z - z0));
+    let (ix, iy, iz) = (x0 as i64, y0 as i64, z0 as i64);
+    let corner = |dx: i64, dy: i64, dz: i64| lattice_value(ix + dx, iy + dy, iz + dz, seed);
+
+    let plane = |dz: i64| {
+        let bottom = lerp(corner(0, 0, dz), corner(1, 0, dz), tx);
+        let top = lerp(corner(0, 1, dz), corner(1, 1, dz), tx);
+        lerp(bottom, top, ty)
+    };
+    lerp(plane(0), plane(1), tz)
+}
+
+fn lattice_value(x: i64, y: i64, z: i64, seed: u32) -> f64 {
+    let mut hash = (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ (z as u64).wrapping_mul(0x1656_67B1_9E37_79F9)
+        ^ u64::from(seed);
+    // SplitMix64 finalizer.
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^= hash >> 31;
+    (hash >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
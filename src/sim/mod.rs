@@ -0,0 +1,7 @@
+mod water_line;
+mod force_field;
+mod double_buffered;
+
+pub use self::water_line::WaterLine;
+pub use self::force_field::{ForceField, ConstantField, RadialField, VortexField, TurbulenceField};
+pub use self::double_buffered::{DoubleBuffered, FixedTimestep};
@@ -0,0 +1,201 @@
+use math::Vec2;
+
+/// A 2D water surface made of a row of vertical springs, as used for splashing water in
+/// platformers. Each column oscillates around the rest surface level and pulls on its
+/// neighbours, so a splash spreads out as waves.
+///
+/// The y axis points up: columns store the height of the surface and the water fills the area
+/// between the surface and `bottom`.
+#[derive(Debug, Clone)]
+pub struct WaterLine {
+    left: f64,
+    right: f64,
+    surface: f64,
+    bottom: f64,
+    heights: Vec<f64>,
+    velocities: Vec<f64>,
+    /// Spring constant pulling each column back to the rest surface.
+    pub stiffness: f64,
+    /// Velocity damping of each column, per second.
+    pub damping: f64,
+    /// Fraction of the height difference transferred to the neighbours each second.
+    pub spread: f64,
+    /// Number of neighbour propagation passes per update. More passes make waves travel faster.
+    pub passes: usize,
+}
+
+impl WaterLine {
+
+    /// Allocates a new WaterLine spanning `left` to `right` horizontally, with the surface at
+    /// rest at `surface` and the water going down to `bottom`. The surface is simulated with
+    /// `columns` springs, at least two.
+    pub fn new(left: f64, right: f64, surface: f64, bottom: f64, columns: usize) -> WaterLine {
+        let columns = columns.max(2);
+        WaterLine {
+            left,
+            right,
+            surface,
+            bottom,
+            heights: vec![surface; columns],
+            velocities: vec![0.0; columns],
+            stiffness: 60.0,
+            damping: 1.5,
+            spread: 15.0,
+            passes: 4,
+        }
+    }
+
+    /// Returns the horizontal distance between two columns.
+    pub fn spacing(&self) -> f64 {
+        (self.right - self.left) / (self.heights.len() - 1) as f64
+    }
+
+    /// Applies a vertical velocity to the column closest to `x`, such as a body falling in
+    /// (negative velocity) or jumping out (positive velocity). Splashes outside of the water are
+    /// ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::sim::WaterLine;
+    /// let mut water = WaterLine::new(0.0, 10.0, 5.0, 0.0, 11);
+    /// water.splash(5.0, -4.0);
+    /// water.update(1.0 / 60.0);
+    /// assert!(water.height_at(5.0) < 5.0);
+    /// for _ in 0..10 {
+    ///     water.update(1.0 / 60.0);
+    /// }
+    /// // The wave reached the neighbouring columns.
+    /// assert!(water.height_at(4.0) != 5.0);
+    /// ```
+    pub fn splash(&mut self, x: f64, velocity: f64) {
+        if x < self.left || x > self.right {
+            return;
+        }
+        let index = ((x - self.left) / self.spacing()).round() as usize;
+        self.velocities[index] += velocity;
+    }
+
+    /// Advances the simulation by `dt` seconds.
+    pub fn update(&mut self, dt: f64) {
+        for (height, velocity) in self.heights.iter_mut().zip(self.velocities.iter_mut()) {
+            let acceleration = -self.stiffness * (*height - self.surface) - self.damping * *velocity;
+            *velocity += acceleration * dt;
+            *height += *velocity * dt;
+        }
+
+        let columns = self.heights.len();
+        let mut deltas = vec![0.0; columns];
+        for _ in 0..self.passes {
+            for (index, delta) in deltas.iter_mut().enumerate() {
+                *delta = 0.0;
+                if index > 0 {
+                    *delta += self.heights[index - 1] - self.heights[index];
+                }
+                if index + 1 < columns {
+                    *delta += self.heights[index + 1] - self.heights[index];
+                }
+            }
+            for (index, delta) in deltas.iter().enumerate() {
+                let delta = delta * self.spread * dt;
+                self.velocities[index] += delta;
+                self.heights[index] += delta * dt;
+            }
+        }
+    }
+
+    /// Returns the height of the surface at `x`, interpolating between columns. Positions
+    /// outside the water return the height of the closest edge.
+    pub fn height_at(&self, x: f64) -> f64 {
+        let position = ((x - self.left) / self.spacing()).max(0.0);
+        let index = (position.floor() as usize).min(self.heights.len() - 2);
+        let t = (position - index as f64).min(1.0);
+        self.heights[index] + (self.heights[index + 1] - self.heights[index]) * t
+    }
+
+    /// Returns the points of the surface from left to right, one per column.
+    pub fn surface_points(&self) -> Vec<Vec2> {
+        let spacing = self.spacing();
+        self.heights.iter().enumerate()
+            .map(|(index, height)| Vec2::new(self.left + spacing * index as f64, *height))
+            .collect()
+    }
+
+    /// Returns the outline of the water body as a counterclockwise polygon, starting at the
+    /// bottom left corner, ready to be triangulated or drawn.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::sim::WaterLine;
+    /// use candle::math::Vec2;
+    /// let water = WaterLine::new(0.0, 4.0, 2.0, 0.0, 3);
+    /// assert_eq!(water.outline(), vec![
+    ///     Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0),
+    ///     Vec2::new(4.0, 2.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0),
+    /// ]);
+    /// ```
+    pub fn outline(&self) -> Vec<Vec2> {
+        let mut outline = vec![Vec2::new(self.left, self.bottom), Vec2::new(self.right, self.bottom)];
+        outline.extend(self.surface_points().into_iter().rev());
+        outline
+    }
+
+    /// Returns the area and the centroid of the part of the axis-aligned box `(min, max)` that
+    /// lies under the water surface. The centroid is the point where buoyancy acts on the box.
+    /// Returns a zero area and the box center if the box is out of the water.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::sim::WaterLine;
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let water = WaterLine::new(0.0, 10.0, 5.0, 0.0, 11);
+    /// let (area, center) = water.submerged_area(&Vec2::new(2.0, 4.0), &Vec2::new(4.0, 8.0));
+    /// assert!(area.approx_eq(2.0));
+    /// assert!(center.approx_eq(Vec2::new(3.0, 4.5)));
+    /// ```
+    pub fn submerged_area(&self, min: &Vec2, max: &Vec2) -> (f64, Vec2) {
+        let left = min.x.max(self.left);
+        let right = max.x.min(self.right);
+        let box_center = Vec2::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+        if right <= left {
+            return (0.0, box_center);
+        }
+
+        // Integrate the submerged depth in strips, two per column spacing covered by the box.
+        let strips = (((right - left) / self.spacing()).ceil() as usize).max(1) * 2;
+        let strip_width = (right - left) / strips as f64;
+        let mut area = 0.0;
+        let mut moment = Vec2::new(0.0, 0.0);
+        for strip in 0..strips {
+            let x = left + strip_width * (strip as f64 + 0.5);
+            let floor = min.y.max(self.bottom);
+            let depth = self.height_at(x).min(max.y) - floor;
+            if depth > 0.0 {
+                let strip_area = depth * strip_width;
+                area += strip_area;
+                moment.x += x * strip_area;
+                moment.y += (floor + depth / 2.0) * strip_area;
+            }
+        }
+        if area == 0.0 {
+            return (0.0, box_center);
+        }
+        (area, Vec2::new(moment.x / area, moment.y / area))
+    }
+
+    /// Returns the buoyant force on the axis-aligned box `(min, max)` for a fluid of the given
+    /// `density` (mass per unit area) under `gravity` (a positive acceleration pointing down),
+    /// and the point where it acts.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::sim::WaterLine;
+    /// use candle::math::{Vec2, ApproxEq};
+    /// let water = WaterLine::new(0.0, 10.0, 5.0, 0.0, 11);
+    /// let (force, _) = water.buoyancy(&Vec2::new(2.0, 4.0), &Vec2::new(4.0, 8.0), 1.0, 9.8);
+    /// assert!(force.approx_eq(Vec2::new(0.0, 19.6)));
+    /// ```
+    pub fn buoyancy(&self, min: &Vec2, max: &Vec2, density: f64, gravity: f64) -> (Vec2, Vec2) {
+        let (area, center) = self.submerged_area(min, max);
+        (Vec2::new(0.0, area * density * gravity), center)
+    }
+}
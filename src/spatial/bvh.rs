@@ -0,0 +1,376 @@
+use geometry::{Aabb3, Ray3, Sphere};
+use spatial::kdtree::KdPoint;
+
+/// The number of items a leaf holds before it is split.
+const MAX_LEAF_ITEMS: usize = 4;
+/// The number of bins candidate surface area splits are sampled from along an axis.
+const BINS: usize = 12;
+
+/// How `Bvh::new` chooses where to split the items of a node in two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BvhSplit {
+    /// Splits where the surface area heuristic estimates ray queries to be cheapest: the
+    /// children's surface areas, weighted by their item counts, are kept small. Slower to
+    /// build, but gives faster queries over unevenly spread items.
+    SurfaceArea,
+    /// Splits at the median of the item centers along their widest axis, giving a balanced
+    /// tree quickly.
+    Median,
+}
+
+/// A bounding volume hierarchy: a binary tree of boxes, each enclosing the items below it, over
+/// the axis-aligned bounding boxes of a set of items. Ray casts and overlap tests only descend
+/// into the boxes they touch, taking about O(log n) time for n items instead of testing every
+/// item, which makes it the broadphase for raycasting against scenes and finding potential
+/// collisions.
+///
+/// Items are the indices of the boxes the tree was built from. When items move, `update` or
+/// `refit` grows and shrinks the boxes to match while keeping the tree's structure, which is
+/// much cheaper than building it again; the queries stay correct however far items move, but
+/// slow down once items end up far from those they were grouped with, when it is worth
+/// rebuilding.
+///
+/// # Examples
+/// ```
+/// use candle::spatial::{Bvh, BvhSplit};
+/// use candle::geometry::{Aabb3, Ray3};
+/// use candle::math::Vec3;
+/// let crates: Vec<Aabb3> = (0..10).map(|i| {
+///     let corner = Vec3::new(i as f64 * 3.0, 0.0, 0.0);
+///     Aabb3::new(corner, corner + Vec3::new(1.0, 1.0, 1.0))
+/// }).collect();
+/// let mut bvh = Bvh::new(&crates, BvhSplit::SurfaceArea);
+/// let ray = Ray3::new(Vec3::new(-5.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+/// assert_eq!(bvh.raycast(&ray, |_, bounds| ray.intersect_aabb(bounds)), Some((0, 5.0)));
+///
+/// // The first crate falls out of the way.
+/// bvh.update(0, Aabb3::new(Vec3::new(0.0, -5.0, 0.0), Vec3::new(1.0, -4.0, 1.0)));
+/// assert_eq!(bvh.raycast(&ray, |_, bounds| ray.intersect_aabb(bounds)), Some((1, 8.0)));
+/// let mut below = bvh.query_aabb(&Aabb3::new(Vec3::new(-1.0, -6.0, -1.0), Vec3::new(5.0, 0.0, 1.0)));
+/// below.sort();
+/// assert_eq!(below, vec![0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    /// The bounds of each item.
+    bounds: Vec<Aabb3>,
+    /// The items of the leaves, each leaf holding a range of them.
+    items: Vec<usize>,
+    /// The leaf holding each item.
+    leaves: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    bounds: Aabb3,
+    parent: Option<usize>,
+    contents: Contents,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Contents {
+    /// The range of `Bvh::items` the leaf holds.
+    Leaf { start: usize, end: usize },
+    Branch { left: usize, right: usize },
+}
+
+impl Bvh {
+
+    /// Builds a Bvh over the items with the given bounds, splitting nodes as `split` says.
+    pub fn new(bounds: &[Aabb3], split: BvhSplit) -> Bvh {
+        let mut bvh = Bvh { nodes: Vec::new(), bounds: bounds.to_vec(), items: (0..bounds.len()).collect(), leaves: vec![0; bounds.len()] };
+        if !bounds.is_empty() {
+            bvh.build(0, bounds.len(), None, split);
+        }
+        bvh
+    }
+
+    /// Returns the number of items in the tree.
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Returns true if the tree holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Returns the box enclosing every item, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Aabb3> {
+        self.nodes.first().map(|root| root.bounds)
+    }
+
+    /// Returns the bounds of the item at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    pub fn item_bounds(&self, index: usize) -> Aabb3 {
+        self.bounds[index]
+    }
+
+    /// Moves the item at `index` to new bounds, refitting the boxes above it up to the first
+    /// one that doesn't change.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    pub fn update(&mut self, index: usize, bounds: Aabb3) {
+        self.bounds[index] = bounds;
+        let mut node = Some(self.leaves[index]);
+        while let Some(current) = node {
+            let fitted = self.fit(current);
+            if fitted == self.nodes[current].bounds {
+                break;
+            }
+            self.nodes[current].bounds = fitted;
+            node = self.nodes[current].parent;
+        }
+    }
+
+    /// Moves every item to new bounds at once and refits all the boxes, in O(n) time.
+    ///
+    /// # Panics
+    /// Panics if `bounds` doesn't have one box per item.
+    pub fn refit(&mut self, bounds: &[Aabb3]) {
+        assert_eq!(bounds.len(), self.bounds.len(), "refit needs one box per item");
+        self.bounds.copy_from_slice(bounds);
+        // Children always come after their parent, so going backwards refits them first.
+        for node in (0..self.nodes.len()).rev() {
+            self.nodes[node].bounds = self.fit(node);
+        }
+    }
+
+    /// Casts `ray` through the tree and returns the nearest item it hits, with the ray parameter
+    /// of the hit, or `None` if it hits nothing. The items whose boxes the ray passes through
+    /// are handed to `hit`, nearer boxes first, which returns the ray parameter where the ray
+    /// hits the item itself, or `None` if it misses, so items can be tested against their exact
+    /// shape. Boxes beyond the nearest hit so far are skipped.
+    pub fn raycast<F>(&self, ray: &Ray3, mut hit: F) -> Option<(usize, f64)>
+        where F: FnMut(usize, &Aabb3) -> Option<f64>
+    {
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack: Vec<(usize, f64)> = self.nodes.first()
+            .and_then(|root| ray.intersect_aabb(&root.bounds))
+            .map(|enter| (0, enter))
+            .into_iter()
+            .collect();
+        while let Some((node, enter)) = stack.pop() {
+            if best.is_some_and(|(_, nearest)| enter > nearest) {
+                continue;
+            }
+            match self.nodes[node].contents {
+                Contents::Leaf { start, end } => {
+                    for &item in &self.items[start..end] {
+                        let bounds = &self.bounds[item];
+                        if ray.intersect_aabb(bounds).is_none_or(|enter| best.is_some_and(|(_, nearest)| enter > nearest)) {
+                            continue;
+                        }
+                        if let Some(t) = hit(item, bounds) {
+                            if t >= 0.0 && best.is_none_or(|(_, nearest)| t < nearest) {
+                                best = Some((item, t));
+                            }
+                        }
+                    }
+                }
+                Contents::Branch { left, right } => {
+                    let enter_left = ray.intersect_aabb(&self.nodes[left].bounds).map(|enter| (left, enter));
+                    let enter_right = ray.intersect_aabb(&self.nodes[right].bounds).map(|enter| (right, enter));
+                    // The nearer child goes on top of the stack, to be searched first.
+                    let (near, far) = match (enter_left, enter_right) {
+                        (Some(l), Some(r)) if r.1 < l.1 => (Some(r), Some(l)),
+                        (l, r) => (l, r),
+                    };
+                    stack.extend(far);
+                    stack.extend(near);
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the items whose boxes overlap or touch `aabb`, in no particular order.
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<usize> {
+        self.query(|bounds| bounds.intersects(aabb))
+    }
+
+    /// Returns the items whose boxes overlap or touch `sphere`, in no particular order.
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<usize> {
+        self.query(|bounds| bounds.intersects_sphere(sphere))
+    }
+
+    /// Returns every pair of items whose boxes overlap or touch, each as `(i, j)` with `i < j`
+    /// and in no particular order: the candidate pairs of a collision broadphase. Only pairs of
+    /// boxes that overlap are visited, rather than every pair of items.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::spatial::{Bvh, BvhSplit};
+    /// use candle::geometry::Aabb3;
+    /// use candle::math::Vec3;
+    /// let bodies: Vec<Aabb3> = [0.0, 0.5, 1.5, 10.0].iter().map(|&x| {
+    ///     Aabb3::new(Vec3::new(x, 0.0, 0.0), Vec3::new(x + 1.0, 1.0, 1.0))
+    /// }).collect();
+    /// let mut pairs = Bvh::new(&bodies, BvhSplit::Median).overlapping_pairs();
+    /// pairs.sort();
+    /// assert_eq!(pairs, vec![(0, 1), (1, 2)]);
+    /// ```
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        if self.nodes.is_empty() {
+            return pairs;
+        }
+        let mut stack = vec![(0, 0)];
+        while let Some((first, second)) = stack.pop() {
+            if first != second && !self.nodes[first].bounds.intersects(&self.nodes[second].bounds) {
+                continue;
+            }
+            match (self.nodes[first].contents, self.nodes[second].contents) {
+                (Contents::Leaf { start, end }, Contents::Leaf { start: other_start, end: other_end }) => {
+                    for (offset, &i) in self.items[start..end].iter().enumerate() {
+                        // A leaf against itself only pairs each item with the ones after it.
+                        let others = if first == second { &self.items[start + offset + 1..end] } else { &self.items[other_start..other_end] };
+                        for &j in others {
+                            if self.bounds[i].intersects(&self.bounds[j]) {
+                                pairs.push((i.min(j), i.max(j)));
+                            }
+                        }
+                    }
+                }
+                (Contents::Branch { left, right }, _) if first == second => {
+                    stack.extend([(left, left), (right, right), (left, right)]);
+                }
+                (Contents::Branch { left, right }, Contents::Leaf { .. }) => stack.extend([(left, second), (right, second)]),
+                (_, Contents::Branch { left, right }) => stack.extend([(first, left), (first, right)]),
+            }
+        }
+        pairs
+    }
+
+    /// Returns the items whose boxes pass `overlaps`, descending only into the boxes that pass
+    /// it too.
+    fn query<F>(&self, overlaps: F) -> Vec<usize>
+        where F: Fn(&Aabb3) -> bool
+    {
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = if self.nodes.is_empty() { Vec::new() } else { vec![0] };
+        while let Some(node) = stack.pop() {
+            if !overlaps(&self.nodes[node].bounds) {
+                continue;
+            }
+            match self.nodes[node].contents {
+                Contents::Leaf { start, end } => {
+                    found.extend(self.items[start..end].iter().filter(|&&item| overlaps(&self.bounds[item])));
+                }
+                Contents::Branch { left, right } => stack.extend([left, right]),
+            }
+        }
+        found
+    }
+
+    /// Returns the box enclosing the items or children of `node`.
+    fn fit(&self, node: usize) -> Aabb3 {
+        match self.nodes[node].contents {
+            Contents::Leaf { start, end } => enclose(self.items[start..end].iter().map(|&item| self.bounds[item])),
+            Contents::Branch { left, right } => self.nodes[left].bounds.union(&self.nodes[right].bounds),
+        }
+    }
+
+    /// Builds the subtree over `items[start..end]`, returning its root.
+    fn build(&mut self, start: usize, end: usize, parent: Option<usize>, split: BvhSplit) -> usize {
+        let node = self.nodes.len();
+        let bounds = enclose(self.items[start..end].iter().map(|&item| self.bounds[item]));
+        self.nodes.push(Node { bounds, parent, contents: Contents::Leaf { start, end } });
+        if end - start <= MAX_LEAF_ITEMS {
+            for &item in &self.items[start..end] {
+                self.leaves[item] = node;
+            }
+            return node;
+        }
+
+        let centers = enclose(self.items[start..end].iter().map(|&item| {
+            let center = self.bounds[item].center();
+            Aabb3::new(center, center)
+        }));
+        let extent = centers.size();
+        let axis = if extent.x >= extent.y && extent.x >= extent.z { 0 } else if extent.y >= extent.z { 1 } else { 2 };
+        let middle = match split {
+            BvhSplit::SurfaceArea => self.partition_by_area(start, end, axis, &centers),
+            BvhSplit::Median => None,
+        };
+        let middle = middle.unwrap_or_else(|| {
+            let middle = start + (end - start) / 2;
+            let bounds = &self.bounds;
+            self.items[start..end].select_nth_unstable_by(middle - start, |&p, &q| {
+                bounds[p].center().coordinate(axis).total_cmp(&bounds[q].center().coordinate(axis))
+            });
+            middle
+        });
+
+        let left = self.build(start, middle, Some(node), split);
+        let right = self.build(middle, end, Some(node), split);
+        self.nodes[node].contents = Contents::Branch { left, right };
+        node
+    }
+
+    /// Sorts the items of `items[start..end]` into bins by their centers along `axis`, and
+    /// partitions them at the bin boundary with the least estimated cost, returning where the
+    /// second part starts. Returns `None` if the centers don't spread along the axis.
+    fn partition_by_area(&mut self, start: usize, end: usize, axis: usize, centers: &Aabb3) -> Option<usize> {
+        let (low, high) = (centers.min.coordinate(axis), centers.max.coordinate(axis));
+        if high <= low {
+            return None;
+        }
+        let bounds = &self.bounds;
+        let bin = |item: usize| (((bounds[item].center().coordinate(axis) - low) / (high - low) * BINS as f64) as usize).min(BINS - 1);
+        let mut bins: Vec<(usize, Option<Aabb3>)> = vec![(0, None); BINS];
+        for &item in &self.items[start..end] {
+            let (count, enclosing) = &mut bins[bin(item)];
+            *count += 1;
+            *enclosing = Some(enclosing.map_or(bounds[item], |enclosing| enclosing.union(&bounds[item])));
+        }
+
+        // The cost of splitting after each bin, from the counts and areas on either side.
+        let below = costs(bins.iter());
+        let mut above = costs(bins.iter().rev());
+        above.reverse();
+        let split = (1..BINS).min_by(|&p, &q| (below[p - 1] + above[p]).total_cmp(&(below[q - 1] + above[q])))?;
+
+        let mut middle = start;
+        for index in start..end {
+            if bin(self.items[index]) < split {
+                self.items.swap(index, middle);
+                middle += 1;
+            }
+        }
+        if middle == start || middle == end { None } else { Some(middle) }
+    }
+}
+
+/// Returns, for each bin in turn, the number of items in it and the bins before it times the
+/// surface area of the box enclosing them.
+fn costs<'a, I>(bins: I) -> Vec<f64>
+    where I: Iterator<Item = &'a (usize, Option<Aabb3>)>
+{
+    let mut count = 0;
+    let mut total: Option<Aabb3> = None;
+    bins.map(|&(items, enclosing)| {
+        count += items;
+        total = match (total, enclosing) {
+            (Some(total), Some(enclosing)) => Some(total.union(&enclosing)),
+            (total, enclosing) => total.or(enclosing),
+        };
+        count as f64 * total.map_or(0.0, |total| surface_area(&total))
+    }).collect()
+}
+
+/// Returns the box enclosing all the boxes, which must not be empty.
+fn enclose<I>(boxes: I) -> Aabb3
+    where I: Iterator<Item = Aabb3>
+{
+    boxes.reduce(|total, next| total.union(&next)).expect("no boxes to enclose")
+}
+
+fn surface_area(aabb: &Aabb3) -> f64 {
+    let size = aabb.size();
+    2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+}
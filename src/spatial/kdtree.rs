@@ -0,0 +1,228 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use math::{Vec2, Vec3};
+
+/// A point a `KdTree` can index: a fixed number of coordinates, split on one axis at a time.
+pub trait KdPoint: Copy {
+    /// The number of coordinates.
+    const DIMENSIONS: usize;
+
+    /// Returns the coordinate along `axis`, from 0 to `DIMENSIONS - 1`.
+    fn coordinate(&self, axis: usize) -> f64;
+
+    /// Returns the squared Euclidean distance to `other`.
+    fn distance_squared(&self, other: &Self) -> f64 {
+        (0..Self::DIMENSIONS).map(|axis| (self.coordinate(axis) - other.coordinate(axis)).powi(2)).sum()
+    }
+}
+
+impl KdPoint for Vec2 {
+    const DIMENSIONS: usize = 2;
+
+    fn coordinate(&self, axis: usize) -> f64 {
+        if axis == 0 { self.x } else { self.y }
+    }
+}
+
+impl KdPoint for Vec3 {
+    const DIMENSIONS: usize = 3;
+
+    fn coordinate(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+}
+
+/// A k-d tree over a fixed set of points, answering nearest neighbour and radius queries in
+/// about O(log n) time for n points, instead of the O(n) of checking every point. Queries
+/// return the indices of the points in the slice the tree was built from, so they can index
+/// the entities the points belong to.
+///
+/// The tree is built once, in O(n log n) time, by splitting the points at the median along the
+/// axis they spread the most over, so clustered points still give a balanced tree. Rebuild it
+/// when the points move; for items that move a few at a time, see `QuadTree`.
+///
+/// # Examples
+/// ```
+/// use candle::spatial::KdTree;
+/// use candle::math::Vec2;
+/// let enemies = [Vec2::new(0.0, 5.0), Vec2::new(3.0, 1.0), Vec2::new(-4.0, -4.0), Vec2::new(8.0, 8.0)];
+/// let tree = KdTree::new(&enemies);
+/// let player = Vec2::new(1.0, 1.0);
+/// assert_eq!(tree.nearest(&player), Some((1, 2.0)));
+/// assert_eq!(tree.k_nearest(&player, 2), vec![(1, 2.0), (0, 17f64.sqrt())]);
+/// let mut in_range = tree.within_radius(&player, 8.0);
+/// in_range.sort();
+/// assert_eq!(in_range, vec![0, 1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    /// The points, ordered so that every subtree is a range whose middle point splits it.
+    points: Vec<P>,
+    /// The index of each point in the slice the tree was built from.
+    indices: Vec<usize>,
+    /// The axis each point splits its subtree on.
+    axes: Vec<usize>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+
+    /// Builds a KdTree over the points.
+    pub fn new(points: &[P]) -> KdTree<P> {
+        let mut entries: Vec<(P, usize)> = points.iter().copied().zip(0..).collect();
+        let mut axes = vec![0; points.len()];
+        build(&mut entries, &mut axes);
+        let (points, indices) = entries.into_iter().unzip();
+        KdTree { points, indices, axes }
+    }
+
+    /// Returns the number of points in the tree.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the tree holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the index of the point nearest to `point` and its distance, or `None` if the
+    /// tree is empty. Ties go to either point.
+    pub fn nearest(&self, point: &P) -> Option<(usize, f64)> {
+        self.approximate_nearest(point, 0.0)
+    }
+
+    /// Returns the indices of the `k` points nearest to `point` with their distances, nearest
+    /// first, or all the points if there are fewer.
+    pub fn k_nearest(&self, point: &P, k: usize) -> Vec<(usize, f64)> {
+        let mut nearest: BinaryHeap<Candidate> = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(0, self.points.len(), point, &mut |position, distance| {
+                if nearest.len() < k || distance < nearest.peek().map_or(f64::INFINITY, |farthest| farthest.distance) {
+                    nearest.push(Candidate { distance, position });
+                    if nearest.len() > k {
+                        nearest.pop();
+                    }
+                }
+                if nearest.len() < k { f64::INFINITY } else { nearest.peek().map_or(f64::INFINITY, |farthest| farthest.distance) }
+            });
+        }
+        nearest.into_sorted_vec().into_iter()
+            .map(|candidate| (self.indices[candidate.position], candidate.distance.sqrt()))
+            .collect()
+    }
+
+    /// Returns the indices of the points within `radius` of `point`, boundary included, in no
+    /// particular order.
+    pub fn within_radius(&self, point: &P, radius: f64) -> Vec<usize> {
+        let mut found = Vec::new();
+        let bound = radius * radius;
+        // Subtrees are searched while the bound is above the distance to their splitting plane.
+        let reach = bound.next_up();
+        self.search(0, self.points.len(), point, &mut |position, distance| {
+            if distance <= bound {
+                found.push(self.indices[position]);
+            }
+            reach
+        });
+        found
+    }
+
+    /// Returns the index of a point at most `1 + epsilon` times as far from `point` as the
+    /// nearest one, and its distance, or `None` if the tree is empty. Allowing some slack skips
+    /// the subtrees that could only hold slightly nearer points, which pays off in higher
+    /// dimensions and for queries far from all points; an `epsilon` of zero gives the nearest.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::spatial::KdTree;
+    /// use candle::math::Vec2;
+    /// let points: Vec<Vec2> = (0..100).map(|i| Vec2::new((i % 10) as f64, (i / 10) as f64)).collect();
+    /// let tree = KdTree::new(&points);
+    /// let query = Vec2::new(4.4, 20.0);
+    /// let (_, exact) = tree.nearest(&query).unwrap();
+    /// let (index, distance) = tree.approximate_nearest(&query, 0.5).unwrap();
+    /// assert!(distance <= exact * 1.5);
+    /// assert_eq!((points[index] - query).length(), distance);
+    /// ```
+    pub fn approximate_nearest(&self, point: &P, epsilon: f64) -> Option<(usize, f64)> {
+        let shrink = (1.0 + epsilon.max(0.0)).powi(2);
+        let mut best: Option<(usize, f64)> = None;
+        self.search(0, self.points.len(), point, &mut |position, distance| {
+            if best.is_none_or(|(_, nearest)| distance < nearest) {
+                best = Some((position, distance));
+            }
+            best.map_or(f64::INFINITY, |(_, nearest)| nearest / shrink)
+        });
+        best.map(|(position, distance)| (self.indices[position], distance.sqrt()))
+    }
+
+    /// Visits the points of the subtree in `start..end` near `point` first, handing `visit`
+    /// each point's position and squared distance. `visit` returns the squared distance bound
+    /// past which the rest of a subtree is skipped.
+    fn search<F>(&self, start: usize, end: usize, point: &P, visit: &mut F) -> f64
+        where F: FnMut(usize, f64) -> f64
+    {
+        if start >= end {
+            return f64::INFINITY;
+        }
+        let middle = start + (end - start) / 2;
+        let bound = visit(middle, point.distance_squared(&self.points[middle]));
+        let axis = self.axes[middle];
+        let offset = point.coordinate(axis) - self.points[middle].coordinate(axis);
+        let (near, far) = if offset < 0.0 { ((start, middle), (middle + 1, end)) } else { ((middle + 1, end), (start, middle)) };
+        let bound = bound.min(self.search(near.0, near.1, point, visit));
+        if offset * offset < bound {
+            bound.min(self.search(far.0, far.1, point, visit))
+        } else {
+            bound
+        }
+    }
+}
+
+/// Orders the points so that the middle one splits the others at the median along their
+/// widest axis, then the halves on either side likewise.
+fn build<P: KdPoint>(entries: &mut [(P, usize)], axes: &mut [usize]) {
+    if entries.len() <= 1 {
+        return;
+    }
+    let spread = |axis: usize| {
+        let (low, high) = entries.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), (point, _)| {
+            (low.min(point.coordinate(axis)), high.max(point.coordinate(axis)))
+        });
+        high - low
+    };
+    let axis = (0..P::DIMENSIONS).max_by(|&p, &q| spread(p).total_cmp(&spread(q))).unwrap_or(0);
+    let middle = entries.len() / 2;
+    entries.select_nth_unstable_by(middle, |p, q| p.0.coordinate(axis).total_cmp(&q.0.coordinate(axis)));
+    axes[middle] = axis;
+    let (below, above) = entries.split_at_mut(middle);
+    let (below_axes, above_axes) = axes.split_at_mut(middle);
+    build(below, below_axes);
+    build(&mut above[1..], &mut above_axes[1..]);
+}
+
+/// A point kept by `KdTree::k_nearest`, ordered so that the `BinaryHeap` pops the farthest
+/// first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f64,
+    position: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.position.cmp(&other.position))
+    }
+}
@@ -0,0 +1,7 @@
+mod quadtree;
+mod kdtree;
+mod bvh;
+
+pub use self::quadtree::QuadTree;
+pub use self::kdtree::{KdTree, KdPoint};
+pub use self::bvh::{Bvh, BvhSplit};
@@ -0,0 +1,441 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::{Index, IndexMut};
+use math::Vec2;
+use geometry::{Rect, Circle, Ray2};
+use geometry::intersect;
+use collections::{Arena, Handle};
+
+/// The number of items a leaf holds before it splits into four.
+const MAX_ITEMS: usize = 8;
+/// The depth past which leaves no longer split, however many items they hold.
+const MAX_DEPTH: usize = 12;
+
+/// A quadtree storing items by their bounding rectangle, or by a point, for finding the items
+/// in a region, near a point or along a ray without testing every one of them.
+///
+/// Each node covers a quarter of its parent, and items sit in the smallest node that contains
+/// their bounds: a leaf splits when it holds too many items, and collapses back once its
+/// subtree holds few enough. Items straddling the edges between nodes stay in the larger node,
+/// and items outside the tree's bounds in the root, so they are still found, only without the
+/// speedup. Items are referred to by the `Handle` returned on insertion.
+///
+/// # Examples
+/// ```
+/// use candle::spatial::QuadTree;
+/// use candle::geometry::{Rect, Circle};
+/// use candle::math::Vec2;
+/// let mut tree = QuadTree::new(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 100.0)));
+/// let player = tree.insert_point(Vec2::new(10.0, 10.0), "player");
+/// let crate_ = tree.insert(Rect::new(Vec2::new(12.0, 8.0), Vec2::new(14.0, 10.0)), "crate");
+/// let door = tree.insert(Rect::new(Vec2::new(80.0, 0.0), Vec2::new(82.0, 6.0)), "door");
+///
+/// assert_eq!(tree.query_circle(&Circle::new(Vec2::new(10.0, 10.0), 3.0)).len(), 2);
+/// tree.update_point(player, Vec2::new(78.0, 3.0));
+/// let mut near_door = tree.query_rect(&Rect::new(Vec2::new(75.0, 0.0), Vec2::new(85.0, 10.0)));
+/// near_door.sort_by_key(|handle| tree[*handle]);
+/// assert_eq!(near_door, vec![door, player]);
+/// assert_eq!(tree.nearest(&Vec2::new(20.0, 9.0)), Some((crate_, 6.0)));
+/// assert_eq!(tree.remove(crate_), Some("crate"));
+/// assert_eq!(tree.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuadTree<T> {
+    values: Arena<T>,
+    /// The bounds of each item and the node holding it, by handle index.
+    entries: Vec<Entry>,
+    nodes: Vec<Node<T>>,
+    /// The first of each run of four nodes freed by collapsing, for reuse.
+    free: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    bounds: Rect,
+    node: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    bounds: Rect,
+    depth: usize,
+    parent: Option<usize>,
+    /// The first of the four children, which are stored in a row.
+    children: Option<usize>,
+    items: Vec<Handle<T>>,
+    /// The number of items in the node and all its descendants.
+    count: usize,
+}
+
+impl<T> Node<T> {
+
+    fn new(bounds: Rect, depth: usize, parent: Option<usize>) -> Node<T> {
+        Node { bounds, depth, parent, children: None, items: Vec::new(), count: 0 }
+    }
+}
+
+impl<T> QuadTree<T> {
+
+    /// Allocates a new, empty QuadTree subdividing `bounds`.
+    pub fn new(bounds: Rect) -> QuadTree<T> {
+        QuadTree { values: Arena::new(), entries: Vec::new(), nodes: vec![Node::new(bounds, 0, None)], free: Vec::new() }
+    }
+
+    /// Returns the region the tree subdivides.
+    pub fn bounds(&self) -> Rect {
+        self.nodes[0].bounds
+    }
+
+    /// Returns the number of items in the tree.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the tree holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Stores `value` with the given bounds and returns its handle.
+    pub fn insert(&mut self, bounds: Rect, value: T) -> Handle<T> {
+        let handle = self.values.insert(value);
+        let entry = Entry { bounds, node: 0 };
+        if handle.index() < self.entries.len() {
+            self.entries[handle.index()] = entry;
+        } else {
+            self.entries.push(entry);
+        }
+        self.attach(handle);
+        handle
+    }
+
+    /// Stores `value` at a single point and returns its handle.
+    pub fn insert_point(&mut self, point: Vec2, value: T) -> Handle<T> {
+        self.insert(Rect::new(point, point), value)
+    }
+
+    /// Removes and returns the value of `handle`, or `None` if it was already removed.
+    pub fn remove(&mut self, handle: Handle<T>) -> Option<T> {
+        if !self.values.contains(handle) {
+            return None;
+        }
+        self.detach(handle);
+        self.values.remove(handle)
+    }
+
+    /// Moves the item of `handle` to new bounds, returning false if it was removed. Items that
+    /// stay within their node are only updated in place, so small moves from frame to frame are
+    /// cheap.
+    pub fn update(&mut self, handle: Handle<T>, bounds: Rect) -> bool {
+        if !self.values.contains(handle) {
+            return false;
+        }
+        let node = self.entries[handle.index()].node;
+        self.entries[handle.index()].bounds = bounds;
+        if (node == 0 || self.nodes[node].bounds.contains_rect(&bounds)) && self.place(node, &bounds) == node {
+            return true;
+        }
+        self.detach(handle);
+        self.attach(handle);
+        true
+    }
+
+    /// Moves the item of `handle` to a single point, returning false if it was removed.
+    pub fn update_point(&mut self, handle: Handle<T>, point: Vec2) -> bool {
+        self.update(handle, Rect::new(point, point))
+    }
+
+    /// Returns the value of `handle`, or `None` if it was removed.
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        self.values.get(handle)
+    }
+
+    /// Returns the value of `handle` mutably, or `None` if it was removed.
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        self.values.get_mut(handle)
+    }
+
+    /// Returns the bounds of the item of `handle`, or `None` if it was removed.
+    pub fn item_bounds(&self, handle: Handle<T>) -> Option<Rect> {
+        self.values.get(handle).map(|_| self.entries[handle.index()].bounds)
+    }
+
+    /// Iterates over the handles and values of the items, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.values.iter()
+    }
+
+    /// Removes every item, invalidating all handles.
+    pub fn clear(&mut self) {
+        let bounds = self.bounds();
+        self.values.clear();
+        self.nodes = vec![Node::new(bounds, 0, None)];
+        self.free.clear();
+    }
+
+    /// Returns the handles of the items whose bounds overlap or touch `rect`, in no particular
+    /// order.
+    pub fn query_rect(&self, rect: &Rect) -> Vec<Handle<T>> {
+        self.query(|bounds| bounds.intersects(rect))
+    }
+
+    /// Returns the handles of the items whose bounds overlap or touch `circle`, in no particular
+    /// order.
+    pub fn query_circle(&self, circle: &Circle) -> Vec<Handle<T>> {
+        self.query(|bounds| circle.intersects_rect(bounds))
+    }
+
+    /// Returns the item whose bounds are nearest to `point`, with their distance, zero if
+    /// `point` is inside them, or `None` if the tree is empty. Nodes are searched nearest first,
+    /// skipping those farther than the best item found so far.
+    pub fn nearest(&self, point: &Vec2) -> Option<(Handle<T>, f64)> {
+        let mut best: Option<(Handle<T>, f64)> = None;
+        let mut pending = BinaryHeap::new();
+        pending.push(Pending { distance: 0.0, node: 0 });
+        while let Some(Pending { distance, node }) = pending.pop() {
+            if best.is_some_and(|(_, closest)| distance >= closest) {
+                break;
+            }
+            let node = &self.nodes[node];
+            for &handle in &node.items {
+                let distance = self.entries[handle.index()].bounds.distance(point);
+                if best.is_none_or(|(_, closest)| distance < closest) {
+                    best = Some((handle, distance));
+                }
+            }
+            for child in node.children.into_iter().flat_map(|first| first..first + 4) {
+                if self.nodes[child].count > 0 {
+                    pending.push(Pending { distance: self.nodes[child].bounds.distance(point), node: child });
+                }
+            }
+        }
+        best
+    }
+
+    /// Casts `ray` through the tree and returns the nearest item it hits, with the ray parameter
+    /// of the hit, or `None` if it hits nothing. The items whose bounds the ray passes through
+    /// are handed to `hit`, nearest nodes first, which returns the ray parameter where the ray
+    /// hits the item itself, or `None` if it misses, so items can be tested against their exact
+    /// shape. Nodes beyond the nearest hit so far are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use candle::spatial::QuadTree;
+    /// use candle::geometry::{Rect, Ray2};
+    /// use candle::geometry::intersect;
+    /// use candle::math::Vec2;
+    /// let mut tree = QuadTree::new(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(64.0, 64.0)));
+    /// let walls: Vec<_> = (0..8).map(|i| {
+    ///     let x = 4.0 + i as f64 * 8.0;
+    ///     tree.insert(Rect::new(Vec2::new(x, 30.0), Vec2::new(x + 1.0, 34.0)), i)
+    /// }).collect();
+    /// let ray = Ray2::new(Vec2::new(0.0, 32.0), Vec2::new(1.0, 0.0));
+    /// let first = tree.raycast(&ray, |_, _, bounds| intersect::ray_rect(&ray, bounds).map(|hit| hit.t));
+    /// assert_eq!(first, Some((walls[0], 4.0)));
+    /// // Skip the walls with an even number.
+    /// let odd = tree.raycast(&ray, |_, &i, bounds| if i % 2 == 1 { intersect::ray_rect(&ray, bounds).map(|hit| hit.t) } else { None });
+    /// assert_eq!(odd, Some((walls[1], 12.0)));
+    /// ```
+    pub fn raycast<F>(&self, ray: &Ray2, mut hit: F) -> Option<(Handle<T>, f64)>
+        where F: FnMut(Handle<T>, &T, &Rect) -> Option<f64>
+    {
+        let mut best: Option<(Handle<T>, f64)> = None;
+        let mut pending = BinaryHeap::new();
+        pending.push(Pending { distance: 0.0, node: 0 });
+        while let Some(Pending { distance, node }) = pending.pop() {
+            if best.is_some_and(|(_, nearest)| distance > nearest) {
+                break;
+            }
+            let node = &self.nodes[node];
+            for &handle in &node.items {
+                let bounds = &self.entries[handle.index()].bounds;
+                if ray_enters(ray, bounds).is_none_or(|enter| best.is_some_and(|(_, nearest)| enter > nearest)) {
+                    continue;
+                }
+                if let Some(t) = hit(handle, &self.values[handle], bounds) {
+                    if t >= 0.0 && best.is_none_or(|(_, nearest)| t < nearest) {
+                        best = Some((handle, t));
+                    }
+                }
+            }
+            for child in node.children.into_iter().flat_map(|first| first..first + 4) {
+                if self.nodes[child].count > 0 {
+                    if let Some(enter) = ray_enters(ray, &self.nodes[child].bounds) {
+                        pending.push(Pending { distance: enter, node: child });
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the handles of the items whose bounds pass `overlaps`, visiting only the nodes
+    /// whose bounds pass it too.
+    fn query<F>(&self, overlaps: F) -> Vec<Handle<T>>
+        where F: Fn(&Rect) -> bool
+    {
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            found.extend(node.items.iter().filter(|handle| overlaps(&self.entries[handle.index()].bounds)));
+            for child in node.children.into_iter().flat_map(|first| first..first + 4) {
+                if self.nodes[child].count > 0 && overlaps(&self.nodes[child].bounds) {
+                    stack.push(child);
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns the deepest node under `start` whose bounds contain `bounds`, or `start` itself.
+    fn place(&self, start: usize, bounds: &Rect) -> usize {
+        let mut node = start;
+        while let Some(first) = self.nodes[node].children {
+            match (first..first + 4).find(|&child| self.nodes[child].bounds.contains_rect(bounds)) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        node
+    }
+
+    /// Adds the item of `handle` to the node its bounds fit in, splitting the node if it grows
+    /// too large.
+    fn attach(&mut self, handle: Handle<T>) {
+        let node = self.place(0, &self.entries[handle.index()].bounds);
+        self.entries[handle.index()].node = node;
+        self.nodes[node].items.push(handle);
+        let mut ancestor = Some(node);
+        while let Some(index) = ancestor {
+            self.nodes[index].count += 1;
+            ancestor = self.nodes[index].parent;
+        }
+        self.split(node);
+    }
+
+    /// Takes the item of `handle` out of its node, collapsing the largest subtree left with few
+    /// enough items into a leaf.
+    fn detach(&mut self, handle: Handle<T>) {
+        let node = self.entries[handle.index()].node;
+        let items = &mut self.nodes[node].items;
+        let position = items.iter().position(|&item| item == handle).expect("item missing from its node");
+        items.swap_remove(position);
+        let mut collapse = None;
+        let mut ancestor = Some(node);
+        while let Some(index) = ancestor {
+            self.nodes[index].count -= 1;
+            if self.nodes[index].children.is_some() && self.nodes[index].count <= MAX_ITEMS {
+                collapse = Some(index);
+            }
+            ancestor = self.nodes[index].parent;
+        }
+        if let Some(index) = collapse {
+            self.collapse(index);
+        }
+    }
+
+    /// Splits a leaf holding too many items into four, moving down the items that fit in a
+    /// child, and so on while the children hold too many.
+    fn split(&mut self, node: usize) {
+        if self.nodes[node].children.is_some() || self.nodes[node].items.len() <= MAX_ITEMS || self.nodes[node].depth >= MAX_DEPTH {
+            return;
+        }
+        let (bounds, depth) = (self.nodes[node].bounds, self.nodes[node].depth + 1);
+        let center = bounds.center();
+        let quarters = [
+            Rect::new(bounds.min, center),
+            Rect::new(Vec2::new(center.x, bounds.min.y), Vec2::new(bounds.max.x, center.y)),
+            Rect::new(Vec2::new(bounds.min.x, center.y), Vec2::new(center.x, bounds.max.y)),
+            Rect::new(center, bounds.max),
+        ];
+        let first = match self.free.pop() {
+            Some(first) => first,
+            None => {
+                self.nodes.extend(quarters.iter().map(|&quarter| Node::new(quarter, depth, Some(node))));
+                self.nodes.len() - 4
+            }
+        };
+        for (child, &quarter) in quarters.iter().enumerate() {
+            self.nodes[first + child] = Node::new(quarter, depth, Some(node));
+        }
+        self.nodes[node].children = Some(first);
+
+        let items = std::mem::take(&mut self.nodes[node].items);
+        for handle in items {
+            let target = self.place(node, &self.entries[handle.index()].bounds);
+            self.entries[handle.index()].node = target;
+            self.nodes[target].items.push(handle);
+            if target != node {
+                self.nodes[target].count += 1;
+            }
+        }
+        for child in first..first + 4 {
+            self.split(child);
+        }
+    }
+
+    /// Turns a node back into a leaf holding all the items of its subtree.
+    fn collapse(&mut self, node: usize) {
+        let mut stack: Vec<usize> = self.nodes[node].children.take().into_iter().collect();
+        while let Some(first) = stack.pop() {
+            self.free.push(first);
+            for child in first..first + 4 {
+                let items = std::mem::take(&mut self.nodes[child].items);
+                for &handle in &items {
+                    self.entries[handle.index()].node = node;
+                }
+                self.nodes[node].items.extend(items);
+                stack.extend(self.nodes[child].children.take());
+            }
+        }
+    }
+}
+
+impl<T> Index<Handle<T>> for QuadTree<T> {
+    type Output = T;
+
+    /// Returns the value of `handle`, panicking if it was removed.
+    fn index(&self, handle: Handle<T>) -> &T {
+        &self.values[handle]
+    }
+}
+
+impl<T> IndexMut<Handle<T>> for QuadTree<T> {
+    /// Returns the value of `handle` mutably, panicking if it was removed.
+    fn index_mut(&mut self, handle: Handle<T>) -> &mut T {
+        &mut self.values[handle]
+    }
+}
+
+/// Returns the ray parameter at which `ray` enters `rect`, zero if it starts inside, or `None`
+/// if it misses.
+fn ray_enters(ray: &Ray2, rect: &Rect) -> Option<f64> {
+    if rect.contains(&ray.origin) {
+        Some(0.0)
+    } else {
+        intersect::ray_rect(ray, rect).map(|hit| hit.t)
+    }
+}
+
+/// A node queued by `QuadTree::nearest` or `QuadTree::raycast`, ordered so that the
+/// `BinaryHeap` pops the nearest first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pending {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Pending) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Pending) -> Ordering {
+        other.distance.total_cmp(&self.distance).then(other.node.cmp(&self.node))
+    }
+}